@@ -0,0 +1,889 @@
+pub mod test;
+
+use crate::config;
+use crate::environments;
+use crate::environments::acrobot;
+use crate::environments::cart_pole;
+use crate::environments::mountain_car;
+use crate::environments::pendulum;
+use crate::environments::snake;
+use crate::environments::twenty_forty_eight;
+use crate::environments::Environment;
+use crate::evolution::benchmark;
+use crate::evolution::cem;
+use crate::evolution::differential_evolution;
+use crate::evolution::es;
+use crate::evolution::hall_of_fame;
+use crate::evolution::metrics;
+use crate::evolution::pepg;
+use crate::evolution::snes;
+use crate::evolution::xnes;
+use crate::evolution::Evolve;
+use crate::games;
+use crate::matrix;
+use crate::models;
+use crate::models::connect_four;
+use crate::models::mlp;
+use crate::models::othello;
+use crate::models::tic_tac_toe;
+use crate::models::xor;
+use crate::models::Model;
+use crate::nn::activations;
+use crate::nn::activations::Activate;
+use crate::nn::layers::conv2d;
+use crate::nn::layers::res2d;
+use crate::optim::adam;
+use crate::play;
+use crate::plot;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Instant;
+
+pub const USAGE: &str = "usage: neuroevolution <command> [--option value ...]
+
+commands
+  train    [--config <file>] [--task <task>] [--model <model>] [--hidden 16,16]
+           [--activation tanh] [--norm layer_norm|batch_norm] [--optimizer <optimizer>]
+           [--sigma 0.1] [--population 20] [--learning-rate 0.05] [--generations 100]
+           [--seed 0] [--episodes 3]
+           [--checkpoint-every 0] [--state-every 10] [--hall-of-fame 10] [--output runs/<task>]
+  train    --resume <run dir> [--generations <more>]
+  eval     --checkpoint <dir> [--config <file>] [--task <task>] [--seed 0] [--episodes 3]
+  inspect  [--checkpoint <dir>] [--config <file>] [--task <task>] [--model <model>]
+  bench    --model <model> [--iterations 1000]
+  play     --checkpoint <dir> [--first human|model]
+  plot     --run <run dir> [--bins 30] [--format png|pgm]
+
+configs are toml, or json when the file ends in .json, and options override them
+plot writes fitness.svg from metrics.csv and a weight histogram per layer to <run dir>/plots,
+and for the game models images of the conv filters and of their outputs on the empty board
+eval and inspect read the config.toml or config.json a training run writes next to its model
+
+tasks       xor, tic_tac_toe, connect_four, othello, cart_pole, pendulum,
+            mountain_car, acrobot, snake, twenty_forty_eight
+models      xor, tic_tac_toe, connect_four, othello, mlp or linear (environment tasks only)
+optimizers  es, pepg, snes, xnes, cem, de, jade";
+
+// a command followed by --key value options
+pub struct Args {
+    pub command: String,
+    pub options: HashMap<String, String>,
+}
+
+pub fn parse(args: &[String]) -> Result<Args, String> {
+    let command = args.first().ok_or("missing command")?.clone();
+
+    let mut options = HashMap::new();
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        let key = arg
+            .strip_prefix("--")
+            .ok_or(format!("expected an option, found {}", arg))?;
+        let (key, value) = match key.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (
+                key.to_string(),
+                rest.next()
+                    .ok_or(format!("missing value for --{}", key))?
+                    .clone(),
+            ),
+        };
+        if options.insert(key.clone(), value).is_some() {
+            return Err(format!("--{} is given twice", key));
+        }
+    }
+
+    Ok(Args { command, options })
+}
+
+// rejects options the command does not know, so typos are not silently ignored
+pub fn check_options(a: &Args, allowed: &[&str]) -> Result<(), String> {
+    let mut keys: Vec<&String> = a.options.keys().collect();
+    keys.sort();
+    for key in keys {
+        if !allowed.contains(&key.as_str()) {
+            return Err(format!("unknown option --{} for {}", key, a.command));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn require<'a>(a: &'a Args, key: &str) -> Result<&'a str, String> {
+    a.options
+        .get(key)
+        .map(|value| value.as_str())
+        .ok_or(format!("{} needs --{}", a.command, key))
+}
+
+pub fn get<T: FromStr>(a: &Args, key: &str, default: T) -> Result<T, String> {
+    match a.options.get(key) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("invalid value {} for --{}", value, key)),
+        None => Ok(default),
+    }
+}
+
+// config from --config, or from the run directory of --checkpoint, or the defaults of --task,
+// with any option given on the command line taking precedence
+pub fn resolve_config(a: &Args) -> Result<config::Config, String> {
+    // a resumed run continues with its own config, only the number of generations may grow
+    if let Some(dir_name) = a.options.get("resume") {
+        check_options(a, &["resume", "generations"])?;
+        let mut b = config::load(
+            &config::find(dir_name).ok_or(format!("no config in run directory {}", dir_name))?,
+        )?;
+        b.generations = get(a, "generations", b.generations)?;
+        b.output = Some(dir_name.clone());
+        return config::resolve(&b);
+    }
+
+    let found = a.options.get("checkpoint").and_then(|checkpoint| {
+        Path::new(checkpoint)
+            .parent()
+            .and_then(|parent| config::find(parent.to_str()?))
+    });
+    let mut b = match (a.options.get("config"), found) {
+        (Some(file_name), _) => config::load(file_name)?,
+        (None, Some(file_name)) => config::load(&file_name)?,
+        (None, None) => config::new(match (a.options.get("task"), a.options.get("model")) {
+            (Some(task), _) => task,
+            // game models only fit the task of the same name
+            (None, Some(model)) if model != "mlp" && model != "linear" => model,
+            _ => return Err(format!("{} needs --task", a.command)),
+        }),
+    };
+
+    if let Some(task) = a.options.get("task") {
+        if *task != b.task {
+            b.task = task.clone();
+            b.model = None;
+        }
+    }
+    if let Some(model) = a.options.get("model") {
+        b.model = Some(match model.as_str() {
+            "xor" => config::ModelConfig::Xor,
+            "tic_tac_toe" => config::ModelConfig::TicTacToe,
+            "connect_four" => config::ModelConfig::ConnectFour,
+            "othello" => config::ModelConfig::Othello,
+            "linear" | "mlp" => config::ModelConfig::Mlp {
+                hidden: vec![],
+                output_activation: "tanh".to_string(),
+            },
+            _ => return Err(format!("unknown model {}", model)),
+        });
+    }
+    if let Some(hidden) = a.options.get("hidden") {
+        if b.model.is_none() {
+            b.model = Some(config::default_model(&b.task)?);
+        }
+        let Some(config::ModelConfig::Mlp { hidden: layers, .. }) = &mut b.model else {
+            return Err("--hidden needs an mlp model".to_string());
+        };
+        *layers = vec![];
+        for outputs in hidden.split(',') {
+            layers.push(config::LayerConfig {
+                outputs: outputs
+                    .parse()
+                    .map_err(|_| format!("invalid value {} for --hidden", hidden))?,
+                activation: get(a, "activation", "tanh".to_string())?,
+                norm: a.options.get("norm").cloned(),
+            });
+        }
+    }
+    if let Some(name) = a.options.get("optimizer") {
+        b.optimizer.name = name.clone();
+    }
+    b.optimizer.sigma = get(a, "sigma", b.optimizer.sigma)?;
+    b.optimizer.learning_rate = get(a, "learning-rate", b.optimizer.learning_rate)?;
+    b.seed = get(a, "seed", b.seed)?;
+    b.population = get(a, "population", b.population)?;
+    b.generations = get(a, "generations", b.generations)?;
+    b.episodes = get(a, "episodes", b.episodes)?;
+    b.checkpoint_every = get(a, "checkpoint-every", b.checkpoint_every)?;
+    b.state_every = get(a, "state-every", b.state_every)?;
+    b.hall_of_fame = get(a, "hall-of-fame", b.hall_of_fame)?;
+    if let Some(output) = a.options.get("output") {
+        b.output = Some(output.clone());
+    }
+
+    config::resolve(&b)
+}
+
+// observations collected for the batch norm statistics of an mlp
+const NORM_OBSERVATIONS: usize = 256;
+
+// mlp of the config from the observation row to the action row of an environment
+pub fn mlp<E: Environment>(c: &config::Config, environment: &mut E) -> mlp::Mlp {
+    let Some(config::ModelConfig::Mlp {
+        hidden,
+        output_activation,
+    }) = &c.model
+    else {
+        panic!("Config is not resolved to an mlp.");
+    };
+    let (_, inputs) = environment.observation_shape();
+    let (_, outputs) = environment.action_shape();
+
+    // activation names are checked by config::resolve
+    let mut layers: Vec<(usize, activations::Activation)> = hidden
+        .iter()
+        .map(|l| (l.outputs, activations::from_name(&l.activation).unwrap()))
+        .collect();
+    layers.push((outputs, activations::from_name(output_activation).unwrap()));
+
+    let mut b = mlp::new(inputs, &layers);
+    for (i, l) in hidden.iter().enumerate() {
+        b.norms[i] = l
+            .norm
+            .as_ref()
+            .map(|name| mlp::norm_from_name(name, l.outputs).unwrap());
+    }
+    if !b
+        .norms
+        .iter()
+        .any(|n| matches!(n, Some(mlp::Norm::Batch(_))))
+    {
+        return b;
+    }
+
+    // batch norm statistics come from the model a training run starts from, playing the
+    // environment, they stay fixed while gain and bias evolve
+    let start = models::gaussian_noise(&b, &mut ChaCha8Rng::seed_from_u64(c.seed))
+        .scalar(c.optimizer.sigma as f32);
+    let mut observations = vec![environment.reset()];
+    while observations.len() < NORM_OBSERVATIONS {
+        let step = environment.step(&mlp::feedforward(&start, observations.last().unwrap()));
+        if step.done {
+            observations.push(environment.reset());
+        } else {
+            observations.push(step.observation);
+        }
+    }
+    let start = mlp::collect_statistics(&start, &observations);
+    for (norm, collected) in b.norms.iter_mut().zip(start.norms) {
+        if let (Some(mlp::Norm::Batch(n)), Some(mlp::Norm::Batch(s))) = (norm, collected) {
+            n.mean = s.mean;
+            n.variance = s.variance;
+        }
+    }
+
+    b
+}
+
+// mean return of a policy over episodes, every evaluation gets a freshly seeded environment
+// so all candidates see the same episodes
+pub fn environment_fitness<E: Environment>(
+    model: &mlp::Mlp,
+    environment: &mut E,
+    episodes: usize,
+    max_steps: usize,
+) -> f32 {
+    let mut policy = |observation: &matrix::Matrix| mlp::feedforward(model, observation);
+
+    environments::evaluate(environment, &mut policy, episodes, max_steps)
+}
+
+// whatever a command does with the model and fitness of a task
+pub trait Runner {
+    fn run<M: Model + 'static, F: Fn(&M) -> f32>(
+        &self,
+        c: &config::Config,
+        a: &Args,
+        model: M,
+        fitness: F,
+    ) -> Result<(), String>;
+}
+
+// builds the model and fitness function of a resolved config and hands them to runner
+pub fn dispatch<R: Runner>(c: &config::Config, a: &Args, runner: &R) -> Result<(), String> {
+    let seed = c.seed;
+    let episodes = c.episodes;
+
+    match c.task.as_str() {
+        "xor" => runner.run(c, a, xor::new(), benchmark::xor),
+        "tic_tac_toe" => runner.run(c, a, tic_tac_toe::new(), |m| {
+            benchmark::tic_tac_toe(m, 2 * episodes, seed)
+        }),
+        "connect_four" => runner.run(c, a, connect_four::new(), |m| {
+            benchmark::connect_four(m, 2, 2 * episodes, seed)
+        }),
+        "othello" => runner.run(c, a, othello::new(), |m| {
+            benchmark::othello(m, 2, 2 * episodes, seed)
+        }),
+        "cart_pole" => runner.run(c, a, mlp(c, &mut cart_pole::new(seed)), |m| {
+            environment_fitness(m, &mut cart_pole::new(seed), episodes, 500)
+        }),
+        "pendulum" => runner.run(c, a, mlp(c, &mut pendulum::new(seed)), |m| {
+            environment_fitness(m, &mut pendulum::new(seed), episodes, 200)
+        }),
+        "mountain_car" => runner.run(c, a, mlp(c, &mut mountain_car::new(seed)), |m| {
+            environment_fitness(m, &mut mountain_car::new(seed), episodes, 200)
+        }),
+        "acrobot" => runner.run(c, a, mlp(c, &mut acrobot::new(seed)), |m| {
+            environment_fitness(m, &mut acrobot::new(seed), episodes, 500)
+        }),
+        "snake" => runner.run(c, a, mlp(c, &mut snake::new(10, 10, seed)), |m| {
+            let mut policy = |observation: &matrix::Matrix| mlp::feedforward(m, observation);
+            snake::fitness(&mut policy, 10, 10, episodes, seed)
+        }),
+        "twenty_forty_eight" => runner.run(c, a, mlp(c, &mut twenty_forty_eight::new(seed)), |m| {
+            let mut policy = |observation: &matrix::Matrix| mlp::feedforward(m, observation);
+            twenty_forty_eight::fitness(&mut policy, episodes, seed)
+        }),
+        _ => Err(format!("unknown task {}", c.task)),
+    }
+}
+
+// optimizer of a resolved config
+pub fn optimizer<M: Model + 'static>(
+    c: &config::Config,
+    model: &M,
+) -> Result<Box<dyn Evolve<M>>, String> {
+    let o = &c.optimizer;
+    let fitness_shaping = config::fitness_shaping(&o.fitness_shaping)?;
+
+    Ok(match o.name.as_str() {
+        "es" => Box::new(es::new(
+            model,
+            adam::new(model, o.learning_rate as f32),
+            o.sigma as f32,
+            c.population,
+            true,
+            fitness_shaping,
+            c.seed,
+        )),
+        "pepg" => Box::new(pepg::new(
+            model,
+            adam::new(model, o.learning_rate as f32),
+            o.sigma as f32,
+            0.2,
+            c.population,
+            fitness_shaping,
+            c.seed,
+        )),
+        // the config's population replaces the default of 4 + 3 ln(d) so the run is the one recorded
+        "snes" => {
+            let mut b = snes::new(model, o.sigma as f32, c.seed);
+            b.population_size = c.population;
+            Box::new(b)
+        }
+        // the covariance is d x d
+        "xnes" if models::num_parameters(model) > 1000 => {
+            return Err("xnes is limited to models with at most 1000 parameters".to_string())
+        }
+        "xnes" => {
+            let mut b = xnes::new(model, o.sigma as f32, c.seed);
+            b.population_size = c.population;
+            Box::new(b)
+        }
+        "cem" => Box::new(cem::new(
+            model,
+            o.sigma as f32,
+            c.population,
+            o.elite_fraction as f32,
+            c.seed,
+        )),
+        "de" => Box::new(differential_evolution::new(
+            model,
+            c.population,
+            o.sigma as f32,
+            differential_evolution::Strategy::Rand1Bin,
+            c.seed,
+        )),
+        "jade" => Box::new(differential_evolution::new(
+            model,
+            c.population,
+            o.sigma as f32,
+            differential_evolution::Strategy::Jade,
+            c.seed,
+        )),
+        _ => return Err(format!("unknown optimizer {}", o.name)),
+    })
+}
+
+pub struct Train;
+
+impl Runner for Train {
+    fn run<M: Model + 'static, F: Fn(&M) -> f32>(
+        &self,
+        c: &config::Config,
+        a: &Args,
+        model: M,
+        fitness: F,
+    ) -> Result<(), String> {
+        let output = c.output.clone().unwrap();
+        // same format as the config given, toml otherwise
+        let config_name = match (a.options.get("config"), a.options.get("resume")) {
+            (Some(file_name), _) if file_name.ends_with(".json") => "/config.json",
+            (_, Some(dir_name)) if config::find(dir_name).is_some_and(|f| f.ends_with(".json")) => {
+                "/config.json"
+            }
+            _ => "/config.toml",
+        };
+        config::save(c, (output.clone() + config_name).as_str());
+
+        // seeded start around zero so a run can be repeated
+        let mut rng = ChaCha8Rng::seed_from_u64(c.seed);
+        let model = models::gaussian_noise(&model, &mut rng).scalar(c.optimizer.sigma as f32);
+        let mut evolve = optimizer(c, &model)?;
+        let mut hall_of_fame = hall_of_fame::new(c.hall_of_fame);
+        let mut start = 0;
+        if a.options.contains_key("resume") {
+            start = load_state(&output, &mut *evolve, &mut hall_of_fame, &model)?;
+            println!("resuming {} from generation {}", output, start);
+        }
+
+        // a fresh run starts fresh logs, a resumed one drops what was logged after its state
+        let sinks = vec![
+            metrics::Sink::Table,
+            metrics::Sink::Csv(output.clone() + "/metrics.csv"),
+            metrics::Sink::Jsonl(output.clone() + "/metrics.jsonl"),
+        ];
+        let mut logger = metrics::new(sinks, &evolve.model());
+        if a.options.contains_key("resume") {
+            metrics::truncate(&mut logger, start);
+        } else {
+            for file_name in ["/metrics.csv", "/metrics.jsonl"] {
+                let _ = fs::remove_file(output.clone() + file_name);
+            }
+        }
+
+        for generation in start..c.generations {
+            let candidates = evolve.ask();
+            let candidate_fitness: Vec<f32> = candidates.iter().map(&fitness).collect();
+            evolve.tell(&candidate_fitness);
+            hall_of_fame::update(&mut hall_of_fame, &candidates, &candidate_fitness);
+            metrics::log(
+                &mut logger,
+                generation,
+                &evolve.model(),
+                &candidate_fitness,
+                evolve.sigma(),
+            );
+
+            let every = |n: usize| n != 0 && (generation + 1).is_multiple_of(n);
+            if every(c.checkpoint_every) {
+                evolve.model().save(
+                    (output.clone() + "/checkpoints/" + &(generation + 1).to_string()).as_str(),
+                );
+            }
+            // a checkpoint can always be resumed from, and the state is also kept on its own
+            // schedule so a crashed run without checkpoints loses at most state_every generations
+            if every(c.checkpoint_every) || every(c.state_every) {
+                save_state(&output, &*evolve, &hall_of_fame, generation + 1);
+            }
+        }
+        save_state(&output, &*evolve, &hall_of_fame, c.generations.max(start));
+
+        let model = evolve.model();
+        model.save((output.clone() + "/model").as_str());
+        println!(
+            "final fitness {:.4} after {:.1}s, saved to {}/model",
+            fitness(&model),
+            logger.start.elapsed().as_secs_f32(),
+            output
+        );
+
+        Ok(())
+    }
+}
+
+// everything needed to continue a run, in <dir_name>/state
+// written to a temporary directory first so a crash never leaves a half written state
+pub fn save_state<M: Model>(
+    dir_name: &str,
+    evolve: &dyn Evolve<M>,
+    hall_of_fame: &hall_of_fame::HallOfFame<M>,
+    generation: usize,
+) {
+    let state = dir_name.to_owned() + "/state";
+    let temporary = dir_name.to_owned() + "/state.tmp";
+    if Path::new(&temporary).exists() {
+        fs::remove_dir_all(&temporary).unwrap();
+    }
+
+    evolve.save((temporary.clone() + "/optimizer").as_str());
+    hall_of_fame::save(hall_of_fame, (temporary.clone() + "/hall_of_fame").as_str());
+    fs::write(
+        temporary.clone() + "/generation.txt",
+        generation.to_string(),
+    )
+    .unwrap();
+
+    if Path::new(&state).exists() {
+        fs::remove_dir_all(&state).unwrap();
+    }
+    fs::rename(&temporary, &state).unwrap();
+}
+
+// restores what save_state wrote and returns the generation to continue from
+pub fn load_state<M: Model>(
+    dir_name: &str,
+    evolve: &mut dyn Evolve<M>,
+    hall_of_fame: &mut hall_of_fame::HallOfFame<M>,
+    template: &M,
+) -> Result<usize, String> {
+    let state = dir_name.to_owned() + "/state";
+    let generation = fs::read_to_string(state.clone() + "/generation.txt")
+        .map_err(|_| format!("no saved state in {}", dir_name))?
+        .trim()
+        .parse()
+        .map_err(|_| format!("generation in {} is incorrect", state))?;
+
+    evolve.load((state.clone() + "/optimizer").as_str());
+    *hall_of_fame = hall_of_fame::load(
+        template,
+        hall_of_fame.size,
+        (state + "/hall_of_fame").as_str(),
+    );
+
+    Ok(generation)
+}
+
+pub struct Eval;
+
+impl Runner for Eval {
+    fn run<M: Model + 'static, F: Fn(&M) -> f32>(
+        &self,
+        _c: &config::Config,
+        a: &Args,
+        model: M,
+        fitness: F,
+    ) -> Result<(), String> {
+        let model = model.load(checkpoint(a)?);
+        println!("fitness {:.4}", fitness(&model));
+
+        Ok(())
+    }
+}
+
+pub struct Inspect;
+
+impl Runner for Inspect {
+    fn run<M: Model + 'static, F: Fn(&M) -> f32>(
+        &self,
+        _c: &config::Config,
+        a: &Args,
+        model: M,
+        _fitness: F,
+    ) -> Result<(), String> {
+        let model = match a.options.get("checkpoint") {
+            Some(_) => model.load(checkpoint(a)?),
+            None => model,
+        };
+
+        println!(
+            "{:<28} {:>12} {:>10} {:>12} {:>12}",
+            "parameter", "shape", "count", "mean", "variance"
+        );
+        for (name, m) in model.parameters() {
+            let mean = matrix::mean(m);
+            println!(
+                "{:<28} {:>12} {:>10} {:>12.6} {:>12.6}",
+                name,
+                format!("{}x{}", m.rows, m.columns),
+                m.value.len(),
+                mean,
+                matrix::variance(m, mean)
+            );
+        }
+        println!("total parameters {}", models::num_parameters(&model));
+
+        Ok(())
+    }
+}
+
+// fitness curves of a run, histograms of its final conv filters and fully connected weights and
+// for the game models images of what the conv layers learned
+pub struct Plot;
+
+impl Runner for Plot {
+    fn run<M: Model + 'static, F: Fn(&M) -> f32>(
+        &self,
+        c: &config::Config,
+        a: &Args,
+        model: M,
+        _fitness: F,
+    ) -> Result<(), String> {
+        let run = require(a, "run")?;
+        let output = run.to_owned() + "/plots";
+        let model = model.load(checkpoint(a)?);
+
+        let svg = plot::fitness_curves((run.to_owned() + "/metrics.csv").as_str(), run)?;
+        fs::create_dir_all(&output).unwrap();
+        fs::write(output.clone() + "/fitness.svg", svg).unwrap();
+        println!("{}/fitness.svg", output);
+        let mut files = plot::weight_histograms(&model, get(a, "bins", 30)?, &output);
+        files.extend(conv_images(
+            &c.task,
+            checkpoint(a)?,
+            &output,
+            &get(a, "format", "png".to_string())?,
+        ));
+        for file_name in files {
+            println!("{}", file_name);
+        }
+
+        Ok(())
+    }
+}
+
+// pixels per filter weight or feature map cell
+const IMAGE_SCALE: usize = 8;
+
+fn save_filters<T: Activate>(
+    conv: &conv2d::Conv2D<T>,
+    name: &str,
+    output: &str,
+    extension: &str,
+) -> String {
+    let file_name = format!("{}/{}.filters.{}", output, name, extension);
+    plot::image::save(
+        &plot::image::filters(conv, IMAGE_SCALE, plot::image::Normalization::Global),
+        &file_name,
+    );
+    file_name
+}
+
+fn save_res_filters<T: Activate>(
+    res: &res2d::Res2D<T>,
+    name: &str,
+    output: &str,
+    extension: &str,
+) -> Vec<String> {
+    let mut b = vec![];
+    for (i, layer) in res.layers.iter().enumerate() {
+        b.push(save_filters(
+            layer,
+            &format!("{}.layers{}", name, i),
+            output,
+            extension,
+        ));
+    }
+    b.push(save_filters(
+        &res.last_layer,
+        &format!("{}.last_layer", name),
+        output,
+        extension,
+    ));
+    b
+}
+
+// conv filters of a game model in dir_name, one grid per layer with a row per filter, and the
+// channel outputs of every layer on the empty board, returns the files written
+pub fn conv_images(task: &str, dir_name: &str, output: &str, extension: &str) -> Vec<String> {
+    let (mut b, maps) = match task {
+        "tic_tac_toe" => {
+            let m = tic_tac_toe::new().load(dir_name);
+            let files = vec![
+                save_filters(&m.conv1, "conv1", output, extension),
+                save_filters(&m.conv2, "conv2", output, extension),
+                save_filters(&m.conv3, "conv3", output, extension),
+            ];
+            let input = games::tic_tac_toe::encode(&games::tic_tac_toe::new());
+            (files, tic_tac_toe::feature_maps(&m, &input))
+        }
+        "connect_four" => {
+            let m = connect_four::new().load(dir_name);
+            let mut files = vec![save_filters(&m.conv1, "conv1", output, extension)];
+            files.extend(save_res_filters(&m.res1, "res1", output, extension));
+            files.extend(save_res_filters(&m.res2, "res2", output, extension));
+            let input = games::connect_four::encode(&games::connect_four::new());
+            (files, connect_four::feature_maps(&m, &input))
+        }
+        "othello" => {
+            let m = othello::new().load(dir_name);
+            let mut files = vec![save_filters(&m.conv1, "conv1", output, extension)];
+            files.extend(save_res_filters(&m.res1, "res1", output, extension));
+            files.extend(save_res_filters(&m.res2, "res2", output, extension));
+            let input = games::othello::encode(&games::othello::new());
+            (files, othello::feature_maps(&m, &input))
+        }
+        _ => return vec![],
+    };
+
+    // each map on its own range, dead and saturated channels show up as flat grey
+    for (name, channels) in maps {
+        let file_name = format!("{}/{}.feedforward.{}", output, name, extension);
+        plot::image::save(
+            &plot::image::channels(&channels, IMAGE_SCALE, plot::image::Normalization::PerTile),
+            &file_name,
+        );
+        b.push(file_name);
+    }
+
+    b
+}
+
+// checked up front, loading a missing directory would panic deep inside matrix::load
+fn checkpoint(a: &Args) -> Result<&str, String> {
+    let dir_name = require(a, "checkpoint")?;
+    if !fs::metadata(dir_name).map(|m| m.is_dir()).unwrap_or(false) {
+        return Err(format!("checkpoint {} is not a directory", dir_name));
+    }
+
+    Ok(dir_name)
+}
+
+// feedforward calls per second on random input of the model's shape
+pub fn bench(model: &str, iterations: usize) -> Result<f32, String> {
+    if iterations == 0 {
+        return Err("--iterations cannot be zero".to_string());
+    }
+
+    let time = |forward: &dyn Fn()| {
+        // one untimed call so allocation warm up is not measured
+        forward();
+        let now = Instant::now();
+        for _ in 0..iterations {
+            forward();
+        }
+        iterations as f32 / now.elapsed().as_secs_f32()
+    };
+
+    Ok(match model {
+        "xor" => {
+            let m = xor::new_gaussian_noise();
+            let input = matrix::new_gaussian_noise(1, 2);
+            time(&|| {
+                xor::feedforward(&m, &input);
+            })
+        }
+        "tic_tac_toe" => {
+            let m = tic_tac_toe::new_gaussian_noise();
+            let input = games::tic_tac_toe::encode(&games::tic_tac_toe::new());
+            time(&|| {
+                tic_tac_toe::feedforward(&m, &input);
+            })
+        }
+        "connect_four" => {
+            let m = connect_four::new_gaussian_noise();
+            let input = games::connect_four::encode(&games::connect_four::new());
+            time(&|| {
+                connect_four::feedforward(&m, &input);
+            })
+        }
+        "othello" => {
+            let m = othello::new_gaussian_noise();
+            let input = games::othello::encode(&games::othello::new());
+            time(&|| {
+                othello::feedforward(&m, &input);
+            })
+        }
+        _ => return Err(format!("unknown model {}", model)),
+    })
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let a = parse(args)?;
+    match a.command.as_str() {
+        "train" => {
+            check_options(
+                &a,
+                &[
+                    "config",
+                    "task",
+                    "model",
+                    "hidden",
+                    "activation",
+                    "norm",
+                    "optimizer",
+                    "sigma",
+                    "population",
+                    "learning-rate",
+                    "generations",
+                    "seed",
+                    "episodes",
+                    "checkpoint-every",
+                    "state-every",
+                    "hall-of-fame",
+                    "output",
+                    "resume",
+                ],
+            )?;
+            dispatch(&resolve_config(&a)?, &a, &Train)
+        }
+        "eval" => {
+            check_options(
+                &a,
+                &[
+                    "config",
+                    "task",
+                    "model",
+                    "hidden",
+                    "activation",
+                    "norm",
+                    "checkpoint",
+                    "seed",
+                    "episodes",
+                ],
+            )?;
+            dispatch(&resolve_config(&a)?, &a, &Eval)
+        }
+        "inspect" => {
+            check_options(
+                &a,
+                &[
+                    "config",
+                    "task",
+                    "model",
+                    "hidden",
+                    "activation",
+                    "norm",
+                    "checkpoint",
+                ],
+            )?;
+            dispatch(&resolve_config(&a)?, &a, &Inspect)
+        }
+        "bench" => {
+            check_options(&a, &["model", "iterations"])?;
+            let model = require(&a, "model")?;
+            let per_second = bench(model, get(&a, "iterations", 1000)?)?;
+            println!(
+                "{} {:.1} feedforward/s, {:.1} us each",
+                model,
+                per_second,
+                1e6 / per_second
+            );
+            Ok(())
+        }
+        "play" => {
+            check_options(&a, &["checkpoint", "first"])?;
+            let first = a.options.get("first").map(|f| f.as_str());
+            if !matches!(first, None | Some("human") | Some("model")) {
+                return Err("--first must be human or model".to_string());
+            }
+            play::run(checkpoint(&a)?, first);
+            Ok(())
+        }
+        "plot" => {
+            check_options(&a, &["run", "bins", "format"])?;
+            if get(&a, "bins", 30)? == 0 {
+                return Err("--bins cannot be zero".to_string());
+            }
+            if !matches!(
+                a.options.get("format").map(|f| f.as_str()),
+                None | Some("png") | Some("pgm")
+            ) {
+                return Err("--format must be png or pgm".to_string());
+            }
+            // the run's model is loaded like a checkpoint so its config is found next to it
+            let mut b = Args {
+                command: a.command.clone(),
+                options: a.options.clone(),
+            };
+            b.options.insert(
+                "checkpoint".to_string(),
+                require(&a, "run")?.to_owned() + "/model",
+            );
+            dispatch(&resolve_config(&b)?, &b, &Plot)
+        }
+        command => Err(format!("unknown command {}", command)),
+    }
+}
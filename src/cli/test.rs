@@ -0,0 +1,325 @@
+#[cfg(test)]
+use crate::cli;
+#[cfg(test)]
+use crate::config;
+#[cfg(test)]
+use crate::environments::cart_pole;
+#[cfg(test)]
+use crate::models::mlp;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use std::fs;
+#[cfg(test)]
+use std::path::Path;
+
+#[cfg(test)]
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn parse_test() {
+    let a = cli::parse(&args("train --task xor --sigma=0.5 --generations 3")).unwrap();
+    assert_eq!(a.command, "train");
+    assert_eq!(cli::require(&a, "task").unwrap(), "xor");
+    assert_eq!(cli::get::<f32>(&a, "sigma", 0.1).unwrap(), 0.5);
+    assert_eq!(cli::get::<usize>(&a, "generations", 100).unwrap(), 3);
+    assert_eq!(cli::get::<u64>(&a, "seed", 7).unwrap(), 7);
+    assert!(cli::get::<usize>(&a, "task", 0).is_err());
+    assert!(cli::require(&a, "output").is_err());
+
+    assert!(cli::parse(&[]).is_err());
+    assert!(cli::parse(&args("train xor")).is_err());
+    assert!(cli::parse(&args("train --task")).is_err());
+    assert!(cli::parse(&args("train --task xor --task othello")).is_err());
+}
+
+#[test]
+fn check_options_test() {
+    let a = cli::parse(&args("eval --task xor --sigmaa 0.1")).unwrap();
+    assert!(cli::check_options(&a, &["task", "sigma"]).is_err());
+    assert!(cli::check_options(&a, &["task", "sigmaa"]).is_ok());
+}
+
+#[test]
+fn resolve_config_test() {
+    let a = cli::resolve_config(&cli::parse(&args("inspect --model othello")).unwrap()).unwrap();
+    assert_eq!(a.task, "othello");
+
+    let b = cli::resolve_config(
+        &cli::parse(&args(
+            "train --task acrobot --hidden 8,4 --activation relu --optimizer cem --population 10 --state-every 3",
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(b.optimizer.name, "cem");
+    assert_eq!(b.population, 10);
+    assert_eq!(b.state_every, 3);
+    let Some(config::ModelConfig::Mlp { hidden, .. }) = b.model else {
+        panic!("acrobot uses an mlp");
+    };
+    assert_eq!(hidden.len(), 2);
+    assert_eq!(hidden[1].outputs, 4);
+    assert_eq!(hidden[1].activation, "relu");
+
+    assert!(cli::resolve_config(&cli::parse(&args("train --model mlp")).unwrap()).is_err());
+    assert!(
+        cli::resolve_config(&cli::parse(&args("train --task xor --hidden 4")).unwrap()).is_err()
+    );
+}
+
+#[test]
+fn config_file_test() {
+    let mut c = config::new("cart_pole");
+    c.generations = 1;
+    c.population = 4;
+    c.checkpoint_every = 1;
+    c.output = Some("cli_config_file_test/run".to_string());
+    c.model = Some(config::ModelConfig::Mlp {
+        hidden: vec![config::LayerConfig {
+            outputs: 3,
+            activation: "tanh".to_string(),
+            norm: None,
+        }],
+        output_activation: "none".to_string(),
+    });
+    config::save(&c, "cli_config_file_test/experiment.json");
+
+    // options override the file, the resolved config lands next to the model
+    let train = cli::run(&args(
+        "train --config cli_config_file_test/experiment.json --seed 3",
+    ));
+    let written = config::load("cli_config_file_test/run/config.json");
+    let checkpoint = Path::new("cli_config_file_test/run/checkpoints/1").is_dir();
+    // eval and inspect find the architecture from the run directory
+    let eval = cli::run(&args("eval --checkpoint cli_config_file_test/run/model"));
+    let inspect = cli::run(&args("inspect --checkpoint cli_config_file_test/run/model"));
+    fs::remove_dir_all("cli_config_file_test").unwrap();
+
+    assert!(train.is_ok());
+    let written = written.unwrap();
+    assert_eq!(written.seed, 3);
+    assert_eq!(written.model, c.model);
+    assert!(checkpoint);
+    assert!(eval.is_ok());
+    assert!(inspect.is_ok());
+}
+
+#[test]
+fn errors_test() {
+    assert!(cli::run(&args("fly")).is_err());
+    assert!(cli::run(&args("train --task chess")).is_err());
+    assert!(cli::run(&args("train --task xor --model othello")).is_err());
+    assert!(cli::run(&args("train --task xor --optimizer sgd --generations 1")).is_err());
+    assert!(cli::run(&args("train --task xor --population 5")).is_err());
+    assert!(cli::run(&args(
+        "eval --task xor --checkpoint cli_errors_test_missing"
+    ))
+    .is_err());
+    assert!(cli::run(&args("inspect --model linear")).is_err());
+    assert!(cli::run(&args("train --task xor --seeed 1")).is_err());
+    assert!(cli::run(&args("bench --model chess")).is_err());
+    assert!(cli::run(&args("play --checkpoint . --first nobody")).is_err());
+}
+
+#[test]
+fn train_eval_inspect_test() {
+    for optimizer in ["es", "snes", "cem", "jade"] {
+        cli::run(&args(&format!(
+            "train --task xor --optimizer {} --generations 2 --output cli_train_eval_inspect_test",
+            optimizer
+        )))
+        .unwrap();
+    }
+    cli::run(&args(
+        "train --task cart_pole --optimizer xnes --generations 1 --episodes 1 --output cli_train_eval_inspect_test/cart_pole",
+    ))
+    .unwrap();
+
+    let saved = Path::new("cli_train_eval_inspect_test/model").is_dir();
+    let eval = cli::run(&args(
+        "eval --task xor --checkpoint cli_train_eval_inspect_test/model",
+    ));
+    let inspect = cli::run(&args(
+        "inspect --model xor --checkpoint cli_train_eval_inspect_test/model",
+    ));
+    let inspect_linear = cli::run(&args(
+        "inspect --task cart_pole --checkpoint cli_train_eval_inspect_test/cart_pole/model",
+    ));
+    fs::remove_dir_all("cli_train_eval_inspect_test").unwrap();
+
+    assert!(saved);
+    assert!(eval.is_ok());
+    assert!(inspect.is_ok());
+    assert!(inspect_linear.is_ok());
+}
+
+#[test]
+fn plot_test() {
+    cli::run(&args(
+        "train --task cart_pole --hidden 4 --optimizer snes --generations 3 --episodes 1 --output cli_plot_test",
+    ))
+    .unwrap();
+
+    let plot = cli::run(&args("plot --run cli_plot_test --bins 10"));
+    let fitness = fs::read_to_string("cli_plot_test/plots/fitness.svg");
+    let hidden = Path::new("cli_plot_test/plots/layers0.weights.svg").is_file();
+    let output = Path::new("cli_plot_test/plots/layers1.weights.svg").is_file();
+    let zero_bins = cli::run(&args("plot --run cli_plot_test --bins 0"));
+    let missing = cli::run(&args("plot --run cli_plot_test_missing"));
+    fs::remove_dir_all("cli_plot_test").unwrap();
+
+    assert!(plot.is_ok());
+    assert_eq!(fitness.unwrap().matches("<polyline").count(), 3);
+    assert!(hidden && output);
+    assert!(zero_bins.is_err());
+    assert!(missing.is_err());
+}
+
+#[test]
+fn plot_conv_images_test() {
+    cli::run(&args(
+        "train --task tic_tac_toe --optimizer cem --population 4 --generations 1 --episodes 1 --output cli_plot_conv_images_test",
+    ))
+    .unwrap();
+
+    let plot = cli::run(&args("plot --run cli_plot_conv_images_test --format pgm"));
+    let filters = fs::read("cli_plot_conv_images_test/plots/conv2.filters.pgm");
+    let maps = fs::read("cli_plot_conv_images_test/plots/conv3.feedforward.pgm");
+    let format = cli::run(&args("plot --run cli_plot_conv_images_test --format gif"));
+    fs::remove_dir_all("cli_plot_conv_images_test").unwrap();
+
+    assert!(plot.is_ok());
+    // 64 filters by 64 channels of 3x3 tiles at 8 pixels each
+    let side = 64 * (3 * 8 + 1) + 1;
+    assert!(filters
+        .unwrap()
+        .starts_with(format!("P5\n{} {}\n", side, side).as_bytes()));
+    // 64 channels of the 3x3 board in an 8x8 grid
+    let side = 8 * (3 * 8 + 1) + 1;
+    assert!(maps
+        .unwrap()
+        .starts_with(format!("P5\n{} {}\n", side, side).as_bytes()));
+    assert!(format.is_err());
+}
+
+#[test]
+fn optimizer_population_test() {
+    // every optimizer samples the population of the config
+    for name in ["es", "pepg", "snes", "xnes", "cem", "de", "jade"] {
+        let mut c = config::resolve(&config::new("xor")).unwrap();
+        c.optimizer.name = name.to_string();
+        c.population = 12;
+
+        let mut evolve = cli::optimizer(&c, &xor::new()).unwrap();
+        assert_eq!(evolve.ask().len(), 12, "{}", name);
+    }
+}
+
+#[test]
+fn bench_test() {
+    assert!(cli::bench("xor", 10).unwrap() > 0.0);
+    assert!(cli::bench("tic_tac_toe", 1).unwrap() > 0.0);
+    assert!(cli::bench("xor", 0).is_err());
+    assert!(cli::run(&args("bench --model xor --iterations 10")).is_ok());
+}
+
+#[test]
+fn resume_test() {
+    let train = "train --task cart_pole --optimizer snes --population 4 --episodes 1 --seed 2";
+    cli::run(&args(&format!(
+        "{} --generations 6 --checkpoint-every 3 --output cli_resume_test/straight",
+        train
+    )))
+    .unwrap();
+    cli::run(&args(&format!(
+        "{} --generations 3 --checkpoint-every 3 --output cli_resume_test/resumed",
+        train
+    )))
+    .unwrap();
+    let resume = cli::run(&args(
+        "train --resume cli_resume_test/resumed --generations 6",
+    ));
+    let not_a_run = cli::run(&args("train --resume cli_resume_test"));
+    let changed = cli::run(&args("train --resume cli_resume_test/resumed --seed 3"));
+
+    let read = |name: &str| fs::read("cli_resume_test/".to_owned() + name).unwrap();
+    let same_model =
+        read("straight/model/layers0/weights.bin") == read("resumed/model/layers0/weights.bin");
+    let same_hall_of_fame = read("straight/state/hall_of_fame/fitness.bin")
+        == read("resumed/state/hall_of_fame/fitness.bin");
+    let generation = fs::read_to_string("cli_resume_test/resumed/state/generation.txt").unwrap();
+    let metrics = fs::read_to_string("cli_resume_test/resumed/metrics.csv").unwrap();
+    let straight_metrics = fs::read_to_string("cli_resume_test/straight/metrics.csv").unwrap();
+    fs::remove_dir_all("cli_resume_test").unwrap();
+
+    assert!(resume.is_ok());
+    assert!(not_a_run.is_err());
+    assert!(changed.is_err());
+    assert!(same_model);
+    assert!(same_hall_of_fame);
+    assert_eq!(generation, "6");
+    // one log across the resume, with the same fitness as the straight run
+    let fitness = |text: &str| -> Vec<String> {
+        text.lines()
+            .map(|l| l.split(',').take(5).collect::<Vec<&str>>().join(","))
+            .collect()
+    };
+    assert_eq!(metrics.lines().count(), 7);
+    assert_eq!(fitness(&metrics), fitness(&straight_metrics));
+    // cumulative columns continue across the resume
+    let column = |text: &str, name: &str| -> Vec<f32> {
+        let index = text
+            .lines()
+            .next()
+            .unwrap()
+            .split(',')
+            .position(|c| c == name)
+            .unwrap();
+        text.lines()
+            .skip(1)
+            .map(|l| l.split(',').nth(index).unwrap().parse().unwrap())
+            .collect()
+    };
+    assert_eq!(
+        column(&metrics, "evaluations"),
+        column(&straight_metrics, "evaluations")
+    );
+    let seconds = column(&metrics, "seconds");
+    assert!(seconds.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn norm_test() {
+    let c = cli::resolve_config(
+        &cli::parse(&args(
+            "train --task cart_pole --hidden 4,4 --norm batch_norm --output cli_norm_test",
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+    let model = cli::mlp(&c, &mut cart_pole::new(0));
+    // statistics are collected from the environment, the output layer has no norm
+    let Some(mlp::Norm::Batch(n)) = &model.norms[1] else {
+        panic!("hidden layers use batch norm");
+    };
+    assert!(n.variance.value.iter().all(|v| *v > 0.0 && *v != 1.0));
+    assert!(model.norms[2].is_none());
+
+    cli::run(&args(
+        "train --task cart_pole --hidden 4 --norm layer_norm --optimizer snes --generations 1 --episodes 1 --output cli_norm_test",
+    ))
+    .unwrap();
+    let saved = Path::new("cli_norm_test/model/norms0/gain.bin").is_file();
+    let eval = cli::run(&args("eval --checkpoint cli_norm_test/model"));
+    let unknown = cli::run(&args(
+        "train --task cart_pole --hidden 4 --norm group_norm --output cli_norm_test",
+    ));
+    fs::remove_dir_all("cli_norm_test").unwrap();
+
+    assert!(saved);
+    assert!(eval.is_ok());
+    assert!(unknown.is_err());
+}
@@ -0,0 +1,270 @@
+pub mod test;
+
+use crate::evolution::fitness_shaping::FitnessShaping;
+use crate::models::mlp;
+use crate::nn::activations;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+pub const TASKS: [&str; 10] = [
+    "xor",
+    "tic_tac_toe",
+    "connect_four",
+    "othello",
+    "cart_pole",
+    "pendulum",
+    "mountain_car",
+    "acrobot",
+    "snake",
+    "twenty_forty_eight",
+];
+pub const OPTIMIZERS: [&str; 7] = ["es", "pepg", "snes", "xnes", "cem", "de", "jade"];
+
+// everything a training run depends on, written next to the saved model so the run can be repeated
+// missing fields take the defaults below, resolve fills in the ones that depend on the task
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub task: String,
+    #[serde(default)]
+    pub model: Option<ModelConfig>,
+    #[serde(default)]
+    pub optimizer: OptimizerConfig,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default = "default_population")]
+    pub population: usize,
+    #[serde(default = "default_generations")]
+    pub generations: usize,
+    // episodes per environment evaluation, games are twice this
+    #[serde(default = "default_episodes")]
+    pub episodes: usize,
+    // generations between checkpoints, 0 only saves the final model
+    #[serde(default)]
+    pub checkpoint_every: usize,
+    // generations between saves of the resumable run state, 0 only saves it at the end
+    #[serde(default = "default_state_every")]
+    pub state_every: usize,
+    // best candidates kept over the whole run, saved with the run state
+    #[serde(default = "default_hall_of_fame")]
+    pub hall_of_fame: usize,
+    // runs/<task> when missing
+    #[serde(default)]
+    pub output: Option<String>,
+}
+
+// game models have a fixed architecture, environment tasks use an mlp on the observation row
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ModelConfig {
+    Xor,
+    TicTacToe,
+    ConnectFour,
+    Othello,
+    Mlp {
+        #[serde(default)]
+        hidden: Vec<LayerConfig>,
+        #[serde(default = "default_output_activation")]
+        output_activation: String,
+    },
+}
+
+// arguments of fully_connected::new, inputs come from the previous layer
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LayerConfig {
+    pub outputs: usize,
+    pub activation: String,
+    // layer_norm or batch_norm on the output of the layer, none when missing
+    #[serde(default)]
+    pub norm: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct OptimizerConfig {
+    pub name: String,
+    // f64 so the written config shows the values as they were typed
+    pub sigma: f64,
+    // adam learning rate of es and pepg
+    pub learning_rate: f64,
+    // cem only
+    pub elite_fraction: f64,
+    // es and pepg, raw, centered_ranks, utilities or z_score
+    pub fitness_shaping: String,
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> OptimizerConfig {
+        OptimizerConfig {
+            name: "es".to_string(),
+            sigma: 0.1,
+            learning_rate: 0.05,
+            elite_fraction: 0.2,
+            fitness_shaping: "centered_ranks".to_string(),
+        }
+    }
+}
+
+fn default_population() -> usize {
+    20
+}
+
+fn default_generations() -> usize {
+    100
+}
+
+fn default_episodes() -> usize {
+    3
+}
+
+fn default_state_every() -> usize {
+    10
+}
+
+fn default_hall_of_fame() -> usize {
+    10
+}
+
+fn default_output_activation() -> String {
+    "tanh".to_string()
+}
+
+// defaults for task
+pub fn new(task: &str) -> Config {
+    Config {
+        task: task.to_string(),
+        model: None,
+        optimizer: OptimizerConfig::default(),
+        seed: 0,
+        population: default_population(),
+        generations: default_generations(),
+        episodes: default_episodes(),
+        checkpoint_every: 0,
+        state_every: default_state_every(),
+        hall_of_fame: default_hall_of_fame(),
+        output: None,
+    }
+}
+
+// model every task is trained with by default, environment tasks use a linear policy
+pub fn default_model(task: &str) -> Result<ModelConfig, String> {
+    match task {
+        "xor" => Ok(ModelConfig::Xor),
+        "tic_tac_toe" => Ok(ModelConfig::TicTacToe),
+        "connect_four" => Ok(ModelConfig::ConnectFour),
+        "othello" => Ok(ModelConfig::Othello),
+        task if TASKS.contains(&task) => Ok(ModelConfig::Mlp {
+            hidden: vec![],
+            output_activation: default_output_activation(),
+        }),
+        _ => Err(format!("unknown task {}", task)),
+    }
+}
+
+pub fn fitness_shaping(name: &str) -> Result<FitnessShaping, String> {
+    match name {
+        "raw" => Ok(FitnessShaping::Raw),
+        "centered_ranks" => Ok(FitnessShaping::CenteredRanks),
+        "utilities" => Ok(FitnessShaping::Utilities),
+        "z_score" => Ok(FitnessShaping::ZScore),
+        _ => Err(format!("unknown fitness shaping {}", name)),
+    }
+}
+
+// checks every field and fills in the task dependent defaults
+pub fn resolve(a: &Config) -> Result<Config, String> {
+    let mut b = a.clone();
+    let model = default_model(&a.task)?;
+
+    match (&a.model, &model) {
+        (None, _) => b.model = Some(model),
+        (Some(ModelConfig::Mlp { .. }), ModelConfig::Mlp { .. }) => {}
+        (Some(m), _) if *m == model => {}
+        (Some(m), _) => {
+            return Err(format!(
+                "model {:?} does not fit task {}, use {:?}",
+                m, a.task, model
+            ))
+        }
+    }
+    if let Some(ModelConfig::Mlp {
+        hidden,
+        output_activation,
+    }) = &b.model
+    {
+        for layer in hidden {
+            if layer.outputs == 0 {
+                return Err("hidden layers need at least one output".to_string());
+            }
+            activations::from_name(&layer.activation)
+                .ok_or(format!("unknown activation {}", layer.activation))?;
+            if let Some(norm) = &layer.norm {
+                mlp::norm_from_name(norm, layer.outputs).ok_or(format!("unknown norm {}", norm))?;
+            }
+        }
+        activations::from_name(output_activation)
+            .ok_or(format!("unknown activation {}", output_activation))?;
+    }
+
+    if !OPTIMIZERS.contains(&a.optimizer.name.as_str()) {
+        return Err(format!("unknown optimizer {}", a.optimizer.name));
+    }
+    fitness_shaping(&a.optimizer.fitness_shaping)?;
+    if a.population < 4 || !a.population.is_multiple_of(2) {
+        return Err("population must be even and at least 4".to_string());
+    }
+    if a.hall_of_fame == 0 {
+        return Err("hall of fame cannot be empty".to_string());
+    }
+    if a.episodes == 0 {
+        return Err("episodes cannot be zero".to_string());
+    }
+    if a.optimizer.elite_fraction <= 0.0 || a.optimizer.elite_fraction > 1.0 {
+        return Err("elite fraction must be in (0, 1]".to_string());
+    }
+    if b.output.is_none() {
+        b.output = Some("runs/".to_owned() + &a.task);
+    }
+
+    Ok(b)
+}
+
+fn is_json(file_name: &str) -> bool {
+    Path::new(file_name)
+        .extension()
+        .is_some_and(|e| e == "json")
+}
+
+// toml, or json for files ending in .json
+pub fn load(file_name: &str) -> Result<Config, String> {
+    let text = fs::read_to_string(file_name).map_err(|e| format!("{}: {}", file_name, e))?;
+    if is_json(file_name) {
+        serde_json::from_str(&text).map_err(|e| format!("{}: {}", file_name, e))
+    } else {
+        toml::from_str(&text).map_err(|e| format!("{}: {}", file_name, e))
+    }
+}
+
+// format follows the extension like load
+pub fn save(a: &Config, file_name: &str) {
+    let text = if is_json(file_name) {
+        serde_json::to_string_pretty(a).unwrap()
+    } else {
+        toml::to_string(a).unwrap()
+    };
+    if let Some(parent) = Path::new(file_name).parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    fs::write(file_name, text).unwrap();
+}
+
+// config.toml or config.json in dir_name, as written by a training run
+pub fn find(dir_name: &str) -> Option<String> {
+    ["config.toml", "config.json"]
+        .iter()
+        .map(|name| dir_name.to_owned() + "/" + name)
+        .find(|file_name| Path::new(file_name).is_file())
+}
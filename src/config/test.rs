@@ -0,0 +1,122 @@
+#[cfg(test)]
+use crate::config;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn toml_test() {
+    let text = r#"
+task = "cart_pole"
+seed = 4
+generations = 10
+
+[model]
+type = "mlp"
+
+[[model.hidden]]
+outputs = 8
+activation = "relu"
+
+[optimizer]
+name = "snes"
+sigma = 0.5
+"#;
+    let a: config::Config = toml::from_str(text).unwrap();
+    assert_eq!(a.seed, 4);
+    assert_eq!(a.population, 20);
+    // resumable state is saved periodically even without model checkpoints
+    assert_eq!(a.checkpoint_every, 0);
+    assert_eq!(a.state_every, 10);
+    assert_eq!(a.optimizer.name, "snes");
+    assert_eq!(a.optimizer.learning_rate, 0.05);
+    assert_eq!(
+        a.model,
+        Some(config::ModelConfig::Mlp {
+            hidden: vec![config::LayerConfig {
+                outputs: 8,
+                activation: "relu".to_string(),
+                norm: None,
+            }],
+            output_activation: "tanh".to_string(),
+        })
+    );
+
+    // typos are errors rather than silently ignored
+    assert!(toml::from_str::<config::Config>("task = \"xor\"\nseeed = 1").is_err());
+}
+
+#[test]
+fn resolve_test() {
+    let a = config::resolve(&config::new("othello")).unwrap();
+    assert_eq!(a.model, Some(config::ModelConfig::Othello));
+    assert_eq!(a.output, Some("runs/othello".to_string()));
+
+    let mut b = config::new("xor");
+    b.model = Some(config::ModelConfig::Othello);
+    assert!(config::resolve(&b).is_err());
+    assert!(config::resolve(&config::new("chess")).is_err());
+
+    let mut c = config::new("xor");
+    c.optimizer.name = "sgd".to_string();
+    assert!(config::resolve(&c).is_err());
+    let mut c = config::new("xor");
+    c.population = 5;
+    assert!(config::resolve(&c).is_err());
+
+    let mut d = config::new("acrobot");
+    d.model = Some(config::ModelConfig::Mlp {
+        hidden: vec![config::LayerConfig {
+            outputs: 4,
+            activation: "softmax".to_string(),
+            norm: None,
+        }],
+        output_activation: "tanh".to_string(),
+    });
+    assert!(config::resolve(&d).is_err());
+
+    let mut e = config::new("acrobot");
+    e.model = Some(config::ModelConfig::Mlp {
+        hidden: vec![config::LayerConfig {
+            outputs: 4,
+            activation: "tanh".to_string(),
+            norm: Some("batch_norm".to_string()),
+        }],
+        output_activation: "tanh".to_string(),
+    });
+    assert!(config::resolve(&e).is_ok());
+    let Some(config::ModelConfig::Mlp { hidden, .. }) = &mut e.model else {
+        panic!("acrobot uses an mlp");
+    };
+    hidden[0].norm = Some("group_norm".to_string());
+    assert!(config::resolve(&e).is_err());
+}
+
+#[test]
+fn save_load_test() {
+    let mut a = config::resolve(&config::new("pendulum")).unwrap();
+    a.checkpoint_every = 5;
+    a.model = Some(config::ModelConfig::Mlp {
+        hidden: vec![config::LayerConfig {
+            outputs: 16,
+            activation: "tanh".to_string(),
+            norm: Some("layer_norm".to_string()),
+        }],
+        output_activation: "none".to_string(),
+    });
+
+    config::save(&a, "config_save_load_test/config.toml");
+    config::save(&a, "config_save_load_test/other/config.json");
+    let b = config::load("config_save_load_test/config.toml");
+    let c = config::load("config_save_load_test/other/config.json");
+    let found = config::find("config_save_load_test/other");
+    let missing = config::load("config_save_load_test/missing.toml");
+    fs::remove_dir_all("config_save_load_test").unwrap();
+
+    assert_eq!(b.unwrap(), a);
+    assert_eq!(c.unwrap(), a);
+    assert_eq!(
+        found,
+        Some("config_save_load_test/other/config.json".to_string())
+    );
+    assert!(missing.is_err());
+}
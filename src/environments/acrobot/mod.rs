@@ -0,0 +1,134 @@
+pub mod test;
+
+use crate::environments::actions;
+use crate::environments::pendulum;
+use crate::environments::Environment;
+use crate::environments::Step;
+use crate::matrix;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::f32::consts::PI;
+
+const DT: f32 = 0.2;
+const LINK_LENGTH: f32 = 1.0;
+const LINK_MASS: f32 = 1.0;
+// position of the center of mass along each link
+const LINK_COM: f32 = 0.5;
+const LINK_MOI: f32 = 1.0;
+const GRAVITY: f32 = 9.8;
+const MAX_VELOCITY_1: f32 = 4.0 * PI;
+const MAX_VELOCITY_2: f32 = 9.0 * PI;
+
+// swing the tip of a two link pendulum above a line by torquing the middle joint
+// (Sutton 1996), same constants and book dynamics as gym's Acrobot-v1
+// observation 1 x 6 [cos(a1), sin(a1), cos(a2), sin(a2), angular velocity 1, angular velocity 2]
+// action 1 x 3 scores for torques -1, 0 and 1, reward -1 per step until the goal
+#[derive(Clone)]
+pub struct Acrobot {
+    // [angle 1, angle 2, angular velocity 1, angular velocity 2]
+    pub state: [f32; 4],
+    pub rng: ChaCha8Rng,
+}
+
+pub fn new(seed: u64) -> Acrobot {
+    Acrobot {
+        state: [0.0; 4],
+        rng: ChaCha8Rng::seed_from_u64(seed),
+    }
+}
+
+pub fn observation(a: &Acrobot) -> matrix::Matrix {
+    let [angle1, angle2, velocity1, velocity2] = a.state;
+    let mut b = matrix::new(1, 6);
+    b.value = vec![
+        angle1.cos(),
+        angle1.sin(),
+        angle2.cos(),
+        angle2.sin(),
+        velocity1,
+        velocity2,
+    ];
+
+    b
+}
+
+// tip is above the pivot by more than one link length
+pub fn is_goal(a: &Acrobot) -> bool {
+    -a.state[0].cos() - (a.state[0] + a.state[1]).cos() > 1.0
+}
+
+// time derivative of the state under a torque at the middle joint
+fn derivative(state: [f32; 4], torque: f32) -> [f32; 4] {
+    let [angle1, angle2, velocity1, velocity2] = state;
+    let (m, l, lc, i) = (LINK_MASS, LINK_LENGTH, LINK_COM, LINK_MOI);
+
+    let d1 = m * lc * lc + m * (l * l + lc * lc + 2.0 * l * lc * angle2.cos()) + i + i;
+    let d2 = m * (lc * lc + l * lc * angle2.cos()) + i;
+    let phi2 = m * lc * GRAVITY * (angle1 + angle2 - PI / 2.0).cos();
+    let phi1 = -m * l * lc * velocity2 * velocity2 * angle2.sin()
+        - 2.0 * m * l * lc * velocity2 * velocity1 * angle2.sin()
+        + (m * lc + m * l) * GRAVITY * (angle1 - PI / 2.0).cos()
+        + phi2;
+    let acceleration2 =
+        (torque + d2 / d1 * phi1 - m * l * lc * velocity1 * velocity1 * angle2.sin() - phi2)
+            / (m * lc * lc + i - d2 * d2 / d1);
+    let acceleration1 = -(d2 * acceleration2 + phi1) / d1;
+
+    [velocity1, velocity2, acceleration1, acceleration2]
+}
+
+fn add_scaled(a: [f32; 4], b: [f32; 4], s: f32) -> [f32; 4] {
+    [
+        a[0] + s * b[0],
+        a[1] + s * b[1],
+        a[2] + s * b[2],
+        a[3] + s * b[3],
+    ]
+}
+
+impl Environment for Acrobot {
+    fn reset(&mut self) -> matrix::Matrix {
+        for value in self.state.iter_mut() {
+            *value = self.rng.gen_range(-0.1..0.1);
+        }
+
+        observation(self)
+    }
+
+    fn step(&mut self, action: &matrix::Matrix) -> Step {
+        let torque = actions::argmax(action) as f32 - 1.0;
+
+        // runge kutta 4 over one step
+        let k1 = derivative(self.state, torque);
+        let k2 = derivative(add_scaled(self.state, k1, DT / 2.0), torque);
+        let k3 = derivative(add_scaled(self.state, k2, DT / 2.0), torque);
+        let k4 = derivative(add_scaled(self.state, k3, DT), torque);
+        let mut state = self.state;
+        for j in 0..4 {
+            state[j] += DT / 6.0 * (k1[j] + 2.0 * k2[j] + 2.0 * k3[j] + k4[j]);
+        }
+
+        self.state = [
+            pendulum::normalize_angle(state[0]),
+            pendulum::normalize_angle(state[1]),
+            state[2].clamp(-MAX_VELOCITY_1, MAX_VELOCITY_1),
+            state[3].clamp(-MAX_VELOCITY_2, MAX_VELOCITY_2),
+        ];
+
+        let done = is_goal(self);
+        Step {
+            observation: observation(self),
+            reward: if done { 0.0 } else { -1.0 },
+            done,
+        }
+    }
+
+    fn observation_shape(&self) -> (usize, usize) {
+        (1, 6)
+    }
+
+    fn action_shape(&self) -> (usize, usize) {
+        (1, 3)
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+use crate::environments::acrobot;
+#[cfg(test)]
+use crate::environments::Environment;
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use std::f32::consts::PI;
+
+#[test]
+fn reset_test() {
+    let mut a = acrobot::new(0);
+
+    let observation = a.reset();
+    assert_eq!(observation.columns, 6);
+    assert!(a.state.iter().all(|v| v.abs() <= 0.1));
+    assert_eq!(observation.value, acrobot::new(0).reset().value);
+}
+
+#[test]
+fn is_goal_test() {
+    let mut a = acrobot::new(0);
+    assert!(!acrobot::is_goal(&a));
+
+    // both links pointing up
+    a.state = [PI, 0.0, 0.0, 0.0];
+    assert!(acrobot::is_goal(&a));
+}
+
+#[test]
+fn step_test() {
+    let mut a = acrobot::new(0);
+    let mut none = matrix::new(1, 3);
+    none.value[1] = 1.0;
+
+    // hanging still with no torque stays still
+    a.state = [0.0; 4];
+    let step = a.step(&none);
+    assert!(a.state.iter().all(|v| v.abs() < 1e-5));
+    assert_eq!(step.reward, -1.0);
+    assert!(!step.done);
+
+    // a torque at the middle joint moves the links in opposite directions
+    let mut positive = matrix::new(1, 3);
+    positive.value[2] = 1.0;
+    a.step(&positive);
+    assert!(a.state[3] > 0.0);
+    assert!(a.state[2] < 0.0);
+
+    // angles stay wrapped and velocities stay clipped
+    a.state = [3.0, 3.0, 20.0, 40.0];
+    a.step(&none);
+    assert!(a.state[0].abs() <= PI && a.state[1].abs() <= PI);
+    assert!(a.state[2].abs() <= 4.0 * PI && a.state[3].abs() <= 9.0 * PI);
+}
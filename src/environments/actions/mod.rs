@@ -0,0 +1,97 @@
+pub mod test;
+
+use crate::environments::Environment;
+use crate::environments::Step;
+use crate::matrix;
+
+// index of the highest score in a row of action scores
+pub fn argmax(a: &matrix::Matrix) -> usize {
+    let mut best = 0;
+    for i in 1..a.value.len() {
+        if a.value[i] > a.value[best] {
+            best = i;
+        }
+    }
+
+    best
+}
+
+// continuous environment driven by discrete action scores,
+// the highest scoring of the preset actions is taken
+pub struct Discretized<E: Environment> {
+    pub environment: E,
+    pub actions: Vec<matrix::Matrix>,
+}
+
+pub fn discretize<E: Environment>(environment: E, actions: Vec<matrix::Matrix>) -> Discretized<E> {
+    assert!(
+        !actions.is_empty(),
+        "Discretized environment needs actions."
+    );
+    let shape = environment.action_shape();
+    for action in actions.iter() {
+        assert!(
+            (action.rows, action.columns) == shape,
+            "Action shape is incorrect."
+        );
+    }
+
+    Discretized {
+        environment,
+        actions,
+    }
+}
+
+impl<E: Environment> Environment for Discretized<E> {
+    fn reset(&mut self) -> matrix::Matrix {
+        self.environment.reset()
+    }
+
+    fn step(&mut self, action: &matrix::Matrix) -> Step {
+        let index = argmax(action);
+        self.environment.step(&self.actions[index])
+    }
+
+    fn observation_shape(&self) -> (usize, usize) {
+        self.environment.observation_shape()
+    }
+
+    fn action_shape(&self) -> (usize, usize) {
+        (1, self.actions.len())
+    }
+}
+
+// discrete environment driven by a single continuous value in [-1, 1],
+// the range is split into one equal bin per discrete action
+pub struct Binned<E: Environment> {
+    pub environment: E,
+}
+
+pub fn bin<E: Environment>(environment: E) -> Binned<E> {
+    Binned { environment }
+}
+
+impl<E: Environment> Environment for Binned<E> {
+    fn reset(&mut self) -> matrix::Matrix {
+        self.environment.reset()
+    }
+
+    fn step(&mut self, action: &matrix::Matrix) -> Step {
+        let (rows, columns) = self.environment.action_shape();
+        let bins = rows * columns;
+        let value = action.value[0].clamp(-1.0, 1.0);
+        let index = (((value + 1.0) / 2.0 * bins as f32) as usize).min(bins - 1);
+
+        let mut scores = matrix::new(rows, columns);
+        scores.value[index] = 1.0;
+        self.environment.step(&scores)
+    }
+
+    fn observation_shape(&self) -> (usize, usize) {
+        self.environment.observation_shape()
+    }
+
+    fn action_shape(&self) -> (usize, usize) {
+        (1, 1)
+    }
+}
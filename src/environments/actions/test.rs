@@ -0,0 +1,74 @@
+#[cfg(test)]
+use crate::environments::actions;
+#[cfg(test)]
+use crate::environments::cart_pole;
+#[cfg(test)]
+use crate::environments::pendulum;
+#[cfg(test)]
+use crate::environments::Environment;
+#[cfg(test)]
+use crate::matrix;
+
+#[test]
+fn argmax_test() {
+    let mut a = matrix::new(1, 3);
+    a.value = vec![0.5, 2.0, -1.0];
+
+    assert_eq!(actions::argmax(&a), 1);
+}
+
+#[test]
+fn discretize_test() {
+    let mut torques: Vec<matrix::Matrix> = vec![];
+    for torque in [-2.0, 0.0, 2.0] {
+        let mut a = matrix::new(1, 1);
+        a.value[0] = torque;
+        torques.push(a);
+    }
+    let mut a = actions::discretize(pendulum::new(0), torques);
+    assert_eq!(a.action_shape(), (1, 3));
+    assert_eq!(a.observation_shape(), (1, 3));
+
+    // the third score picks a torque of 2, same as driving the pendulum directly
+    let mut b = pendulum::new(0);
+    a.reset();
+    b.reset();
+    let mut scores = matrix::new(1, 3);
+    scores.value[2] = 1.0;
+    let mut torque = matrix::new(1, 1);
+    torque.value[0] = 2.0;
+    assert_eq!(a.step(&scores).reward, b.step(&torque).reward);
+    assert_eq!(a.environment.angle, b.angle);
+}
+
+#[test]
+#[should_panic]
+fn discretize_shape_panic_test() {
+    let _ = actions::discretize(pendulum::new(0), vec![matrix::new(1, 2)]);
+}
+
+#[test]
+fn bin_test() {
+    let mut a = actions::bin(cart_pole::new(0));
+    let mut b = cart_pole::new(0);
+    assert_eq!(a.action_shape(), (1, 1));
+
+    // values above 0 fall in the second bin and push right
+    a.reset();
+    b.reset();
+    let mut value = matrix::new(1, 1);
+    value.value[0] = 0.3;
+    let mut scores = matrix::new(1, 2);
+    scores.value[1] = 1.0;
+    assert_eq!(
+        a.step(&value).observation.value,
+        b.step(&scores).observation.value
+    );
+
+    value.value[0] = -5.0;
+    scores.value = vec![1.0, 0.0];
+    assert_eq!(
+        a.step(&value).observation.value,
+        b.step(&scores).observation.value
+    );
+}
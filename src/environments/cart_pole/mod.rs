@@ -0,0 +1,90 @@
+pub mod test;
+
+use crate::environments::actions;
+use crate::environments::Environment;
+use crate::environments::Step;
+use crate::matrix;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const GRAVITY: f32 = 9.8;
+const CART_MASS: f32 = 1.0;
+const POLE_MASS: f32 = 0.1;
+// half the length of the pole
+const POLE_LENGTH: f32 = 0.5;
+const FORCE: f32 = 10.0;
+const DT: f32 = 0.02;
+const ANGLE_LIMIT: f32 = 12.0 * std::f32::consts::PI / 180.0;
+const POSITION_LIMIT: f32 = 2.4;
+
+// balance a pole on a cart (Barto et al. 1983), same constants as gym's CartPole-v1
+// observation 1 x 4 [position, velocity, angle, angular velocity]
+// action 1 x 2 scores for pushing left or right, reward 1 per step until the pole falls
+#[derive(Clone)]
+pub struct CartPole {
+    pub state: [f32; 4],
+    pub rng: ChaCha8Rng,
+}
+
+pub fn new(seed: u64) -> CartPole {
+    CartPole {
+        state: [0.0; 4],
+        rng: ChaCha8Rng::seed_from_u64(seed),
+    }
+}
+
+pub fn observation(a: &CartPole) -> matrix::Matrix {
+    let mut b = matrix::new(1, 4);
+    b.value = a.state.to_vec();
+
+    b
+}
+
+impl Environment for CartPole {
+    fn reset(&mut self) -> matrix::Matrix {
+        for value in self.state.iter_mut() {
+            *value = self.rng.gen_range(-0.05..0.05);
+        }
+
+        observation(self)
+    }
+
+    fn step(&mut self, action: &matrix::Matrix) -> Step {
+        let [x, x_dot, theta, theta_dot] = self.state;
+        let force = if actions::argmax(action) == 1 {
+            FORCE
+        } else {
+            -FORCE
+        };
+
+        let total_mass = CART_MASS + POLE_MASS;
+        let temp =
+            (force + POLE_MASS * POLE_LENGTH * theta_dot * theta_dot * theta.sin()) / total_mass;
+        let theta_acc = (GRAVITY * theta.sin() - theta.cos() * temp)
+            / (POLE_LENGTH * (4.0 / 3.0 - POLE_MASS * theta.cos() * theta.cos() / total_mass));
+        let x_acc = temp - POLE_MASS * POLE_LENGTH * theta_acc * theta.cos() / total_mass;
+
+        // euler integration
+        self.state = [
+            x + DT * x_dot,
+            x_dot + DT * x_acc,
+            theta + DT * theta_dot,
+            theta_dot + DT * theta_acc,
+        ];
+
+        Step {
+            observation: observation(self),
+            reward: 1.0,
+            done: self.state[0].abs() > POSITION_LIMIT || self.state[2].abs() > ANGLE_LIMIT,
+        }
+    }
+
+    fn observation_shape(&self) -> (usize, usize) {
+        (1, 4)
+    }
+
+    fn action_shape(&self) -> (usize, usize) {
+        (1, 2)
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+use crate::environments;
+#[cfg(test)]
+use crate::environments::cart_pole;
+#[cfg(test)]
+use crate::environments::Environment;
+#[cfg(test)]
+use crate::evolution;
+#[cfg(test)]
+use crate::evolution::cem;
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::no_activation;
+#[cfg(test)]
+use crate::nn::layers::fully_connected;
+
+#[test]
+fn reset_test() {
+    let mut a = cart_pole::new(0);
+    let mut b = cart_pole::new(0);
+
+    let observation = a.reset();
+    assert_eq!(observation.rows, 1);
+    assert_eq!(observation.columns, 4);
+    assert!(observation.value.iter().all(|v| v.abs() <= 0.05));
+    assert_eq!(observation.value, b.reset().value);
+}
+
+#[test]
+fn step_test() {
+    let mut a = cart_pole::new(0);
+    a.state = [0.0; 4];
+
+    // pushing right accelerates the cart right and the pole left
+    let mut right = matrix::new(1, 2);
+    right.value[1] = 1.0;
+    a.step(&right);
+    let step = a.step(&right);
+    assert_eq!(step.reward, 1.0);
+    assert!(!step.done);
+    assert!(a.state[0] > 0.0 && a.state[1] > 0.0);
+    assert!(a.state[2] < 0.0 && a.state[3] < 0.0);
+
+    // always pushing right tips the pole over
+    let mut steps = 2;
+    while !a.step(&right).done {
+        steps += 1;
+    }
+    assert!(steps < 100);
+}
+
+#[test]
+fn evolve_policy_test() {
+    // a linear policy balances the pole for the whole episode
+    let template = fully_connected::new(4, 2, no_activation::new());
+    let fitness = |fc: &fully_connected::FullyConnected<no_activation::NoActivation>| {
+        let mut environment = cart_pole::new(0);
+        let mut policy = |o: &matrix::Matrix| fully_connected::feedforward(fc, o);
+        environments::evaluate(&mut environment, &mut policy, 3, 200)
+    };
+
+    let mut a = cem::new(&template, 1.0, 30, 0.2, 0);
+    let fc = evolution::run(&mut a, 20, fitness);
+    assert_eq!(fitness(&fc), 200.0);
+}
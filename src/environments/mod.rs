@@ -0,0 +1,130 @@
+pub mod acrobot;
+pub mod actions;
+pub mod cart_pole;
+pub mod mountain_car;
+pub mod pendulum;
+pub mod snake;
+pub mod test;
+pub mod twenty_forty_eight;
+
+use crate::matrix;
+
+pub struct Step {
+    pub observation: matrix::Matrix,
+    pub reward: f32,
+    pub done: bool,
+}
+
+// episodic task, observations and actions are matrices of a fixed (rows, columns) shape
+pub trait Environment {
+    // starts a new episode and returns its first observation
+    fn reset(&mut self) -> matrix::Matrix;
+    fn step(&mut self, action: &matrix::Matrix) -> Step;
+    fn observation_shape(&self) -> (usize, usize);
+    fn action_shape(&self) -> (usize, usize);
+}
+
+// maps observations to actions, reset is called at the start of every episode so
+// recurrent policies can clear their hidden state
+pub trait Policy {
+    fn act(&mut self, observation: &matrix::Matrix) -> matrix::Matrix;
+    fn reset(&mut self) {}
+}
+
+// any closure works as a stateless policy, e.g. |o| fully_connected::feedforward(&fc, o)
+impl<F: FnMut(&matrix::Matrix) -> matrix::Matrix> Policy for F {
+    fn act(&mut self, observation: &matrix::Matrix) -> matrix::Matrix {
+        self(observation)
+    }
+}
+
+// grid environments concatenate their channel planes into one observation row,
+// this splits the row back into planes of shape (rows, columns) for conv2d::feedforward
+pub fn to_channels(observation: &matrix::Matrix, shape: (usize, usize)) -> Vec<matrix::Matrix> {
+    let size = shape.0 * shape.1;
+    assert!(
+        observation.value.len().is_multiple_of(size),
+        "Observation does not split into planes of this shape."
+    );
+
+    observation
+        .value
+        .chunks(size)
+        .map(|values| {
+            let mut plane = matrix::new(shape.0, shape.1);
+            plane.value.copy_from_slice(values);
+            plane
+        })
+        .collect()
+}
+
+// inverse of to_channels
+pub fn from_channels(planes: &[matrix::Matrix]) -> matrix::Matrix {
+    assert!(
+        !planes.is_empty(),
+        "Cannot make an observation from no planes."
+    );
+
+    let mut b = matrix::new(1, planes.len() * planes[0].value.len());
+    b.value = vec![];
+    for plane in planes {
+        b.value.extend_from_slice(&plane.value);
+    }
+
+    b
+}
+
+pub struct Episode {
+    pub total_reward: f32,
+    pub steps: usize,
+}
+
+// runs one episode until done or max_steps
+pub fn run_episode<E: Environment, P: Policy>(
+    environment: &mut E,
+    policy: &mut P,
+    max_steps: usize,
+) -> Episode {
+    policy.reset();
+    let mut observation = environment.reset();
+    let mut episode = Episode {
+        total_reward: 0.0,
+        steps: 0,
+    };
+
+    while episode.steps < max_steps {
+        let action = policy.act(&observation);
+        let (rows, columns) = environment.action_shape();
+        assert!(
+            action.rows == rows && action.columns == columns,
+            "Action shape is incorrect."
+        );
+
+        let step = environment.step(&action);
+        episode.total_reward += step.reward;
+        episode.steps += 1;
+        observation = step.observation;
+        if step.done {
+            break;
+        }
+    }
+
+    episode
+}
+
+// mean return over several episodes, usable directly as fitness
+pub fn evaluate<E: Environment, P: Policy>(
+    environment: &mut E,
+    policy: &mut P,
+    episodes: usize,
+    max_steps: usize,
+) -> f32 {
+    assert!(episodes != 0, "Number of episodes cannot be zero.");
+
+    let mut total: f32 = 0.0;
+    for _ in 0..episodes {
+        total += run_episode(environment, policy, max_steps).total_reward;
+    }
+
+    total / episodes as f32
+}
@@ -0,0 +1,77 @@
+pub mod test;
+
+use crate::environments::actions;
+use crate::environments::Environment;
+use crate::environments::Step;
+use crate::matrix;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const MIN_POSITION: f32 = -1.2;
+const MAX_POSITION: f32 = 0.6;
+const MAX_SPEED: f32 = 0.07;
+const GOAL_POSITION: f32 = 0.5;
+const FORCE: f32 = 0.001;
+const GRAVITY: f32 = 0.0025;
+
+// drive an underpowered car up a hill (Moore 1990), same constants as gym's MountainCar-v0
+// observation 1 x 2 [position, velocity]
+// action 1 x 3 scores for pushing left, nothing or right, reward -1 per step until the goal
+#[derive(Clone)]
+pub struct MountainCar {
+    pub position: f32,
+    pub velocity: f32,
+    pub rng: ChaCha8Rng,
+}
+
+pub fn new(seed: u64) -> MountainCar {
+    MountainCar {
+        position: 0.0,
+        velocity: 0.0,
+        rng: ChaCha8Rng::seed_from_u64(seed),
+    }
+}
+
+pub fn observation(a: &MountainCar) -> matrix::Matrix {
+    let mut b = matrix::new(1, 2);
+    b.value = vec![a.position, a.velocity];
+
+    b
+}
+
+impl Environment for MountainCar {
+    fn reset(&mut self) -> matrix::Matrix {
+        self.position = self.rng.gen_range(-0.6..-0.4);
+        self.velocity = 0.0;
+
+        observation(self)
+    }
+
+    fn step(&mut self, action: &matrix::Matrix) -> Step {
+        let push = actions::argmax(action) as f32 - 1.0;
+
+        self.velocity += push * FORCE - (3.0 * self.position).cos() * GRAVITY;
+        self.velocity = self.velocity.clamp(-MAX_SPEED, MAX_SPEED);
+        self.position += self.velocity;
+        self.position = self.position.clamp(MIN_POSITION, MAX_POSITION);
+        // the left wall stops the car
+        if self.position == MIN_POSITION && self.velocity < 0.0 {
+            self.velocity = 0.0;
+        }
+
+        Step {
+            observation: observation(self),
+            reward: -1.0,
+            done: self.position >= GOAL_POSITION,
+        }
+    }
+
+    fn observation_shape(&self) -> (usize, usize) {
+        (1, 2)
+    }
+
+    fn action_shape(&self) -> (usize, usize) {
+        (1, 3)
+    }
+}
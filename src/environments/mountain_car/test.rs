@@ -0,0 +1,57 @@
+#[cfg(test)]
+use crate::environments;
+#[cfg(test)]
+use crate::environments::mountain_car;
+#[cfg(test)]
+use crate::environments::Environment;
+#[cfg(test)]
+use crate::matrix;
+
+#[test]
+fn reset_test() {
+    let mut a = mountain_car::new(0);
+
+    let observation = a.reset();
+    assert_eq!(observation.columns, 2);
+    assert!(a.position >= -0.6 && a.position < -0.4);
+    assert_eq!(a.velocity, 0.0);
+    assert_eq!(observation.value, mountain_car::new(0).reset().value);
+}
+
+#[test]
+fn step_test() {
+    let mut a = mountain_car::new(0);
+    a.position = -0.5;
+    a.velocity = 0.0;
+
+    let mut right = matrix::new(1, 3);
+    right.value[2] = 1.0;
+    let step = a.step(&right);
+    let velocity = 0.001 - (3.0_f32 * -0.5).cos() * 0.0025;
+    assert!((a.velocity - velocity).abs() < 1e-7);
+    assert!((a.position - (-0.5 + velocity)).abs() < 1e-7);
+    assert_eq!(step.reward, -1.0);
+    assert!(!step.done);
+
+    // the wall stops the car
+    a.position = -1.19;
+    a.velocity = -0.05;
+    a.step(&right);
+    assert_eq!(a.position, -1.2);
+    assert_eq!(a.velocity, 0.0);
+}
+
+#[test]
+fn goal_test() {
+    // pushing along with the velocity swings the car up the hill
+    let mut a = mountain_car::new(0);
+    let mut policy = |o: &matrix::Matrix| {
+        let mut b = matrix::new(1, 3);
+        b.value[if o.value[1] < 0.0 { 0 } else { 2 }] = 1.0;
+        b
+    };
+
+    let episode = environments::run_episode(&mut a, &mut policy, 200);
+    assert!(episode.steps < 200);
+    assert_eq!(episode.total_reward, -(episode.steps as f32));
+}
@@ -0,0 +1,82 @@
+pub mod test;
+
+use crate::environments::Environment;
+use crate::environments::Step;
+use crate::matrix;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::f32::consts::PI;
+
+const MAX_SPEED: f32 = 8.0;
+const MAX_TORQUE: f32 = 2.0;
+const DT: f32 = 0.05;
+const GRAVITY: f32 = 10.0;
+const MASS: f32 = 1.0;
+const LENGTH: f32 = 1.0;
+
+// swing a pendulum up and keep it upright, same constants as gym's Pendulum-v1
+// observation 1 x 3 [cos(angle), sin(angle), angular velocity], angle 0 is upright
+// action 1 x 1 torque clipped to [-2, 2], the episode never ends on its own
+#[derive(Clone)]
+pub struct Pendulum {
+    pub angle: f32,
+    pub angular_velocity: f32,
+    pub rng: ChaCha8Rng,
+}
+
+pub fn new(seed: u64) -> Pendulum {
+    Pendulum {
+        angle: 0.0,
+        angular_velocity: 0.0,
+        rng: ChaCha8Rng::seed_from_u64(seed),
+    }
+}
+
+pub fn observation(a: &Pendulum) -> matrix::Matrix {
+    let mut b = matrix::new(1, 3);
+    b.value = vec![a.angle.cos(), a.angle.sin(), a.angular_velocity];
+
+    b
+}
+
+// angle wrapped into [-pi, pi)
+pub fn normalize_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+impl Environment for Pendulum {
+    fn reset(&mut self) -> matrix::Matrix {
+        self.angle = self.rng.gen_range(-PI..PI);
+        self.angular_velocity = self.rng.gen_range(-1.0..1.0);
+
+        observation(self)
+    }
+
+    fn step(&mut self, action: &matrix::Matrix) -> Step {
+        let torque = action.value[0].clamp(-MAX_TORQUE, MAX_TORQUE);
+        let reward = -(normalize_angle(self.angle).powi(2)
+            + 0.1 * self.angular_velocity.powi(2)
+            + 0.001 * torque.powi(2));
+
+        self.angular_velocity += (3.0 * GRAVITY / (2.0 * LENGTH) * self.angle.sin()
+            + 3.0 / (MASS * LENGTH * LENGTH) * torque)
+            * DT;
+        self.angular_velocity = self.angular_velocity.clamp(-MAX_SPEED, MAX_SPEED);
+        self.angle += self.angular_velocity * DT;
+
+        Step {
+            observation: observation(self),
+            reward,
+            done: false,
+        }
+    }
+
+    fn observation_shape(&self) -> (usize, usize) {
+        (1, 3)
+    }
+
+    fn action_shape(&self) -> (usize, usize) {
+        (1, 1)
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+use crate::environments::pendulum;
+#[cfg(test)]
+use crate::environments::Environment;
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use std::f32::consts::PI;
+
+#[test]
+fn normalize_angle_test() {
+    assert!((pendulum::normalize_angle(3.0 * PI / 2.0) + PI / 2.0).abs() < 1e-5);
+    assert!((pendulum::normalize_angle(-PI / 4.0) + PI / 4.0).abs() < 1e-6);
+}
+
+#[test]
+fn reset_test() {
+    let mut a = pendulum::new(3);
+
+    let observation = a.reset();
+    assert_eq!(observation.columns, 3);
+    assert!((observation.value[0] - a.angle.cos()).abs() < 1e-6);
+    assert!((observation.value[1] - a.angle.sin()).abs() < 1e-6);
+    assert!(a.angular_velocity.abs() <= 1.0);
+    assert_eq!(observation.value, pendulum::new(3).reset().value);
+}
+
+#[test]
+fn step_test() {
+    let mut a = pendulum::new(0);
+    a.angle = 0.0;
+    a.angular_velocity = 0.0;
+
+    // upright and still is the best state
+    let mut torque = matrix::new(1, 1);
+    let step = a.step(&torque);
+    assert_eq!(step.reward, 0.0);
+    assert!(!step.done);
+    assert_eq!(a.angle, 0.0);
+
+    // torque is clipped to 2, velocity += 3 * 2 * 0.05
+    torque.value[0] = 10.0;
+    let step = a.step(&torque);
+    assert!((step.reward + 0.001 * 4.0).abs() < 1e-6);
+    assert!((a.angular_velocity - 0.3).abs() < 1e-6);
+    assert!((a.angle - 0.3 * 0.05).abs() < 1e-6);
+}
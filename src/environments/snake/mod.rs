@@ -0,0 +1,171 @@
+pub mod test;
+
+use crate::environments;
+use crate::environments::actions;
+use crate::environments::Environment;
+use crate::environments::Policy;
+use crate::environments::Step;
+use crate::matrix;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::VecDeque;
+
+// up, right, down, left as (row, column) steps
+const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+// snake on a rows x columns grid, food is placed from the seeded rng
+// observation 1 x (3 * rows * columns), the head, body and food planes from encode
+// action 1 x 3 scores for turning left, going straight and turning right
+// reward 1 per food, the episode ends when the snake hits a wall or itself,
+// or goes rows * columns steps without eating
+#[derive(Clone)]
+pub struct Snake {
+    pub rows: usize,
+    pub columns: usize,
+    // head first
+    pub body: VecDeque<(usize, usize)>,
+    // index into DIRECTIONS
+    pub direction: usize,
+    pub food: (usize, usize),
+    pub score: usize,
+    pub steps_since_food: usize,
+    pub rng: ChaCha8Rng,
+}
+
+pub fn new(rows: usize, columns: usize, seed: u64) -> Snake {
+    assert!(rows >= 4 && columns >= 4, "Grid must be at least 4 x 4.");
+
+    Snake {
+        rows,
+        columns,
+        body: VecDeque::new(),
+        direction: 1,
+        food: (0, 0),
+        score: 0,
+        steps_since_food: 0,
+        rng: ChaCha8Rng::seed_from_u64(seed),
+    }
+}
+
+fn place_food(a: &mut Snake) {
+    let free: Vec<(usize, usize)> = (0..a.rows * a.columns)
+        .map(|i| (i / a.columns, i % a.columns))
+        .filter(|cell| !a.body.contains(cell))
+        .collect();
+    if !free.is_empty() {
+        a.food = free[a.rng.gen_range(0..free.len())];
+    }
+}
+
+// 3 planes of rows x columns: head, body and food
+pub fn encode(a: &Snake) -> Vec<matrix::Matrix> {
+    let mut planes = vec![
+        matrix::new(a.rows, a.columns),
+        matrix::new(a.rows, a.columns),
+        matrix::new(a.rows, a.columns),
+    ];
+    // matrices are column major
+    for (i, (row, column)) in a.body.iter().enumerate() {
+        planes[if i == 0 { 0 } else { 1 }].value[column * a.rows + row] = 1.0;
+    }
+    planes[2].value[a.food.1 * a.rows + a.food.0] = 1.0;
+
+    planes
+}
+
+pub fn print(a: &Snake) {
+    for row in 0..a.rows {
+        let mut line = String::new();
+        for column in 0..a.columns {
+            line.push(if a.body.front() == Some(&(row, column)) {
+                'H'
+            } else if a.body.contains(&(row, column)) {
+                'o'
+            } else if a.food == (row, column) {
+                '*'
+            } else {
+                '.'
+            });
+        }
+        println!("{}", line);
+    }
+    println!("score {}", a.score);
+    println!();
+}
+
+impl Environment for Snake {
+    // a snake of length 3 in the middle heading right
+    fn reset(&mut self) -> matrix::Matrix {
+        let (row, column) = (self.rows / 2, self.columns / 2);
+        self.body = VecDeque::from(vec![(row, column), (row, column - 1), (row, column - 2)]);
+        self.direction = 1;
+        self.score = 0;
+        self.steps_since_food = 0;
+        place_food(self);
+
+        environments::from_channels(&encode(self))
+    }
+
+    fn step(&mut self, action: &matrix::Matrix) -> Step {
+        // left, straight, right
+        self.direction = (self.direction + 3 + actions::argmax(action)) % 4;
+        let (row_step, column_step) = DIRECTIONS[self.direction];
+        let (row, column) = self.body[0];
+        let (row, column) = (row as i32 + row_step, column as i32 + column_step);
+
+        let mut reward: f32 = 0.0;
+        let mut done =
+            row < 0 || row >= self.rows as i32 || column < 0 || column >= self.columns as i32;
+        if !done {
+            let head = (row as usize, column as usize);
+            let eats = head == self.food;
+            if !eats {
+                self.body.pop_back();
+            }
+            if self.body.contains(&head) {
+                done = true;
+            }
+            self.body.push_front(head);
+
+            self.steps_since_food += 1;
+            if eats {
+                reward = 1.0;
+                self.score += 1;
+                self.steps_since_food = 0;
+                place_food(self);
+            }
+            if self.steps_since_food >= self.rows * self.columns
+                || self.body.len() == self.rows * self.columns
+            {
+                done = true;
+            }
+        }
+
+        Step {
+            observation: environments::from_channels(&encode(self)),
+            reward,
+            done,
+        }
+    }
+
+    fn observation_shape(&self) -> (usize, usize) {
+        (1, 3 * self.rows * self.columns)
+    }
+
+    fn action_shape(&self) -> (usize, usize) {
+        (1, 3)
+    }
+}
+
+// mean food eaten per episode on a seeded grid, usable directly as fitness
+pub fn fitness<P: Policy>(
+    policy: &mut P,
+    rows: usize,
+    columns: usize,
+    episodes: usize,
+    seed: u64,
+) -> f32 {
+    // starvation ends every episode, so no step limit is needed
+    environments::evaluate(&mut new(rows, columns, seed), policy, episodes, usize::MAX)
+}
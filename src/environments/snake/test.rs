@@ -0,0 +1,115 @@
+#[cfg(test)]
+use crate::environments;
+#[cfg(test)]
+use crate::environments::snake;
+#[cfg(test)]
+use crate::environments::Environment;
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
+use crate::nn::layers::conv2d;
+
+#[cfg(test)]
+fn turn(index: usize) -> matrix::Matrix {
+    let mut action = matrix::new(1, 3);
+    action.value[index] = 1.0;
+    action
+}
+
+#[test]
+fn reset_test() {
+    let mut a = snake::new(8, 10, 0);
+    let mut b = snake::new(8, 10, 0);
+
+    let observation = a.reset();
+    assert_eq!(observation.rows, 1);
+    assert_eq!(observation.columns, 3 * 8 * 10);
+    assert_eq!(a.body.len(), 3);
+    assert_eq!(a.body[0], (4, 5));
+    assert!(!a.body.contains(&a.food));
+    assert_eq!(observation.value, b.reset().value);
+}
+
+#[test]
+fn encode_test() {
+    let mut a = snake::new(6, 6, 0);
+    a.reset();
+
+    let planes = snake::encode(&a);
+    assert_eq!(planes.len(), 3);
+    assert!(planes.iter().all(|p| p.rows == 6 && p.columns == 6));
+    // column major, head at row 3 column 3
+    assert_eq!(planes[0].value[3 * 6 + 3], 1.0);
+    assert_eq!(planes[0].value.iter().sum::<f32>(), 1.0);
+    assert_eq!(planes[1].value.iter().sum::<f32>(), 2.0);
+    assert_eq!(planes[2].value[a.food.1 * 6 + a.food.0], 1.0);
+
+    // planes go through a conv layer after splitting the observation
+    let channels = environments::to_channels(&a.reset(), (6, 6));
+    assert_eq!(channels.len(), 3);
+    let output = conv2d::feedforward(
+        &conv2d::new_gaussian_noise(3, 4, (3, 3), tanh::new()),
+        &channels,
+        (1, 1),
+        (1, 1, 1, 1),
+    );
+    assert_eq!(output.len(), 4);
+    assert_eq!(output[0].rows, 6);
+}
+
+#[test]
+fn step_test() {
+    let mut a = snake::new(6, 6, 0);
+    a.reset();
+    a.food = (0, 0);
+
+    // straight moves right, then turning right heads down
+    let step = a.step(&turn(1));
+    assert_eq!(a.body[0], (3, 4));
+    assert_eq!(a.body.len(), 3);
+    assert_eq!(step.reward, 0.0);
+    assert!(!step.done);
+    a.step(&turn(2));
+    assert_eq!(a.body[0], (4, 4));
+
+    // eating grows the snake and places new food
+    a.food = (4, 3);
+    let step = a.step(&turn(2));
+    assert_eq!(step.reward, 1.0);
+    assert_eq!(a.score, 1);
+    assert_eq!(a.body.len(), 4);
+    assert!(!a.body.contains(&a.food));
+}
+
+#[test]
+fn collision_test() {
+    let mut a = snake::new(6, 6, 0);
+    a.reset();
+    a.food = (0, 0);
+
+    // straight into the right wall
+    assert!(!a.step(&turn(1)).done);
+    assert!(!a.step(&turn(1)).done);
+    assert!(a.step(&turn(1)).done);
+
+    // turning back into the body
+    a.reset();
+    a.food = (0, 0);
+    a.body.push_back((3, 0));
+    a.body.push_back((2, 0));
+    a.step(&turn(0));
+    a.step(&turn(0));
+    assert!(a.step(&turn(0)).done);
+}
+
+#[test]
+fn fitness_test() {
+    // going straight forever eats at most the food in the starting row
+    let mut straight = |_: &matrix::Matrix| turn(1);
+    let first = snake::fitness(&mut straight, 8, 8, 5, 3);
+    let second = snake::fitness(&mut straight, 8, 8, 5, 3);
+    assert_eq!(first, second);
+    assert!((0.0..=1.0).contains(&first));
+}
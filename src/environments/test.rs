@@ -0,0 +1,136 @@
+#[cfg(test)]
+use crate::environments;
+#[cfg(test)]
+use crate::environments::Environment;
+#[cfg(test)]
+use crate::environments::Policy;
+#[cfg(test)]
+use crate::environments::Step;
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::no_activation;
+#[cfg(test)]
+use crate::nn::layers::fully_connected;
+
+// rewards the action value for 5 steps, the observation counts the steps
+#[cfg(test)]
+struct Counter {
+    t: usize,
+}
+
+#[cfg(test)]
+impl Environment for Counter {
+    fn reset(&mut self) -> matrix::Matrix {
+        self.t = 0;
+        matrix::new(1, 1)
+    }
+
+    fn step(&mut self, action: &matrix::Matrix) -> Step {
+        self.t += 1;
+        let mut observation = matrix::new(1, 1);
+        observation.value[0] = self.t as f32;
+
+        Step {
+            observation,
+            reward: action.value[0],
+            done: self.t == 5,
+        }
+    }
+
+    fn observation_shape(&self) -> (usize, usize) {
+        (1, 1)
+    }
+
+    fn action_shape(&self) -> (usize, usize) {
+        (1, 1)
+    }
+}
+
+// counts its resets, acts with the number of steps since the last reset
+#[cfg(test)]
+struct Stateful {
+    steps: usize,
+    resets: usize,
+}
+
+#[cfg(test)]
+impl Policy for Stateful {
+    fn act(&mut self, _observation: &matrix::Matrix) -> matrix::Matrix {
+        self.steps += 1;
+        let mut a = matrix::new(1, 1);
+        a.value[0] = self.steps as f32;
+
+        a
+    }
+
+    fn reset(&mut self) {
+        self.steps = 0;
+        self.resets += 1;
+    }
+}
+
+#[test]
+fn run_episode_test() {
+    let mut environment = Counter { t: 0 };
+
+    // fully connected policy, action = 2 * observation + 1
+    let mut fc = fully_connected::new(1, 1, no_activation::new());
+    fc.weights.value = vec![2.0];
+    fc.bias.value = vec![1.0];
+    let mut policy = |o: &matrix::Matrix| fully_connected::feedforward(&fc, o);
+
+    let episode = environments::run_episode(&mut environment, &mut policy, 100);
+    assert_eq!(episode.steps, 5);
+    // observations 0, 1, 2, 3, 4
+    assert_eq!(episode.total_reward, 1.0 + 3.0 + 5.0 + 7.0 + 9.0);
+
+    let episode = environments::run_episode(&mut environment, &mut policy, 3);
+    assert_eq!(episode.steps, 3);
+    assert_eq!(episode.total_reward, 9.0);
+}
+
+#[test]
+#[should_panic]
+fn run_episode_action_shape_panic_test() {
+    let mut environment = Counter { t: 0 };
+    let mut policy = |_: &matrix::Matrix| matrix::new(1, 2);
+
+    let _ = environments::run_episode(&mut environment, &mut policy, 10);
+}
+
+#[test]
+fn evaluate_test() {
+    let mut environment = Counter { t: 0 };
+    let mut policy = Stateful {
+        steps: 0,
+        resets: 0,
+    };
+
+    // state is cleared between episodes so every episode returns 1 + 2 + 3 + 4 + 5
+    let fitness = environments::evaluate(&mut environment, &mut policy, 3, 100);
+    assert_eq!(fitness, 15.0);
+    assert_eq!(policy.resets, 3);
+}
+
+#[test]
+fn to_channels_test() {
+    let mut observation = matrix::new(1, 12);
+    observation.value = (0..12).map(|v| v as f32).collect();
+
+    let planes = environments::to_channels(&observation, (2, 3));
+    assert_eq!(planes.len(), 2);
+    assert_eq!(planes[1].rows, 2);
+    assert_eq!(planes[1].columns, 3);
+    assert_eq!(planes[1].value, vec![6.0, 7.0, 8.0, 9.0, 10.0, 11.0]);
+    assert_eq!(
+        environments::from_channels(&planes).value,
+        observation.value
+    );
+}
+
+#[test]
+#[should_panic]
+fn to_channels_panic_test() {
+    let _ = environments::to_channels(&matrix::new(1, 10), (2, 3));
+}
@@ -0,0 +1,192 @@
+pub mod test;
+
+use crate::environments;
+use crate::environments::Environment;
+use crate::environments::Policy;
+use crate::environments::Step;
+use crate::matrix;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+pub const SIZE: usize = 4;
+// one plane per tile exponent, empty cells in plane 0 and tiles past 2^15 in the last plane
+pub const CHANNELS: usize = 16;
+
+// 2048 on a 4 x 4 board, new tiles are placed from the seeded rng
+// observation 1 x (16 * 4 * 4), the one hot exponent planes from encode
+// action 1 x 4 scores for up, right, down and left, the highest scoring move that
+// changes the board is taken, reward is the value of the tiles merged by the move
+// the episode ends when no move changes the board
+#[derive(Clone)]
+pub struct TwentyFortyEight {
+    // exponents, 0 is empty and 1 is a 2 tile, index is row * 4 + column
+    pub cells: [u8; SIZE * SIZE],
+    pub score: u32,
+    pub rng: ChaCha8Rng,
+}
+
+pub fn new(seed: u64) -> TwentyFortyEight {
+    TwentyFortyEight {
+        cells: [0; SIZE * SIZE],
+        score: 0,
+        rng: ChaCha8Rng::seed_from_u64(seed),
+    }
+}
+
+// a 2 with probability 0.9, otherwise a 4, on a random empty cell
+fn spawn(a: &mut TwentyFortyEight) {
+    let empty: Vec<usize> = (0..SIZE * SIZE).filter(|i| a.cells[*i] == 0).collect();
+    if empty.is_empty() {
+        return;
+    }
+
+    let index = empty[a.rng.gen_range(0..empty.len())];
+    a.cells[index] = if a.rng.gen::<f32>() < 0.9 { 1 } else { 2 };
+}
+
+// slides one line towards its start, returns the new line and the merged value
+pub fn slide(line: [u8; SIZE]) -> ([u8; SIZE], u32) {
+    let tiles: Vec<u8> = line.iter().cloned().filter(|t| *t != 0).collect();
+    let mut b = [0; SIZE];
+    let mut merged: u32 = 0;
+    let mut k = 0;
+    let mut i = 0;
+    while i < tiles.len() {
+        // each tile merges at most once per move
+        if i + 1 < tiles.len() && tiles[i] == tiles[i + 1] {
+            b[k] = tiles[i] + 1;
+            merged += 1 << b[k];
+            i += 2;
+        } else {
+            b[k] = tiles[i];
+            i += 1;
+        }
+        k += 1;
+    }
+
+    (b, merged)
+}
+
+// cell indices of every line of a move, ordered from the edge tiles move towards
+fn lines(direction: usize) -> [[usize; SIZE]; SIZE] {
+    let mut b = [[0; SIZE]; SIZE];
+    for (i, line) in b.iter_mut().enumerate() {
+        for (k, index) in line.iter_mut().enumerate() {
+            let (row, column) = match direction {
+                // up
+                0 => (k, i),
+                // right
+                1 => (i, SIZE - 1 - k),
+                // down
+                2 => (SIZE - 1 - k, i),
+                // left
+                _ => (i, k),
+            };
+            *index = row * SIZE + column;
+        }
+    }
+
+    b
+}
+
+// board after a move without spawning, and the merged value
+pub fn shift(a: &TwentyFortyEight, direction: usize) -> ([u8; SIZE * SIZE], u32) {
+    let mut cells = a.cells;
+    let mut merged: u32 = 0;
+    for line in lines(direction) {
+        let (slid, value) = slide([
+            a.cells[line[0]],
+            a.cells[line[1]],
+            a.cells[line[2]],
+            a.cells[line[3]],
+        ]);
+        for k in 0..SIZE {
+            cells[line[k]] = slid[k];
+        }
+        merged += value;
+    }
+
+    (cells, merged)
+}
+
+// directions that change the board
+pub fn legal_moves(a: &TwentyFortyEight) -> Vec<usize> {
+    (0..4).filter(|d| shift(a, *d).0 != a.cells).collect()
+}
+
+pub fn encode(a: &TwentyFortyEight) -> Vec<matrix::Matrix> {
+    let mut planes = vec![matrix::new(SIZE, SIZE); CHANNELS];
+    for (index, exponent) in a.cells.iter().enumerate() {
+        let (row, column) = (index / SIZE, index % SIZE);
+        // matrices are column major
+        planes[(*exponent as usize).min(CHANNELS - 1)].value[column * SIZE + row] = 1.0;
+    }
+
+    planes
+}
+
+pub fn print(a: &TwentyFortyEight) {
+    for row in 0..SIZE {
+        let mut line = String::new();
+        for column in 0..SIZE {
+            let exponent = a.cells[row * SIZE + column];
+            let value = if exponent == 0 { 0 } else { 1 << exponent };
+            line.push_str(&format!("{:>6}", value));
+        }
+        println!("{}", line);
+    }
+    println!("score {}", a.score);
+    println!();
+}
+
+impl Environment for TwentyFortyEight {
+    fn reset(&mut self) -> matrix::Matrix {
+        self.cells = [0; SIZE * SIZE];
+        self.score = 0;
+        spawn(self);
+        spawn(self);
+
+        environments::from_channels(&encode(self))
+    }
+
+    fn step(&mut self, action: &matrix::Matrix) -> Step {
+        let moves = legal_moves(self);
+        let mut reward: f32 = 0.0;
+        if !moves.is_empty() {
+            let mut best = moves[0];
+            for direction in moves {
+                if action.value[direction] > action.value[best] {
+                    best = direction;
+                }
+            }
+
+            let (cells, merged) = shift(self, best);
+            self.cells = cells;
+            self.score += merged;
+            reward = merged as f32;
+            spawn(self);
+        }
+
+        Step {
+            observation: environments::from_channels(&encode(self)),
+            reward,
+            done: legal_moves(self).is_empty(),
+        }
+    }
+
+    fn observation_shape(&self) -> (usize, usize) {
+        (1, CHANNELS * SIZE * SIZE)
+    }
+
+    fn action_shape(&self) -> (usize, usize) {
+        (1, 4)
+    }
+}
+
+// mean score per episode on seeded boards, usable directly as fitness
+pub fn fitness<P: Policy>(policy: &mut P, episodes: usize, seed: u64) -> f32 {
+    // merges keep the sum of the tiles and every move adds a new one, so the sum grows with
+    // every step and a 16 cell board cannot take it past its largest tiles, no step limit is needed
+    environments::evaluate(&mut new(seed), policy, episodes, usize::MAX)
+}
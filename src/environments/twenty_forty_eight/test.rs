@@ -0,0 +1,133 @@
+#[cfg(test)]
+use crate::environments;
+#[cfg(test)]
+use crate::environments::twenty_forty_eight;
+#[cfg(test)]
+use crate::environments::Environment;
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
+use crate::nn::layers::conv2d;
+
+#[test]
+fn reset_test() {
+    let mut a = twenty_forty_eight::new(0);
+    let mut b = twenty_forty_eight::new(0);
+
+    let observation = a.reset();
+    assert_eq!(observation.columns, 16 * 4 * 4);
+    assert_eq!(a.cells.iter().filter(|c| **c != 0).count(), 2);
+    assert_eq!(observation.value, b.reset().value);
+}
+
+#[test]
+fn slide_test() {
+    assert_eq!(twenty_forty_eight::slide([1, 1, 1, 1]), ([2, 2, 0, 0], 8));
+    assert_eq!(twenty_forty_eight::slide([0, 2, 0, 2]), ([3, 0, 0, 0], 8));
+    assert_eq!(twenty_forty_eight::slide([2, 1, 1, 0]), ([2, 2, 0, 0], 4));
+    assert_eq!(twenty_forty_eight::slide([1, 2, 3, 4]), ([1, 2, 3, 4], 0));
+}
+
+#[test]
+fn shift_test() {
+    let mut a = twenty_forty_eight::new(0);
+    a.cells = [0; 16];
+    a.cells[0] = 1;
+    a.cells[3] = 1;
+    a.cells[12] = 2;
+
+    // left merges the top row, up moves the bottom tile under the top one
+    let (cells, merged) = twenty_forty_eight::shift(&a, 3);
+    assert_eq!(cells[0], 2);
+    assert_eq!(cells[3], 0);
+    assert_eq!(merged, 4);
+    let (cells, merged) = twenty_forty_eight::shift(&a, 0);
+    assert_eq!(cells[0], 1);
+    assert_eq!(cells[4], 2);
+    assert_eq!(cells[12], 0);
+    assert_eq!(merged, 0);
+    let (cells, _) = twenty_forty_eight::shift(&a, 1);
+    assert_eq!(cells[3], 2);
+    let (cells, _) = twenty_forty_eight::shift(&a, 2);
+    assert_eq!(cells[12], 2);
+    assert_eq!(cells[15], 1);
+}
+
+#[test]
+fn step_test() {
+    let mut a = twenty_forty_eight::new(0);
+    a.reset();
+    a.cells = [0; 16];
+    a.cells[0] = 1;
+    a.cells[1] = 1;
+
+    // up scores highest but does not change the board, so left is taken
+    let mut action = matrix::new(1, 4);
+    action.value = vec![1.0, 0.0, 0.0, 0.5];
+    let step = a.step(&action);
+    assert_eq!(step.reward, 4.0);
+    assert_eq!(a.cells[0], 2);
+    assert_eq!(a.score, 4);
+    // a new tile is spawned
+    assert_eq!(a.cells.iter().filter(|c| **c != 0).count(), 2);
+    assert!(!step.done);
+}
+
+#[test]
+fn game_over_test() {
+    let mut a = twenty_forty_eight::new(0);
+    a.cells = [1, 2, 1, 2, 2, 1, 2, 1, 1, 2, 1, 2, 2, 1, 2, 1];
+    assert!(twenty_forty_eight::legal_moves(&a).is_empty());
+
+    a.cells[15] = 2;
+    assert_eq!(twenty_forty_eight::legal_moves(&a), vec![0, 1, 2, 3]);
+    let step = a.step(&matrix::new(1, 4));
+    assert!(step.reward > 0.0);
+}
+
+#[test]
+fn encode_test() {
+    let mut a = twenty_forty_eight::new(0);
+    a.cells = [0; 16];
+    // a 4 tile at row 0 column 1
+    a.cells[1] = 2;
+
+    let planes = twenty_forty_eight::encode(&a);
+    assert_eq!(planes.len(), twenty_forty_eight::CHANNELS);
+    assert_eq!(planes[0].value.iter().sum::<f32>(), 15.0);
+    // column major
+    assert_eq!(planes[2].value[4], 1.0);
+
+    let channels = environments::to_channels(
+        &environments::from_channels(&planes),
+        (twenty_forty_eight::SIZE, twenty_forty_eight::SIZE),
+    );
+    let output = conv2d::feedforward(
+        &conv2d::new_gaussian_noise(twenty_forty_eight::CHANNELS, 8, (3, 3), tanh::new()),
+        &channels,
+        (1, 1),
+        (1, 1, 1, 1),
+    );
+    assert_eq!(output.len(), 8);
+}
+
+#[test]
+fn fitness_test() {
+    // cycling through the moves always ends the game with a positive score
+    let fitness = || {
+        let mut t = 0;
+        let mut policy = |_: &matrix::Matrix| {
+            let mut action = matrix::new(1, 4);
+            action.value[t % 4] = 1.0;
+            t += 1;
+            action
+        };
+        twenty_forty_eight::fitness(&mut policy, 3, 5)
+    };
+    let first = fitness();
+    let second = fitness();
+    assert_eq!(first, second);
+    assert!(first > 0.0);
+}
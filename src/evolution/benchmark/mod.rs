@@ -0,0 +1,261 @@
+pub mod test;
+
+use crate::evolution::Evolve;
+use crate::games;
+use crate::matrix;
+use crate::models::connect_four;
+use crate::models::othello;
+use crate::models::tic_tac_toe;
+use crate::models::xor;
+use crate::models::Model;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::time::Instant;
+
+// runs any optimizer through the same loop so they can be compared on one task
+pub struct BenchmarkResult {
+    pub name: String,
+    // per generation, over the candidates of that generation
+    pub best_fitness: Vec<f32>,
+    pub mean_fitness: Vec<f32>,
+    // fitness of the optimizer's own model after the last generation
+    pub final_fitness: f32,
+    pub evaluations: usize,
+    pub seconds: f32,
+}
+
+pub fn benchmark<M: Model, E: Evolve<M> + ?Sized, F: Fn(&M) -> f32>(
+    name: &str,
+    evolve: &mut E,
+    generations: usize,
+    fitness: F,
+) -> BenchmarkResult {
+    let now = Instant::now();
+    let mut best_fitness: Vec<f32> = Vec::with_capacity(generations);
+    let mut mean_fitness: Vec<f32> = Vec::with_capacity(generations);
+    let mut evaluations = 0;
+
+    for _ in 0..generations {
+        let candidates = evolve.ask();
+        let candidate_fitness: Vec<f32> = candidates.iter().map(&fitness).collect();
+        evolve.tell(&candidate_fitness);
+
+        evaluations += candidate_fitness.len();
+        best_fitness.push(candidate_fitness.iter().cloned().fold(f32::MIN, f32::max));
+        mean_fitness.push(candidate_fitness.iter().sum::<f32>() / candidate_fitness.len() as f32);
+    }
+
+    BenchmarkResult {
+        name: name.to_string(),
+        best_fitness,
+        mean_fitness,
+        final_fitness: fitness(&evolve.model()),
+        evaluations,
+        seconds: now.elapsed().as_secs_f32(),
+    }
+}
+
+pub fn print(results: &[BenchmarkResult]) {
+    println!(
+        "{:<16} {:>14} {:>14} {:>12} {:>10}",
+        "optimizer", "final fitness", "best fitness", "evaluations", "seconds"
+    );
+    for result in results {
+        println!(
+            "{:<16} {:>14.4} {:>14.4} {:>12} {:>10.3}",
+            result.name,
+            result.final_fitness,
+            result.best_fitness.last().cloned().unwrap_or(f32::NAN),
+            result.evaluations,
+            result.seconds
+        );
+    }
+}
+
+// negative squared error over the 4 xor cases, output 0 scores false and output 1 true
+pub fn xor(model: &xor::XorModel) -> f32 {
+    let mut error: f32 = 0.0;
+    for (a, b) in [(0.0, 0.0), (0.0, 1.0), (1.0, 0.0), (1.0, 1.0)] {
+        let mut input = matrix::new(1, 2);
+        input.value = vec![a, b];
+        let target: f32 = if a != b { 1.0 } else { 0.0 };
+
+        let output = xor::feedforward(model, &input);
+        error += (output.value[0] - (1.0 - target)).powi(2);
+        error += (output.value[1] - target).powi(2);
+    }
+
+    -error
+}
+
+// mean score against a uniformly random player, 1 for a win, 0.5 for a draw and 0 for a loss
+// the model moves first in every other game, seed fixes the opponent's moves
+pub fn tic_tac_toe(model: &tic_tac_toe::TicTacToe, games: usize, seed: u64) -> f32 {
+    assert!(games != 0, "Number of games cannot be zero.");
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut score: f32 = 0.0;
+    for game in 0..games {
+        let model_player: i8 = if game.is_multiple_of(2) { 1 } else { -1 };
+        let mut board = games::tic_tac_toe::new();
+        while games::tic_tac_toe::winner(&board).is_none() {
+            let index = if board.player == model_player {
+                tic_tac_toe::choose_move(model, &board)
+            } else {
+                *games::tic_tac_toe::legal_moves(&board)
+                    .choose(&mut rng)
+                    .unwrap()
+            };
+            board = games::tic_tac_toe::play(&board, index);
+        }
+
+        let winner = games::tic_tac_toe::winner(&board).unwrap();
+        if winner == model_player {
+            score += 1.0;
+        } else if winner == 0 {
+            score += 0.5;
+        }
+    }
+
+    score / games as f32
+}
+
+// score of a against b over a pair of tic tac toe games with swapped colours
+pub fn tic_tac_toe_match(a: &tic_tac_toe::TicTacToe, b: &tic_tac_toe::TicTacToe) -> f32 {
+    let mut score: f32 = 0.0;
+    for a_player in [1, -1] {
+        let mut board = games::tic_tac_toe::new();
+        while games::tic_tac_toe::winner(&board).is_none() {
+            let model = if board.player == a_player { a } else { b };
+            board = games::tic_tac_toe::play(&board, tic_tac_toe::choose_move(model, &board));
+        }
+
+        let winner = games::tic_tac_toe::winner(&board).unwrap();
+        if winner == a_player {
+            score += 1.0;
+        } else if winner == 0 {
+            score += 0.5;
+        }
+    }
+
+    score / 2.0
+}
+
+// mean score against a depth limited negamax player, scored like tic_tac_toe
+// every game opens with 2 random moves from seed so the games differ
+pub fn connect_four(
+    model: &connect_four::ConnectFour,
+    depth: usize,
+    games: usize,
+    seed: u64,
+) -> f32 {
+    assert!(games != 0, "Number of games cannot be zero.");
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut score: f32 = 0.0;
+    for game in 0..games {
+        let model_player: i8 = if game.is_multiple_of(2) { 1 } else { -1 };
+        let mut board = games::connect_four::new();
+        for _ in 0..2 {
+            board =
+                games::connect_four::play(&board, rng.gen_range(0..games::connect_four::COLUMNS));
+        }
+
+        while games::connect_four::winner(&board).is_none() {
+            let column = if board.player == model_player {
+                connect_four::choose_move(model, &board)
+            } else {
+                games::best_move(&board, depth, &games::connect_four::heuristic)
+            };
+            board = games::connect_four::play(&board, column);
+        }
+
+        let winner = games::connect_four::winner(&board).unwrap();
+        if winner == model_player {
+            score += 1.0;
+        } else if winner == 0 {
+            score += 0.5;
+        }
+    }
+
+    score / games as f32
+}
+
+// score of one othello game for black, 1 for a win, 0.5 for a draw and 0 for a loss
+// players get the board and return a move, the game opens with 2 random moves from seed
+pub fn othello_game<
+    B: FnMut(&games::othello::Board) -> usize,
+    W: FnMut(&games::othello::Board) -> usize,
+>(
+    mut black: B,
+    mut white: W,
+    seed: u64,
+) -> f32 {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut board = games::othello::new();
+    for _ in 0..2 {
+        let moves = games::othello::legal_moves(&board);
+        board = games::othello::play(&board, *moves.choose(&mut rng).unwrap());
+    }
+
+    while games::othello::winner(&board).is_none() {
+        let index = if board.player == 1 {
+            black(&board)
+        } else {
+            white(&board)
+        };
+        board = games::othello::play(&board, index);
+    }
+
+    match games::othello::winner(&board).unwrap() {
+        1 => 1.0,
+        0 => 0.5,
+        _ => 0.0,
+    }
+}
+
+// mean score against the baselines, even games against the greedy player and odd games
+// against alpha-beta of the given depth, colours swap every other game
+pub fn othello(model: &othello::Othello, depth: usize, games: usize, seed: u64) -> f32 {
+    assert!(games != 0, "Number of games cannot be zero.");
+
+    let mut score: f32 = 0.0;
+    for game in 0..games {
+        let opponent = |board: &games::othello::Board| {
+            if game.is_multiple_of(2) {
+                games::othello::greedy_move(board)
+            } else {
+                games::best_move(board, depth, &games::othello::heuristic)
+            }
+        };
+        let player = |board: &games::othello::Board| othello::choose_move(model, board);
+        let game_seed = seed.wrapping_add(game as u64);
+
+        score += if (game / 2).is_multiple_of(2) {
+            othello_game(player, opponent, game_seed)
+        } else {
+            1.0 - othello_game(opponent, player, game_seed)
+        };
+    }
+
+    score / games as f32
+}
+
+// score of a against b over a pair of othello games with swapped colours
+pub fn othello_match(a: &othello::Othello, b: &othello::Othello, seed: u64) -> f32 {
+    let first = othello_game(
+        |board| othello::choose_move(a, board),
+        |board| othello::choose_move(b, board),
+        seed,
+    );
+    let second = 1.0
+        - othello_game(
+            |board| othello::choose_move(b, board),
+            |board| othello::choose_move(a, board),
+            seed,
+        );
+
+    (first + second) / 2.0
+}
@@ -0,0 +1,137 @@
+#[cfg(test)]
+use crate::evolution::benchmark;
+#[cfg(test)]
+use crate::evolution::cem;
+#[cfg(test)]
+use crate::evolution::differential_evolution;
+#[cfg(test)]
+use crate::evolution::es;
+#[cfg(test)]
+use crate::evolution::fitness_shaping::FitnessShaping;
+#[cfg(test)]
+use crate::evolution::pepg;
+#[cfg(test)]
+use crate::evolution::snes;
+#[cfg(test)]
+use crate::evolution::xnes;
+#[cfg(test)]
+use crate::evolution::Evolve;
+#[cfg(test)]
+use crate::models::connect_four;
+#[cfg(test)]
+use crate::models::othello;
+#[cfg(test)]
+use crate::models::tic_tac_toe;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use crate::optim::adam;
+
+#[test]
+fn xor_test() {
+    // zero weights output 0 for both classes, every case misses by exactly 1
+    assert_eq!(benchmark::xor(&xor::new()), -4.0);
+}
+
+#[test]
+fn tic_tac_toe_test() {
+    let model = tic_tac_toe::new();
+
+    let a = benchmark::tic_tac_toe(&model, 4, 0);
+    let b = benchmark::tic_tac_toe(&model, 4, 0);
+    assert_eq!(a, b);
+    assert!((0.0..=1.0).contains(&a));
+}
+
+#[test]
+fn tic_tac_toe_match_test() {
+    let a = tic_tac_toe::new_gaussian_noise();
+    let b = tic_tac_toe::new_gaussian_noise();
+
+    let score = benchmark::tic_tac_toe_match(&a, &b);
+    assert!([0.0, 0.25, 0.5, 0.75, 1.0].contains(&score));
+    assert_eq!(score + benchmark::tic_tac_toe_match(&b, &a), 1.0);
+    assert_eq!(benchmark::tic_tac_toe_match(&a, &a), 0.5);
+}
+
+#[test]
+fn connect_four_test() {
+    let model = connect_four::new();
+
+    let a = benchmark::connect_four(&model, 2, 2, 0);
+    let b = benchmark::connect_four(&model, 2, 2, 0);
+    assert_eq!(a, b);
+    assert!((0.0..=1.0).contains(&a));
+}
+
+#[test]
+fn othello_test() {
+    let model = othello::new_gaussian_noise().scalar(0.1);
+
+    let a = benchmark::othello(&model, 1, 4, 0);
+    assert_eq!(a, benchmark::othello(&model, 1, 4, 0));
+    assert!((0.0..=1.0).contains(&a));
+
+    // a match against itself is even
+    assert_eq!(benchmark::othello_match(&model, &model, 0), 0.5);
+}
+
+#[test]
+fn benchmark_test() {
+    let model = xor::new_gaussian_noise().scalar(0.5);
+    let mut optimizers: Vec<(&str, Box<dyn Evolve<xor::XorModel>>)> = vec![
+        (
+            "es",
+            Box::new(es::new(
+                &model,
+                adam::new(&model, 0.05),
+                0.1,
+                20,
+                true,
+                FitnessShaping::CenteredRanks,
+                0,
+            )),
+        ),
+        ("snes", Box::new(snes::new(&model, 0.1, 0))),
+        ("xnes", Box::new(xnes::new(&model, 0.1, 0))),
+        (
+            "de",
+            Box::new(differential_evolution::new(
+                &model,
+                20,
+                0.1,
+                differential_evolution::Strategy::Jade,
+                0,
+            )),
+        ),
+        ("cem", Box::new(cem::new(&model, 0.1, 20, 0.2, 0))),
+        (
+            "pepg",
+            Box::new(pepg::new(
+                &model,
+                adam::new(&model, 0.05),
+                0.1,
+                0.05,
+                20,
+                FitnessShaping::CenteredRanks,
+                0,
+            )),
+        ),
+    ];
+
+    let start = benchmark::xor(&model);
+    let mut results: Vec<benchmark::BenchmarkResult> = vec![];
+    for (name, optimizer) in optimizers.iter_mut() {
+        let result = benchmark::benchmark(name, optimizer.as_mut(), 50, benchmark::xor);
+        assert_eq!(result.best_fitness.len(), 50);
+        assert_eq!(result.mean_fitness.len(), 50);
+        assert!(result.evaluations >= 50 * 15);
+        assert!(result.final_fitness > start);
+        results.push(result);
+    }
+
+    // pass = does not panic
+    benchmark::print(&results);
+}
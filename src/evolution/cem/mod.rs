@@ -0,0 +1,130 @@
+pub mod test;
+
+use crate::evolution;
+use crate::evolution::fitness_shaping;
+use crate::evolution::Evolve;
+use crate::models;
+use crate::models::Model;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rand_distr::StandardNormal;
+use std::fs;
+
+// cross-entropy method, a diagonal gaussian over the flattened parameters is refit
+// to the elite fraction of every generation
+#[derive(Clone)]
+pub struct Cem<M: Model> {
+    // gives the shape of the model, its values are not used
+    pub template: M,
+    pub mean: Vec<f32>,
+    pub sigma: Vec<f32>,
+    pub population_size: usize,
+    pub elite_fraction: f32,
+    // added to the refit variance so sigma does not collapse early (Szita and Lorincz 2006)
+    pub extra_noise: f32,
+    pub rng: ChaCha8Rng,
+    pub samples: Vec<Vec<f32>>,
+}
+
+pub fn new<M: Model>(
+    model: &M,
+    sigma: f32,
+    population_size: usize,
+    elite_fraction: f32,
+    seed: u64,
+) -> Cem<M> {
+    assert!(population_size != 0, "Population size cannot be zero.");
+    assert!(
+        elite_fraction > 0.0 && elite_fraction <= 1.0,
+        "Elite fraction must be in (0, 1]."
+    );
+
+    Cem {
+        template: model.clone(),
+        mean: models::flatten(model),
+        sigma: vec![sigma; models::num_parameters(model)],
+        population_size,
+        elite_fraction,
+        extra_noise: 0.0,
+        rng: ChaCha8Rng::seed_from_u64(seed),
+        samples: vec![],
+    }
+}
+
+// per parameter sigma in the shape of the model, parameters() names the layers
+pub fn step_sizes<M: Model>(a: &Cem<M>) -> M {
+    models::unflatten(&a.template, &a.sigma)
+}
+
+impl<M: Model> Evolve<M> for Cem<M> {
+    fn ask(&mut self) -> Vec<M> {
+        self.samples.clear();
+        for _ in 0..self.population_size {
+            let z: Vec<f32> = (0..self.mean.len())
+                .map(|i| {
+                    let s: f32 = self.rng.sample(StandardNormal);
+                    self.mean[i] + self.sigma[i] * s
+                })
+                .collect();
+            self.samples.push(z);
+        }
+
+        self.samples
+            .iter()
+            .map(|z| models::unflatten(&self.template, z))
+            .collect()
+    }
+
+    fn tell(&mut self, fitness: &[f32]) {
+        assert!(
+            fitness.len() == self.samples.len(),
+            "Every candidate needs a fitness."
+        );
+
+        let mut order: Vec<usize> = (0..fitness.len()).collect();
+        order.sort_by(|a, b| fitness_shaping::compare(fitness[*b], fitness[*a]));
+        let num_elites = ((self.elite_fraction * fitness.len() as f32).round() as usize).max(1);
+        let elites = &order[..num_elites];
+
+        for i in 0..self.mean.len() {
+            let mean: f32 =
+                elites.iter().map(|k| self.samples[*k][i]).sum::<f32>() / num_elites as f32;
+            let variance: f32 = elites
+                .iter()
+                .map(|k| (self.samples[*k][i] - mean).powi(2))
+                .sum::<f32>()
+                / num_elites as f32;
+
+            self.mean[i] = mean;
+            self.sigma[i] = (variance + self.extra_noise * self.extra_noise).sqrt();
+        }
+    }
+
+    fn model(&self) -> M {
+        models::unflatten(&self.template, &self.mean)
+    }
+
+    fn inject(&mut self, migrants: &[M], fitness: &[f32]) {
+        if let Some(best) = evolution::best_migrant(migrants, fitness) {
+            self.mean = models::flatten(best);
+        }
+    }
+
+    fn sigma(&self) -> Option<f32> {
+        Some(self.sigma.iter().sum::<f32>() / self.sigma.len() as f32)
+    }
+
+    fn save(&self, dir_name: &str) {
+        fs::create_dir_all(dir_name).unwrap();
+        evolution::save_values(&self.mean, (dir_name.to_owned() + "/mean.bin").as_str());
+        evolution::save_values(&self.sigma, (dir_name.to_owned() + "/sigma.bin").as_str());
+        evolution::save_rng(&self.rng, (dir_name.to_owned() + "/rng.txt").as_str());
+    }
+
+    fn load(&mut self, dir_name: &str) {
+        self.mean = evolution::load_values((dir_name.to_owned() + "/mean.bin").as_str());
+        self.sigma = evolution::load_values((dir_name.to_owned() + "/sigma.bin").as_str());
+        self.rng = evolution::load_rng((dir_name.to_owned() + "/rng.txt").as_str());
+    }
+}
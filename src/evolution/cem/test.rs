@@ -0,0 +1,90 @@
+#[cfg(test)]
+use crate::evolution;
+#[cfg(test)]
+use crate::evolution::cem;
+#[cfg(test)]
+use crate::evolution::Evolve;
+#[cfg(test)]
+use crate::models;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+
+#[cfg(test)]
+fn distance(a: &xor::XorModel, b: &xor::XorModel) -> f32 {
+    let a = models::flatten(a);
+    let b = models::flatten(b);
+
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+#[test]
+fn new_test() {
+    let model = xor::new_gaussian_noise();
+    let a = cem::new(&model, 0.1, 20, 0.2, 0);
+
+    assert_eq!(a.mean, models::flatten(&model));
+    assert_eq!(a.sigma, vec![0.1; 52]);
+    assert_eq!(a.population_size, 20);
+}
+
+#[test]
+#[should_panic]
+fn new_elite_fraction_panic_test() {
+    let _ = cem::new(&xor::new(), 0.1, 20, 0.0, 0);
+}
+
+#[test]
+fn tell_test() {
+    let mut a = cem::new(&xor::new(), 1.0, 4, 0.5, 0);
+
+    let candidates = a.ask();
+    a.tell(&[1.0, 4.0, 2.0, 3.0]);
+
+    // mean and spread of the two best candidates
+    let best = models::flatten(&candidates[1]);
+    let second = models::flatten(&candidates[3]);
+    for i in 0..best.len() {
+        assert!((a.mean[i] - (best[i] + second[i]) / 2.0).abs() < 1e-6);
+        assert!((a.sigma[i] - (best[i] - second[i]).abs() / 2.0).abs() < 1e-6);
+    }
+
+    let step_sizes = cem::step_sizes(&a);
+    assert_eq!(step_sizes.parameters()[0].0, "fc1.weights");
+    assert_eq!(step_sizes.parameters()[0].1.value[0], a.sigma[0]);
+}
+
+#[test]
+fn tell_nan_test() {
+    let mut a = cem::new(&xor::new(), 1.0, 4, 0.5, 0);
+
+    // a NaN candidate is never an elite
+    let candidates = a.ask();
+    a.tell(&[1.0, f32::NAN, 2.0, 3.0]);
+    let best = models::flatten(&candidates[3]);
+    let second = models::flatten(&candidates[2]);
+    for i in 0..best.len() {
+        assert!((a.mean[i] - (best[i] + second[i]) / 2.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+#[should_panic]
+fn tell_length_panic_test() {
+    let mut a = cem::new(&xor::new(), 0.1, 4, 0.5, 0);
+
+    a.ask();
+    a.tell(&[1.0, 2.0]);
+}
+
+#[test]
+fn run_test() {
+    let target = xor::new_gaussian_noise();
+    let start = xor::new();
+    let mut a = cem::new(&start, 1.0, 50, 0.2, 1);
+    a.extra_noise = 0.01;
+
+    let model = evolution::run(&mut a, 200, |m| -distance(m, &target));
+    assert!(distance(&model, &target) < distance(&start, &target) * 0.01);
+}
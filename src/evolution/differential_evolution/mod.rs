@@ -0,0 +1,271 @@
+pub mod test;
+
+use crate::evolution;
+use crate::evolution::fitness_shaping;
+use crate::evolution::Evolve;
+use crate::models;
+use crate::models::Model;
+use rand::seq::index;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rand_distr::StandardNormal;
+use std::cmp::Ordering;
+use std::fs;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Strategy {
+    // v = a + f * (b - c) with a, b and c random members
+    Rand1Bin,
+    // v = best + f * (b - c)
+    Best1Bin,
+    // current-to-pbest/1/bin with f and cr sampled per member and adapted to the
+    // values that produced improvements (Zhang and Sanderson 2009), no archive
+    Jade,
+}
+
+#[derive(Clone)]
+pub struct DifferentialEvolution<M: Model> {
+    pub population: Vec<M>,
+    // empty until the first population has been evaluated
+    pub fitness: Vec<f32>,
+    pub strategy: Strategy,
+    // differential weight, the mean of the sampled values for jade
+    pub f: f32,
+    // crossover rate, the mean of the sampled values for jade
+    pub crossover_rate: f32,
+    // jade only, fraction of the best members the pbest is chosen from and adaptation rate
+    pub p: f32,
+    pub c: f32,
+    pub rng: ChaCha8Rng,
+    pub trials: Vec<M>,
+    pub trial_f: Vec<f32>,
+    pub trial_crossover_rate: Vec<f32>,
+}
+
+// population is model + sigma * noise, the first ask returns it for evaluation
+pub fn new<M: Model>(
+    model: &M,
+    population_size: usize,
+    sigma: f32,
+    strategy: Strategy,
+    seed: u64,
+) -> DifferentialEvolution<M> {
+    assert!(
+        population_size >= 4,
+        "Differential evolution needs at least 4 members."
+    );
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut population: Vec<M> = Vec::with_capacity(population_size);
+    for _ in 0..population_size {
+        let noise = models::gaussian_noise(model, &mut rng);
+        population.push(model.add(&noise.scalar(sigma)));
+    }
+
+    DifferentialEvolution {
+        population,
+        fitness: vec![],
+        strategy,
+        f: 0.5,
+        crossover_rate: 0.9,
+        p: 0.05,
+        c: 0.1,
+        rng,
+        trials: vec![],
+        trial_f: vec![],
+        trial_crossover_rate: vec![],
+    }
+}
+
+// binomial crossover, each parameter comes from the mutant with probability crossover_rate
+// and at least one always does so the trial differs from the target
+pub fn crossover<M: Model, R: Rng>(target: &M, mutant: &M, crossover_rate: f32, rng: &mut R) -> M {
+    let mut values = models::flatten(target);
+    let mutant_values = models::flatten(mutant);
+    let forced = rng.gen_range(0..values.len());
+    for i in 0..values.len() {
+        if i == forced || rng.gen::<f32>() < crossover_rate {
+            values[i] = mutant_values[i];
+        }
+    }
+
+    models::unflatten(target, &values)
+}
+
+// f from cauchy(mean, 0.1) resampled until positive and cut at 1, cr from normal(mean, 0.1) in [0, 1]
+fn sample_parameters<R: Rng>(f: f32, crossover_rate: f32, rng: &mut R) -> (f32, f32) {
+    let mut trial_f: f32 = 0.0;
+    while trial_f <= 0.0 {
+        trial_f = f + 0.1 * (std::f32::consts::PI * (rng.gen::<f32>() - 0.5)).tan();
+    }
+    let normal: f32 = rng.sample(StandardNormal);
+
+    (
+        trial_f.min(1.0),
+        (crossover_rate + 0.1 * normal).clamp(0.0, 1.0),
+    )
+}
+
+impl<M: Model> Evolve<M> for DifferentialEvolution<M> {
+    fn ask(&mut self) -> Vec<M> {
+        if self.fitness.is_empty() {
+            return self.population.clone();
+        }
+
+        let n = self.population.len();
+        let best = evolution::best_index(&self.fitness);
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|a, b| fitness_shaping::compare(self.fitness[*b], self.fitness[*a]));
+        let num_pbest = ((self.p * n as f32).round() as usize).max(1);
+
+        self.trials.clear();
+        self.trial_f.clear();
+        self.trial_crossover_rate.clear();
+        for i in 0..n {
+            // three distinct members other than i
+            let mut r: Vec<usize> = vec![];
+            for j in index::sample(&mut self.rng, n, 4).into_iter() {
+                if j != i && r.len() < 3 {
+                    r.push(j);
+                }
+            }
+
+            let (f, crossover_rate) = match self.strategy {
+                Strategy::Jade => sample_parameters(self.f, self.crossover_rate, &mut self.rng),
+                _ => (self.f, self.crossover_rate),
+            };
+
+            let difference = self.population[r[1]].add(&self.population[r[2]].scalar(-1.0));
+            let mutant = match self.strategy {
+                Strategy::Rand1Bin => self.population[r[0]].add(&difference.scalar(f)),
+                Strategy::Best1Bin => self.population[best].add(&difference.scalar(f)),
+                Strategy::Jade => {
+                    let pbest = order[self.rng.gen_range(0..num_pbest)];
+                    let to_pbest = self.population[pbest].add(&self.population[i].scalar(-1.0));
+                    self.population[i].add(&to_pbest.add(&difference).scalar(f))
+                }
+            };
+
+            self.trials.push(crossover(
+                &self.population[i],
+                &mutant,
+                crossover_rate,
+                &mut self.rng,
+            ));
+            self.trial_f.push(f);
+            self.trial_crossover_rate.push(crossover_rate);
+        }
+
+        self.trials.clone()
+    }
+
+    fn tell(&mut self, fitness: &[f32]) {
+        if self.fitness.is_empty() {
+            assert!(
+                fitness.len() == self.population.len(),
+                "Every candidate needs a fitness."
+            );
+            self.fitness = fitness.to_vec();
+            return;
+        }
+
+        assert!(
+            fitness.len() == self.trials.len(),
+            "Every candidate needs a fitness."
+        );
+
+        // trials replace their target when they are at least as good
+        let mut successful_f: Vec<f32> = vec![];
+        let mut successful_crossover_rate: Vec<f32> = vec![];
+        for (i, value) in fitness.iter().enumerate() {
+            // a NaN member is always replaced, a NaN trial never replaces a member
+            if fitness_shaping::compare(*value, self.fitness[i]) != Ordering::Less {
+                self.population[i] = self.trials[i].clone();
+                self.fitness[i] = *value;
+                successful_f.push(self.trial_f[i]);
+                successful_crossover_rate.push(self.trial_crossover_rate[i]);
+            }
+        }
+
+        if self.strategy == Strategy::Jade && !successful_f.is_empty() {
+            // lehmer mean favours larger f
+            let squares: f32 = successful_f.iter().map(|f| f * f).sum();
+            let sum: f32 = successful_f.iter().sum();
+            let mean_crossover_rate: f32 = successful_crossover_rate.iter().sum::<f32>()
+                / successful_crossover_rate.len() as f32;
+
+            self.f = (1.0 - self.c) * self.f + self.c * squares / sum;
+            self.crossover_rate =
+                (1.0 - self.c) * self.crossover_rate + self.c * mean_crossover_rate;
+        }
+    }
+
+    // best member found so far
+    fn model(&self) -> M {
+        if self.fitness.is_empty() {
+            return self.population[0].clone();
+        }
+
+        self.population[evolution::best_index(&self.fitness)].clone()
+    }
+
+    // migrants replace the worst members they are better than
+    fn inject(&mut self, migrants: &[M], fitness: &[f32]) {
+        assert!(
+            migrants.len() == fitness.len(),
+            "Every migrant needs a fitness."
+        );
+        if self.fitness.is_empty() {
+            return;
+        }
+
+        for (migrant, value) in migrants.iter().zip(fitness.iter()) {
+            let mut worst = 0;
+            for i in 1..self.fitness.len() {
+                if fitness_shaping::compare(self.fitness[i], self.fitness[worst]) == Ordering::Less
+                {
+                    worst = i;
+                }
+            }
+
+            // same rule as tell, a NaN member is the worst and a NaN migrant never gets in
+            if fitness_shaping::compare(*value, self.fitness[worst]) == Ordering::Greater {
+                self.population[worst] = migrant.clone();
+                self.fitness[worst] = *value;
+            }
+        }
+    }
+
+    // mutations are differences of members, there is no distribution to report
+    fn sigma(&self) -> Option<f32> {
+        None
+    }
+
+    // f and crossover_rate are saved since jade adapts them
+    fn save(&self, dir_name: &str) {
+        fs::create_dir_all(dir_name).unwrap();
+        for (i, member) in self.population.iter().enumerate() {
+            member.save((dir_name.to_owned() + "/population/" + &i.to_string()).as_str());
+        }
+        evolution::save_values(
+            &self.fitness,
+            (dir_name.to_owned() + "/fitness.bin").as_str(),
+        );
+        evolution::save_values(
+            &[self.f, self.crossover_rate],
+            (dir_name.to_owned() + "/rates.bin").as_str(),
+        );
+        evolution::save_rng(&self.rng, (dir_name.to_owned() + "/rng.txt").as_str());
+    }
+
+    fn load(&mut self, dir_name: &str) {
+        for (i, member) in self.population.iter_mut().enumerate() {
+            *member = member.load((dir_name.to_owned() + "/population/" + &i.to_string()).as_str());
+        }
+        self.fitness = evolution::load_values((dir_name.to_owned() + "/fitness.bin").as_str());
+        let rates = evolution::load_values((dir_name.to_owned() + "/rates.bin").as_str());
+        (self.f, self.crossover_rate) = (rates[0], rates[1]);
+        self.rng = evolution::load_rng((dir_name.to_owned() + "/rng.txt").as_str());
+    }
+}
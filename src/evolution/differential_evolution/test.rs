@@ -0,0 +1,147 @@
+#[cfg(test)]
+use crate::evolution;
+#[cfg(test)]
+use crate::evolution::differential_evolution;
+#[cfg(test)]
+use crate::evolution::differential_evolution::Strategy;
+#[cfg(test)]
+use crate::evolution::Evolve;
+#[cfg(test)]
+use crate::models;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use rand::SeedableRng;
+#[cfg(test)]
+use rand_chacha::ChaCha8Rng;
+
+#[cfg(test)]
+fn distance(a: &xor::XorModel, b: &xor::XorModel) -> f32 {
+    let a = models::flatten(a);
+    let b = models::flatten(b);
+
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+#[test]
+fn new_test() {
+    let model = xor::new();
+    let a = differential_evolution::new(&model, 10, 0.1, Strategy::Rand1Bin, 0);
+
+    assert_eq!(a.population.len(), 10);
+    assert!(a.fitness.is_empty());
+    assert!(distance(&a.population[0], &a.population[1]) > 0.0);
+}
+
+#[test]
+#[should_panic]
+fn new_small_population_panic_test() {
+    let _ = differential_evolution::new(&xor::new(), 3, 0.1, Strategy::Rand1Bin, 0);
+}
+
+#[test]
+fn crossover_test() {
+    let target = xor::new();
+    let mutant = xor::new_gaussian_noise();
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    // at least one parameter always comes from the mutant
+    let a = differential_evolution::crossover(&target, &mutant, 0.0, &mut rng);
+    let changed = models::flatten(&a).iter().filter(|v| **v != 0.0).count();
+    assert_eq!(changed, 1);
+
+    let b = differential_evolution::crossover(&target, &mutant, 1.0, &mut rng);
+    assert_eq!(distance(&b, &mutant), 0.0);
+}
+
+#[test]
+fn ask_tell_test() {
+    let mut a = differential_evolution::new(&xor::new(), 5, 0.1, Strategy::Best1Bin, 0);
+
+    // the first ask evaluates the initial population
+    let first = a.ask();
+    assert_eq!(distance(&first[2], &a.population[2]), 0.0);
+    a.tell(&[0.0, 1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(distance(&a.model(), &a.population[4]), 0.0);
+
+    // only trials that are at least as good replace their target
+    let trials = a.ask();
+    assert_eq!(trials.len(), 5);
+    a.tell(&[-1.0, 1.0, 5.0, 3.0, 3.0]);
+    assert_eq!(a.fitness, [0.0, 1.0, 5.0, 3.0, 4.0]);
+    assert_eq!(distance(&a.population[1], &trials[1]), 0.0);
+    assert_eq!(distance(&a.population[2], &trials[2]), 0.0);
+    assert!(distance(&a.population[0], &trials[0]) > 0.0);
+    assert_eq!(distance(&a.model(), &trials[2]), 0.0);
+}
+
+#[test]
+fn nan_fitness_test() {
+    let mut a = differential_evolution::new(&xor::new(), 5, 0.1, Strategy::Jade, 0);
+
+    // NaN counts as the worst fitness, it never wins selection and is always replaced
+    a.ask();
+    a.tell(&[f32::NAN, 1.0, 2.0, 3.0, 4.0]);
+    a.ask();
+    a.tell(&[-1.0, f32::NAN, 2.0, 3.0, 4.0]);
+    assert_eq!(a.fitness, [-1.0, 1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(evolution::best_index(&a.fitness), 4);
+}
+
+#[test]
+fn jade_adaptation_test() {
+    let mut a = differential_evolution::new(&xor::new(), 10, 0.1, Strategy::Jade, 0);
+
+    a.ask();
+    a.tell(&[0.0; 10]);
+    a.ask();
+    assert!(a.trial_f.iter().all(|f| *f > 0.0 && *f <= 1.0));
+    assert!(a
+        .trial_crossover_rate
+        .iter()
+        .all(|cr| (0.0..=1.0).contains(cr)));
+
+    // every trial succeeds so the means move towards the sampled values
+    let expected_crossover_rate =
+        0.9 * 0.9 + 0.1 * a.trial_crossover_rate.iter().sum::<f32>() / 10.0;
+    a.tell(&[1.0; 10]);
+    assert!((a.crossover_rate - expected_crossover_rate).abs() < 1e-6);
+    assert_ne!(a.f, 0.5);
+}
+
+#[test]
+fn run_test() {
+    let target = xor::new_gaussian_noise();
+    let start = xor::new();
+
+    for strategy in [Strategy::Rand1Bin, Strategy::Best1Bin, Strategy::Jade] {
+        let mut a = differential_evolution::new(&start, 30, 1.0, strategy, 1);
+        // best/1 collapses onto the best member before converging with the default rate
+        a.crossover_rate = 0.5;
+
+        let model = evolution::run(&mut a, 500, |m| -distance(m, &target));
+        assert!(distance(&model, &target) < distance(&start, &target) * 0.05);
+    }
+}
+
+#[test]
+fn inject_test() {
+    let mut a = differential_evolution::new(&xor::new(), 4, 0.1, Strategy::Rand1Bin, 0);
+    a.ask();
+    a.tell(&[1.0, 0.0, 3.0, 2.0]);
+
+    // the migrant replaces the worst member only when it is better
+    let migrant = xor::new_gaussian_noise();
+    a.inject(std::slice::from_ref(&migrant), &[-1.0]);
+    assert_eq!(a.fitness, [1.0, 0.0, 3.0, 2.0]);
+    a.inject(std::slice::from_ref(&migrant), &[0.5]);
+    assert_eq!(a.fitness, [1.0, 0.5, 3.0, 2.0]);
+    assert_eq!(distance(&a.population[1], &migrant), 0.0);
+
+    // a NaN member at index 0 is the worst and gets replaced, a NaN migrant never gets in
+    a.fitness[0] = f32::NAN;
+    a.inject(std::slice::from_ref(&migrant), &[f32::NAN]);
+    assert!(a.fitness[0].is_nan());
+    a.inject(std::slice::from_ref(&migrant), &[-5.0]);
+    assert_eq!(a.fitness, [-5.0, 0.5, 3.0, 2.0]);
+}
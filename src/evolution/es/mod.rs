@@ -0,0 +1,141 @@
+pub mod test;
+
+use crate::evolution;
+use crate::evolution::fitness_shaping;
+use crate::evolution::Evolve;
+use crate::models;
+use crate::models::Model;
+use crate::optim::Optimize;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fs;
+
+// evolution strategies (Salimans et al. 2017)
+// candidates are model + sigma * noise, the shaped fitness weights the noise into a
+// gradient estimate which the first order optimizer turns into the next model
+#[derive(Clone)]
+pub struct Es<M: Model, O: Optimize<M>> {
+    pub model: M,
+    pub optimizer: O,
+    pub sigma: f32,
+    pub population_size: usize,
+    pub mirrored: bool,
+    pub fitness_shaping: fitness_shaping::FitnessShaping,
+    pub rng: ChaCha8Rng,
+    pub noise: Vec<M>,
+}
+
+pub fn new<M: Model, O: Optimize<M>>(
+    model: &M,
+    optimizer: O,
+    sigma: f32,
+    population_size: usize,
+    mirrored: bool,
+    fitness_shaping: fitness_shaping::FitnessShaping,
+    seed: u64,
+) -> Es<M, O> {
+    assert!(population_size != 0, "Population size cannot be zero.");
+    assert!(
+        !mirrored || population_size.is_multiple_of(2),
+        "Mirrored sampling needs an even population size."
+    );
+
+    Es {
+        model: model.clone(),
+        optimizer,
+        sigma,
+        population_size,
+        mirrored,
+        fitness_shaping,
+        rng: ChaCha8Rng::seed_from_u64(seed),
+        noise: vec![],
+    }
+}
+
+// sum(weights[i] * noise[i]) / (n * sigma)
+pub fn gradient<M: Model>(noise: &[M], weights: &[f32], sigma: f32) -> M {
+    assert!(noise.len() == weights.len(), "Every noise needs a weight.");
+    assert!(
+        !noise.is_empty(),
+        "Cannot estimate a gradient from no noise."
+    );
+
+    let mut g = noise[0].scalar(weights[0]);
+    for i in 1..noise.len() {
+        g = g.add(&noise[i].scalar(weights[i]));
+    }
+
+    g.scalar(1.0 / (noise.len() as f32 * sigma))
+}
+
+impl<M: Model, O: Optimize<M>> Evolve<M> for Es<M, O> {
+    fn ask(&mut self) -> Vec<M> {
+        self.noise.clear();
+        if self.mirrored {
+            for _ in 0..self.population_size / 2 {
+                let noise = models::gaussian_noise(&self.model, &mut self.rng);
+                self.noise.push(noise.clone());
+                self.noise.push(noise.scalar(-1.0));
+            }
+        } else {
+            for _ in 0..self.population_size {
+                let noise = models::gaussian_noise(&self.model, &mut self.rng);
+                self.noise.push(noise);
+            }
+        }
+
+        self.noise
+            .iter()
+            .map(|noise| self.model.add(&noise.scalar(self.sigma)))
+            .collect()
+    }
+
+    fn tell(&mut self, fitness: &[f32]) {
+        assert!(
+            fitness.len() == self.noise.len(),
+            "Every candidate needs a fitness."
+        );
+
+        let weights = fitness_shaping::shape(self.fitness_shaping, fitness);
+        let g = if self.mirrored {
+            // pairs share their noise so only the difference of each pair is needed
+            let differences = fitness_shaping::mirrored_differences(&weights);
+            let positive_noise: Vec<M> = self.noise.iter().step_by(2).cloned().collect();
+            gradient(&positive_noise, &differences, self.sigma).scalar(0.5)
+        } else {
+            gradient(&self.noise, &weights, self.sigma)
+        };
+
+        self.model = self.optimizer.step(&self.model, &g);
+    }
+
+    fn model(&self) -> M {
+        self.model.clone()
+    }
+
+    fn inject(&mut self, migrants: &[M], fitness: &[f32]) {
+        if let Some(best) = evolution::best_migrant(migrants, fitness) {
+            self.model = best.clone();
+        }
+    }
+
+    fn sigma(&self) -> Option<f32> {
+        Some(self.sigma)
+    }
+
+    fn save(&self, dir_name: &str) {
+        fs::create_dir_all(dir_name).unwrap();
+        self.model.save((dir_name.to_owned() + "/model").as_str());
+        self.optimizer
+            .save((dir_name.to_owned() + "/optimizer").as_str());
+        evolution::save_rng(&self.rng, (dir_name.to_owned() + "/rng.txt").as_str());
+    }
+
+    fn load(&mut self, dir_name: &str) {
+        self.model = self.model.load((dir_name.to_owned() + "/model").as_str());
+        self.optimizer = self
+            .optimizer
+            .load((dir_name.to_owned() + "/optimizer").as_str());
+        self.rng = evolution::load_rng((dir_name.to_owned() + "/rng.txt").as_str());
+    }
+}
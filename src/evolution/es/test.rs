@@ -0,0 +1,169 @@
+#[cfg(test)]
+use crate::evolution;
+#[cfg(test)]
+use crate::evolution::es;
+#[cfg(test)]
+use crate::evolution::fitness_shaping::FitnessShaping;
+#[cfg(test)]
+use crate::evolution::Evolve;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use crate::optim::adam;
+#[cfg(test)]
+use crate::optim::momentum;
+
+// fitness = -|model - target|^2
+#[cfg(test)]
+fn distance(a: &xor::XorModel, b: &xor::XorModel) -> f32 {
+    let mut sum: f32 = 0.0;
+    for (i, (_, m)) in a.parameters().into_iter().enumerate() {
+        let t = b.parameters()[i].1;
+        for j in 0..m.value.len() {
+            sum += (m.value[j] - t.value[j]).powi(2);
+        }
+    }
+
+    sum
+}
+
+#[test]
+fn new_test() {
+    let model = xor::new();
+    let a = es::new(
+        &model,
+        momentum::new(&model, 0.1, 0.0),
+        0.1,
+        10,
+        true,
+        FitnessShaping::CenteredRanks,
+        0,
+    );
+
+    assert_eq!(a.population_size, 10);
+    assert_eq!(a.sigma, 0.1);
+    assert!(a.noise.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn new_mirrored_odd_panic_test() {
+    let model = xor::new();
+    let _ = es::new(
+        &model,
+        momentum::new(&model, 0.1, 0.0),
+        0.1,
+        9,
+        true,
+        FitnessShaping::Raw,
+        0,
+    );
+}
+
+#[test]
+fn gradient_test() {
+    let a = xor::new_gaussian_noise();
+    let b = xor::new_gaussian_noise();
+
+    let g = es::gradient(&[a.clone(), b.clone()], &[1.0, -2.0], 0.5);
+    let expected = a.add(&b.scalar(-2.0)).scalar(1.0 / (2.0 * 0.5));
+    for (i, (_, m)) in g.parameters().into_iter().enumerate() {
+        let e = expected.parameters()[i].1;
+        for j in 0..m.value.len() {
+            assert!((m.value[j] - e.value[j]).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn ask_mirrored_test() {
+    let model = xor::new_gaussian_noise();
+    let mut a = es::new(
+        &model,
+        momentum::new(&model, 0.1, 0.0),
+        0.1,
+        6,
+        true,
+        FitnessShaping::CenteredRanks,
+        0,
+    );
+
+    let candidates = a.ask();
+    assert_eq!(candidates.len(), 6);
+
+    // each pair is symmetric around the model
+    for pair in candidates.chunks(2) {
+        let middle = pair[0].add(&pair[1]).scalar(0.5);
+        assert!(distance(&middle, &model) < 1e-8);
+        assert!(distance(&pair[0], &model) > 0.0);
+    }
+}
+
+#[test]
+fn ask_seed_test() {
+    let model = xor::new_gaussian_noise();
+    let new_es = |seed: u64| {
+        es::new(
+            &model,
+            momentum::new(&model, 0.1, 0.0),
+            0.1,
+            4,
+            false,
+            FitnessShaping::Raw,
+            seed,
+        )
+    };
+
+    let first = new_es(7).ask();
+    let second = new_es(7).ask();
+    let third = new_es(8).ask();
+    for i in 0..4 {
+        assert_eq!(distance(&first[i], &second[i]), 0.0);
+        assert!(distance(&first[i], &third[i]) > 0.0);
+    }
+}
+
+#[test]
+#[should_panic]
+fn tell_length_panic_test() {
+    let model = xor::new();
+    let mut a = es::new(
+        &model,
+        momentum::new(&model, 0.1, 0.0),
+        0.1,
+        4,
+        false,
+        FitnessShaping::Raw,
+        0,
+    );
+
+    a.ask();
+    a.tell(&[1.0, 2.0]);
+}
+
+#[test]
+fn run_test() {
+    let target = xor::new_gaussian_noise();
+    let start = xor::new();
+
+    for (mirrored, fitness_shaping) in [
+        (true, FitnessShaping::CenteredRanks),
+        (false, FitnessShaping::Utilities),
+        (true, FitnessShaping::ZScore),
+    ] {
+        let mut a = es::new(
+            &start,
+            adam::new(&start, 0.05),
+            0.05,
+            20,
+            mirrored,
+            fitness_shaping,
+            1,
+        );
+
+        let model = evolution::run(&mut a, 300, |m| -distance(m, &target));
+        assert!(distance(&model, &target) < distance(&start, &target) * 0.05);
+    }
+}
@@ -0,0 +1,118 @@
+pub mod test;
+
+use std::cmp::Ordering;
+
+// how raw fitness is turned into the weights of an evolution strategies update
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FitnessShaping {
+    Raw,
+    CenteredRanks,
+    Utilities,
+    ZScore,
+}
+
+pub fn shape(fitness_shaping: FitnessShaping, fitness: &[f32]) -> Vec<f32> {
+    match fitness_shaping {
+        FitnessShaping::Raw => fitness.to_vec(),
+        FitnessShaping::CenteredRanks => centered_ranks(fitness),
+        FitnessShaping::Utilities => utilities(fitness),
+        FitnessShaping::ZScore => z_score(fitness),
+    }
+}
+
+// total order on fitness where NaN, e.g. from a diverging policy, is worse than any number
+pub fn compare(a: f32, b: f32) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.total_cmp(&b),
+    }
+}
+
+// rank 0 is the lowest fitness, tied values share the mean of their ranks
+pub fn ranks(fitness: &[f32]) -> Vec<f32> {
+    let mut order: Vec<usize> = (0..fitness.len()).collect();
+    order.sort_by(|a, b| compare(fitness[*a], fitness[*b]));
+
+    let mut b = vec![0.0; fitness.len()];
+    let mut i = 0;
+    while i < order.len() {
+        let mut j = i;
+        while j + 1 < order.len() && fitness[order[j + 1]] == fitness[order[i]] {
+            j += 1;
+        }
+        let rank = (i + j) as f32 / 2.0;
+        for index in order.iter().take(j + 1).skip(i) {
+            b[*index] = rank;
+        }
+        i = j + 1;
+    }
+
+    b
+}
+
+// ranks scaled to [-0.5, 0.5] (Salimans et al. 2017)
+pub fn centered_ranks(fitness: &[f32]) -> Vec<f32> {
+    if fitness.len() < 2 {
+        return vec![0.0; fitness.len()];
+    }
+
+    ranks(fitness)
+        .iter()
+        .map(|rank| rank / (fitness.len() - 1) as f32 - 0.5)
+        .collect()
+}
+
+// nes utility weights (Wierstra et al. 2014), only the better half gets a positive weight
+// u_k = max(0, ln(n / 2 + 1) - ln(k)) / sum - 1 / n where k = 1 is the best
+pub fn utilities(fitness: &[f32]) -> Vec<f32> {
+    let n = fitness.len();
+    let mut raw: Vec<f32> = Vec::with_capacity(n);
+    for k in 1..=n {
+        raw.push(((n as f32 / 2.0 + 1.0).ln() - (k as f32).ln()).max(0.0));
+    }
+    let sum: f32 = raw.iter().sum();
+
+    let ranks = ranks(fitness);
+    let mut b: Vec<f32> = Vec::with_capacity(n);
+    for rank in ranks.iter() {
+        // best fitness has the highest rank, ties are rounded towards the better utility
+        let k = n - 1 - rank.ceil() as usize;
+        b.push(raw[k] / sum - 1.0 / n as f32);
+    }
+
+    b
+}
+
+pub fn z_score(fitness: &[f32]) -> Vec<f32> {
+    if fitness.is_empty() {
+        return vec![];
+    }
+
+    let mean = fitness.iter().sum::<f32>() / fitness.len() as f32;
+    let variance = fitness.iter().map(|f| (f - mean).powi(2)).sum::<f32>() / fitness.len() as f32;
+    let std = variance.sqrt();
+
+    fitness
+        .iter()
+        .map(|f| if std == 0.0 { 0.0 } else { (f - mean) / std })
+        .collect()
+}
+
+pub fn clip(fitness: &[f32], low: f32, high: f32) -> Vec<f32> {
+    assert!(low <= high, "Low must not be larger than high.");
+
+    fitness.iter().map(|f| f.clamp(low, high)).collect()
+}
+
+// mirrored sampling evaluates the pairs model + noise, model - noise next to each other
+// returns f(model + noise) - f(model - noise) for every pair
+pub fn mirrored_differences(fitness: &[f32]) -> Vec<f32> {
+    assert!(
+        fitness.len().is_multiple_of(2),
+        "Mirrored fitness must come in pairs."
+    );
+
+    fitness.chunks(2).map(|pair| pair[0] - pair[1]).collect()
+}
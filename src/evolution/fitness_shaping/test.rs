@@ -0,0 +1,110 @@
+#[cfg(test)]
+use crate::evolution::fitness_shaping;
+#[cfg(test)]
+use std::cmp::Ordering;
+
+#[test]
+fn ranks_test() {
+    let ranks = fitness_shaping::ranks(&[3.0, -1.0, 10.0, 3.0, 0.0]);
+    assert_eq!(ranks, [2.5, 0.0, 4.0, 2.5, 1.0]);
+
+    // NaN ranks below every number instead of panicking
+    let ranks = fitness_shaping::ranks(&[1.0, f32::NAN, -5.0]);
+    assert_eq!(ranks, [2.0, 0.0, 1.0]);
+}
+
+#[test]
+fn compare_test() {
+    assert_eq!(fitness_shaping::compare(1.0, 2.0), Ordering::Less);
+    assert_eq!(fitness_shaping::compare(f32::NAN, f32::MIN), Ordering::Less);
+    assert_eq!(
+        fitness_shaping::compare(f32::NEG_INFINITY, f32::NAN),
+        Ordering::Greater
+    );
+    assert_eq!(
+        fitness_shaping::compare(f32::NAN, f32::NAN),
+        Ordering::Equal
+    );
+}
+
+#[test]
+fn centered_ranks_test() {
+    let ranks = fitness_shaping::centered_ranks(&[3.0, -1.0, 1000.0, 0.0, 5.0]);
+    assert_eq!(ranks, [0.0, -0.5, 0.5, -0.25, 0.25]);
+
+    assert_eq!(fitness_shaping::centered_ranks(&[4.0]), [0.0]);
+}
+
+#[test]
+fn utilities_test() {
+    let fitness = [1.0, 4.0, 3.0, 2.0];
+    let utilities = fitness_shaping::utilities(&fitness);
+
+    // utilities sum to zero and only the better half is positive
+    assert!(utilities.iter().sum::<f32>().abs() < 1e-6);
+    assert!(utilities[1] > utilities[2]);
+    assert!(utilities[2] > 0.0);
+    assert!(utilities[3] < 0.0);
+    assert_eq!(utilities[0], utilities[3]);
+
+    // outliers do not change the weights
+    let outlier = fitness_shaping::utilities(&[1.0, 4000.0, 3.0, 2.0]);
+    assert_eq!(outlier, utilities);
+}
+
+#[test]
+fn z_score_test() {
+    let z = fitness_shaping::z_score(&[2.0, 4.0, 1.0, 7.0]);
+    let std = 5.25_f32.sqrt();
+    let expected = [-1.5 / std, 0.5 / std, -2.5 / std, 3.5 / std];
+    for (i, val) in z.iter().enumerate() {
+        assert!((val - expected[i]).abs() < 1e-6);
+    }
+
+    assert_eq!(fitness_shaping::z_score(&[3.0, 3.0]), [0.0, 0.0]);
+}
+
+#[test]
+fn clip_test() {
+    let clipped = fitness_shaping::clip(&[-5.0, 0.5, 12.0], -1.0, 1.0);
+    assert_eq!(clipped, [-1.0, 0.5, 1.0]);
+}
+
+#[test]
+#[should_panic]
+fn clip_panic_test() {
+    let _ = fitness_shaping::clip(&[1.0], 1.0, -1.0);
+}
+
+#[test]
+fn shape_test() {
+    let fitness = [3.0, -1.0, 1000.0, 0.0, 5.0];
+    assert_eq!(
+        fitness_shaping::shape(fitness_shaping::FitnessShaping::Raw, &fitness),
+        fitness
+    );
+    assert_eq!(
+        fitness_shaping::shape(fitness_shaping::FitnessShaping::CenteredRanks, &fitness),
+        fitness_shaping::centered_ranks(&fitness)
+    );
+    assert_eq!(
+        fitness_shaping::shape(fitness_shaping::FitnessShaping::Utilities, &fitness),
+        fitness_shaping::utilities(&fitness)
+    );
+    assert_eq!(
+        fitness_shaping::shape(fitness_shaping::FitnessShaping::ZScore, &fitness),
+        fitness_shaping::z_score(&fitness)
+    );
+}
+
+#[test]
+fn mirrored_differences_test() {
+    let differences = fitness_shaping::mirrored_differences(&[3.0, 1.0, -2.0, 4.0]);
+    assert_eq!(differences, [2.0, -6.0]);
+}
+
+#[test]
+#[should_panic]
+fn mirrored_differences_panic_test() {
+    let _ = fitness_shaping::mirrored_differences(&[3.0, 1.0, -2.0]);
+}
@@ -0,0 +1,73 @@
+pub mod test;
+
+use crate::evolution;
+use crate::evolution::fitness_shaping;
+use crate::models::Model;
+use std::cmp::Ordering;
+use std::fs;
+
+// best candidates seen over a whole run, which the final model of an optimizer may not be
+pub struct HallOfFame<M: Model> {
+    pub size: usize,
+    // highest fitness first
+    pub models: Vec<M>,
+    pub fitness: Vec<f32>,
+}
+
+pub fn new<M: Model>(size: usize) -> HallOfFame<M> {
+    assert!(size != 0, "Hall of fame size cannot be zero.");
+
+    HallOfFame {
+        size,
+        models: vec![],
+        fitness: vec![],
+    }
+}
+
+// keeps the best size of the members and candidates, earlier members win ties
+// candidates with NaN fitness are never kept
+pub fn update<M: Model>(a: &mut HallOfFame<M>, candidates: &[M], fitness: &[f32]) {
+    assert!(
+        candidates.len() == fitness.len(),
+        "Every candidate needs a fitness."
+    );
+
+    for (candidate, value) in candidates.iter().zip(fitness.iter()) {
+        if value.is_nan() {
+            continue;
+        }
+        let position = a
+            .fitness
+            .iter()
+            .position(|f| fitness_shaping::compare(*value, *f) == Ordering::Greater)
+            .unwrap_or(a.fitness.len());
+        if position < a.size {
+            a.models.insert(position, candidate.clone());
+            a.fitness.insert(position, *value);
+            a.models.truncate(a.size);
+            a.fitness.truncate(a.size);
+        }
+    }
+}
+
+pub fn save<M: Model>(a: &HallOfFame<M>, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    for (i, model) in a.models.iter().enumerate() {
+        model.save((dir_name.to_owned() + "/" + &i.to_string()).as_str());
+    }
+    evolution::save_values(&a.fitness, (dir_name.to_owned() + "/fitness.bin").as_str());
+}
+
+// template gives the shape of the models
+pub fn load<M: Model>(template: &M, size: usize, dir_name: &str) -> HallOfFame<M> {
+    let fitness = evolution::load_values((dir_name.to_owned() + "/fitness.bin").as_str());
+    let models = (0..fitness.len())
+        .map(|i| template.load((dir_name.to_owned() + "/" + &i.to_string()).as_str()))
+        .collect();
+
+    HallOfFame {
+        size,
+        models,
+        fitness,
+    }
+}
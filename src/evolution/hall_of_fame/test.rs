@@ -0,0 +1,58 @@
+#[cfg(test)]
+use crate::evolution::hall_of_fame;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use std::fs;
+
+#[cfg(test)]
+fn model(value: f32) -> xor::XorModel {
+    let mut a = xor::new();
+    a.parameters_mut()[0].value[0] = value;
+    a
+}
+
+#[test]
+fn update_test() {
+    let mut a = hall_of_fame::new(3);
+
+    hall_of_fame::update(&mut a, &[model(1.0), model(5.0)], &[1.0, 5.0]);
+    assert_eq!(a.fitness, vec![5.0, 1.0]);
+    hall_of_fame::update(
+        &mut a,
+        &[model(2.0), model(0.0), model(5.5), model(1.5)],
+        &[2.0, 0.0, 5.0, 1.5],
+    );
+
+    // the earlier 5 stays ahead of the later one
+    assert_eq!(a.fitness, vec![5.0, 5.0, 2.0]);
+    assert_eq!(a.models[0].parameters()[0].1.value[0], 5.0);
+    assert_eq!(a.models[1].parameters()[0].1.value[0], 5.5);
+
+    // NaN fitness is skipped, even when the hall of fame has room
+    let mut b = hall_of_fame::new(3);
+    hall_of_fame::update(&mut b, &[model(9.0), model(1.0)], &[f32::NAN, 1.0]);
+    hall_of_fame::update(&mut b, &[model(2.0)], &[2.0]);
+    assert_eq!(b.fitness, vec![2.0, 1.0]);
+}
+
+#[test]
+fn save_load_test() {
+    let mut a = hall_of_fame::new(2);
+    hall_of_fame::update(&mut a, &[model(1.0), model(3.0)], &[1.0, 3.0]);
+
+    hall_of_fame::save(&a, "hall_of_fame_save_load_test");
+    let b = hall_of_fame::load(&xor::new(), 2, "hall_of_fame_save_load_test");
+    fs::remove_dir_all("hall_of_fame_save_load_test").unwrap();
+
+    assert_eq!(b.fitness, a.fitness);
+    assert_eq!(b.models[0].parameters()[0].1.value[0], 3.0);
+}
+
+#[test]
+#[should_panic]
+fn new_panic_test() {
+    let _: hall_of_fame::HallOfFame<xor::XorModel> = hall_of_fame::new(0);
+}
@@ -0,0 +1,300 @@
+pub mod test;
+
+use crate::evolution;
+use crate::evolution::fitness_shaping;
+use crate::evolution::Evolve;
+use crate::models::Model;
+use rand::seq::index;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fs;
+
+// which islands receive the migrants of island i
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Topology {
+    // island i + 1
+    Ring,
+    // one other island chosen at random every migration
+    Random,
+    // every other island
+    FullyConnected,
+}
+
+// which of the last generation's candidates leave an island
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MigrantSelection {
+    Best,
+    Random,
+}
+
+#[derive(Clone, Debug)]
+pub struct IslandStats {
+    pub best_fitness: f32,
+    pub mean_fitness: f32,
+    pub best_fitness_ever: f32,
+    pub immigrants: usize,
+}
+
+// sub-populations evolve independently with any optimizer and exchange candidates
+// every migration_interval generations, migrants only enter an island they improve on
+pub struct Islands<M: Model> {
+    pub islands: Vec<Box<dyn Evolve<M>>>,
+    pub topology: Topology,
+    pub migration_interval: usize,
+    pub num_migrants: usize,
+    pub migrant_selection: MigrantSelection,
+    pub rng: ChaCha8Rng,
+    pub generation: usize,
+    pub stats: Vec<IslandStats>,
+    // candidates of the last generation, per island
+    pub candidates: Vec<Vec<M>>,
+    pub fitness: Vec<Vec<f32>>,
+}
+
+pub fn new<M: Model>(
+    islands: Vec<Box<dyn Evolve<M>>>,
+    topology: Topology,
+    migration_interval: usize,
+    num_migrants: usize,
+    migrant_selection: MigrantSelection,
+    seed: u64,
+) -> Islands<M> {
+    assert!(islands.len() >= 2, "Island model needs at least 2 islands.");
+    assert!(
+        migration_interval != 0,
+        "Migration interval cannot be zero."
+    );
+
+    let num_islands = islands.len();
+    Islands {
+        islands,
+        topology,
+        migration_interval,
+        num_migrants,
+        migrant_selection,
+        rng: ChaCha8Rng::seed_from_u64(seed),
+        generation: 0,
+        stats: vec![
+            IslandStats {
+                best_fitness: f32::MIN,
+                mean_fitness: f32::MIN,
+                best_fitness_ever: f32::MIN,
+                immigrants: 0,
+            };
+            num_islands
+        ],
+        candidates: vec![vec![]; num_islands],
+        fitness: vec![vec![]; num_islands],
+    }
+}
+
+pub fn destinations<M: Model>(a: &mut Islands<M>, island: usize) -> Vec<usize> {
+    let n = a.islands.len();
+    match a.topology {
+        Topology::Ring => vec![(island + 1) % n],
+        Topology::Random => {
+            let other = a.rng.gen_range(0..n - 1);
+            vec![if other >= island { other + 1 } else { other }]
+        }
+        Topology::FullyConnected => (0..n).filter(|i| *i != island).collect(),
+    }
+}
+
+// indices of the candidates of an island that emigrate
+pub fn select_migrants<M: Model>(a: &mut Islands<M>, island: usize) -> Vec<usize> {
+    let fitness = &a.fitness[island];
+    let num_migrants = a.num_migrants.min(fitness.len());
+    match a.migrant_selection {
+        MigrantSelection::Best => {
+            let mut order: Vec<usize> = (0..fitness.len()).collect();
+            order.sort_by(|x, y| fitness_shaping::compare(fitness[*y], fitness[*x]));
+            order.truncate(num_migrants);
+            order
+        }
+        MigrantSelection::Random => {
+            index::sample(&mut a.rng, fitness.len(), num_migrants).into_vec()
+        }
+    }
+}
+
+pub fn migrate<M: Model>(a: &mut Islands<M>) {
+    // everyone leaves before anyone arrives so migrants move one step per migration
+    let mut arrivals: Vec<(Vec<M>, Vec<f32>)> = vec![(vec![], vec![]); a.islands.len()];
+    for island in 0..a.islands.len() {
+        let migrants = select_migrants(a, island);
+        for destination in destinations(a, island) {
+            for i in migrants.iter() {
+                arrivals[destination]
+                    .0
+                    .push(a.candidates[island][*i].clone());
+                arrivals[destination].1.push(a.fitness[island][*i]);
+            }
+        }
+    }
+
+    for (island, (migrants, fitness)) in arrivals.into_iter().enumerate() {
+        let mut accepted: Vec<M> = vec![];
+        let mut accepted_fitness: Vec<f32> = vec![];
+        for (migrant, value) in migrants.into_iter().zip(fitness) {
+            if value > a.stats[island].best_fitness {
+                accepted.push(migrant);
+                accepted_fitness.push(value);
+            }
+        }
+
+        a.stats[island].immigrants += accepted.len();
+        a.islands[island].inject(&accepted, &accepted_fitness);
+    }
+}
+
+// statistics of every island followed by all islands combined
+pub fn report<M: Model>(a: &Islands<M>) -> Vec<IslandStats> {
+    let mut b = a.stats.clone();
+
+    let all: Vec<f32> = a.fitness.iter().flatten().cloned().collect();
+    b.push(IslandStats {
+        best_fitness: all.iter().cloned().fold(f32::MIN, f32::max),
+        mean_fitness: if all.is_empty() {
+            f32::MIN
+        } else {
+            all.iter().sum::<f32>() / all.len() as f32
+        },
+        best_fitness_ever: a
+            .stats
+            .iter()
+            .map(|s| s.best_fitness_ever)
+            .fold(f32::MIN, f32::max),
+        immigrants: a.stats.iter().map(|s| s.immigrants).sum(),
+    });
+
+    b
+}
+
+pub fn print_report<M: Model>(a: &Islands<M>) {
+    println!(
+        "{:<8} {:>14} {:>14} {:>14} {:>10}",
+        "island", "best fitness", "mean fitness", "best ever", "immigrants"
+    );
+    let report = report(a);
+    for (i, stats) in report.iter().enumerate() {
+        let name = if i == a.islands.len() {
+            "all".to_string()
+        } else {
+            i.to_string()
+        };
+        println!(
+            "{:<8} {:>14.4} {:>14.4} {:>14.4} {:>10}",
+            name, stats.best_fitness, stats.mean_fitness, stats.best_fitness_ever, stats.immigrants
+        );
+    }
+    println!();
+}
+
+impl<M: Model> Evolve<M> for Islands<M> {
+    // candidates of every island one after the other
+    fn ask(&mut self) -> Vec<M> {
+        for (island, evolve) in self.islands.iter_mut().enumerate() {
+            self.candidates[island] = evolve.ask();
+        }
+
+        self.candidates.iter().flatten().cloned().collect()
+    }
+
+    fn tell(&mut self, fitness: &[f32]) {
+        let num_candidates: usize = self.candidates.iter().map(|c| c.len()).sum();
+        assert!(
+            fitness.len() == num_candidates,
+            "Every candidate needs a fitness."
+        );
+
+        let mut start = 0;
+        for island in 0..self.islands.len() {
+            let end = start + self.candidates[island].len();
+            let island_fitness = &fitness[start..end];
+            self.islands[island].tell(island_fitness);
+            self.fitness[island] = island_fitness.to_vec();
+
+            let stats = &mut self.stats[island];
+            stats.best_fitness = island_fitness.iter().cloned().fold(f32::MIN, f32::max);
+            stats.mean_fitness = island_fitness.iter().sum::<f32>() / island_fitness.len() as f32;
+            stats.best_fitness_ever = stats.best_fitness_ever.max(stats.best_fitness);
+            start = end;
+        }
+
+        self.generation += 1;
+        if self.generation.is_multiple_of(self.migration_interval) && self.num_migrants != 0 {
+            migrate(self);
+        }
+    }
+
+    // model of the island with the best candidate in the last generation
+    fn model(&self) -> M {
+        let best: Vec<f32> = self.stats.iter().map(|s| s.best_fitness).collect();
+        self.islands[evolution::best_index(&best)].model()
+    }
+
+    // migrants from outside enter every island
+    fn inject(&mut self, migrants: &[M], fitness: &[f32]) {
+        for evolve in self.islands.iter_mut() {
+            evolve.inject(migrants, fitness);
+        }
+    }
+
+    // mean over the islands that have one
+    fn sigma(&self) -> Option<f32> {
+        let sigmas: Vec<f32> = self.islands.iter().filter_map(|e| e.sigma()).collect();
+        if sigmas.is_empty() {
+            return None;
+        }
+
+        Some(sigmas.iter().sum::<f32>() / sigmas.len() as f32)
+    }
+
+    fn save(&self, dir_name: &str) {
+        fs::create_dir_all(dir_name).unwrap();
+        for (i, evolve) in self.islands.iter().enumerate() {
+            evolve.save((dir_name.to_owned() + "/islands/" + &i.to_string()).as_str());
+        }
+        evolution::save_rng(&self.rng, (dir_name.to_owned() + "/rng.txt").as_str());
+        fs::write(
+            dir_name.to_owned() + "/generation.txt",
+            self.generation.to_string(),
+        )
+        .unwrap();
+
+        // one line per island, best_fitness,mean_fitness,best_fitness_ever,immigrants
+        let stats: Vec<String> = self
+            .stats
+            .iter()
+            .map(|s| {
+                format!(
+                    "{},{},{},{}",
+                    s.best_fitness, s.mean_fitness, s.best_fitness_ever, s.immigrants
+                )
+            })
+            .collect();
+        fs::write(dir_name.to_owned() + "/stats.csv", stats.join("\n")).unwrap();
+    }
+
+    fn load(&mut self, dir_name: &str) {
+        for (i, evolve) in self.islands.iter_mut().enumerate() {
+            evolve.load((dir_name.to_owned() + "/islands/" + &i.to_string()).as_str());
+        }
+        self.rng = evolution::load_rng((dir_name.to_owned() + "/rng.txt").as_str());
+        self.generation = fs::read_to_string(dir_name.to_owned() + "/generation.txt")
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+
+        let stats = fs::read_to_string(dir_name.to_owned() + "/stats.csv").unwrap();
+        for (s, line) in self.stats.iter_mut().zip(stats.lines()) {
+            let values: Vec<&str> = line.split(',').collect();
+            s.best_fitness = values[0].parse().unwrap();
+            s.mean_fitness = values[1].parse().unwrap();
+            s.best_fitness_ever = values[2].parse().unwrap();
+            s.immigrants = values[3].parse().unwrap();
+        }
+    }
+}
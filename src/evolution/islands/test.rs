@@ -0,0 +1,152 @@
+#[cfg(test)]
+use crate::evolution;
+#[cfg(test)]
+use crate::evolution::cem;
+#[cfg(test)]
+use crate::evolution::differential_evolution;
+#[cfg(test)]
+use crate::evolution::islands;
+#[cfg(test)]
+use crate::evolution::islands::MigrantSelection;
+#[cfg(test)]
+use crate::evolution::islands::Topology;
+#[cfg(test)]
+use crate::evolution::snes;
+#[cfg(test)]
+use crate::evolution::Evolve;
+#[cfg(test)]
+use crate::models;
+#[cfg(test)]
+use crate::models::xor;
+
+#[cfg(test)]
+fn distance(a: &xor::XorModel, b: &xor::XorModel) -> f32 {
+    let a = models::flatten(a);
+    let b = models::flatten(b);
+
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+#[cfg(test)]
+fn cem_islands(
+    n: usize,
+    topology: Topology,
+    migration_interval: usize,
+) -> islands::Islands<xor::XorModel> {
+    let mut b: Vec<Box<dyn Evolve<xor::XorModel>>> = vec![];
+    for seed in 0..n {
+        b.push(Box::new(cem::new(&xor::new(), 1.0, 2, 0.5, seed as u64)));
+    }
+
+    islands::new(
+        b,
+        topology,
+        migration_interval,
+        1,
+        MigrantSelection::Best,
+        0,
+    )
+}
+
+#[test]
+#[should_panic]
+fn new_single_island_panic_test() {
+    let _ = cem_islands(1, Topology::Ring, 1);
+}
+
+#[test]
+fn destinations_test() {
+    let mut a = cem_islands(3, Topology::Ring, 1);
+    assert_eq!(islands::destinations(&mut a, 0), [1]);
+    assert_eq!(islands::destinations(&mut a, 2), [0]);
+
+    a.topology = Topology::FullyConnected;
+    assert_eq!(islands::destinations(&mut a, 1), [0, 2]);
+
+    a.topology = Topology::Random;
+    for _ in 0..20 {
+        let b = islands::destinations(&mut a, 1);
+        assert_eq!(b.len(), 1);
+        assert_ne!(b[0], 1);
+    }
+}
+
+#[test]
+fn select_migrants_test() {
+    let mut a = cem_islands(2, Topology::Ring, 1);
+    a.num_migrants = 2;
+    a.fitness[0] = vec![1.0, 3.0, 2.0];
+
+    assert_eq!(islands::select_migrants(&mut a, 0), [1, 2]);
+    // a NaN candidate never emigrates ahead of a numeric one
+    a.fitness[0] = vec![f32::NAN, 3.0, 2.0];
+    assert_eq!(islands::select_migrants(&mut a, 0), [1, 2]);
+
+    a.migrant_selection = MigrantSelection::Random;
+    let b = islands::select_migrants(&mut a, 0);
+    assert_eq!(b.len(), 2);
+    assert_ne!(b[0], b[1]);
+}
+
+#[test]
+fn migration_test() {
+    let mut a = cem_islands(2, Topology::Ring, 2);
+
+    // no migration on the first generation
+    let candidates = a.ask();
+    assert_eq!(candidates.len(), 4);
+    a.tell(&[1.0, 2.0, 0.0, 0.5]);
+    assert_eq!(a.stats[1].immigrants, 0);
+
+    // the best candidate of island 0 improves on island 1, not the other way around
+    let candidates = a.ask();
+    a.tell(&[1.0, 5.0, 0.0, 0.5]);
+    assert_eq!(a.stats[0].immigrants, 0);
+    assert_eq!(a.stats[1].immigrants, 1);
+    assert_eq!(distance(&a.islands[1].model(), &candidates[1]), 0.0);
+
+    let report = islands::report(&a);
+    assert_eq!(report.len(), 3);
+    assert_eq!(report[0].best_fitness, 5.0);
+    assert_eq!(report[1].mean_fitness, 0.25);
+    assert_eq!(report[2].best_fitness, 5.0);
+    assert_eq!(report[2].best_fitness_ever, 5.0);
+    assert_eq!(report[2].immigrants, 1);
+
+    // pass = does not panic
+    islands::print_report(&a);
+}
+
+#[test]
+#[should_panic]
+fn tell_length_panic_test() {
+    let mut a = cem_islands(2, Topology::Ring, 1);
+
+    a.ask();
+    a.tell(&[1.0, 2.0]);
+}
+
+#[test]
+fn run_test() {
+    let target = xor::new_gaussian_noise();
+    let start = xor::new();
+
+    for topology in [Topology::Ring, Topology::Random, Topology::FullyConnected] {
+        // any mix of optimizers can share an archipelago
+        let b: Vec<Box<dyn Evolve<xor::XorModel>>> = vec![
+            Box::new(snes::new(&start, 0.5, 0)),
+            Box::new(cem::new(&start, 1.0, 50, 0.2, 1)),
+            Box::new(differential_evolution::new(
+                &start,
+                30,
+                1.0,
+                differential_evolution::Strategy::Jade,
+                2,
+            )),
+        ];
+
+        let mut a = islands::new(b, topology, 10, 2, MigrantSelection::Best, 3);
+        let model = evolution::run(&mut a, 200, |m| -distance(m, &target));
+        assert!(distance(&model, &target) < distance(&start, &target) * 0.05);
+    }
+}
@@ -0,0 +1,335 @@
+pub mod test;
+
+use crate::evolution::fitness_shaping;
+use crate::evolution::Evolve;
+use crate::models;
+use crate::models::Model;
+use serde_json::json;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::Instant;
+
+// where every generation's metrics go
+#[derive(Clone, Debug, PartialEq)]
+pub enum Sink {
+    // aligned columns on stdout, without the per layer norms
+    Table,
+    // appended, the header is only written to an empty file so resumed runs continue the log
+    Csv(String),
+    Jsonl(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    pub generation: usize,
+    pub best_fitness: f32,
+    pub mean_fitness: f32,
+    pub median_fitness: f32,
+    pub std_fitness: f32,
+    // l2 norm of every parameter matrix of the optimizer's model, by name
+    pub parameter_norms: Vec<(String, f32)>,
+    // l2 norm of the change of the optimizer's model this generation
+    pub update_norm: f32,
+    pub sigma: Option<f32>,
+    // since the first logged generation, across resumes
+    pub seconds: f32,
+    pub evaluations: usize,
+    pub evaluations_per_second: f32,
+}
+
+pub struct Logger {
+    pub sinks: Vec<Sink>,
+    pub start: Instant,
+    pub last: Instant,
+    // flattened model of the previous generation
+    pub previous: Vec<f32>,
+    // cumulative over the whole log, truncate restores both from the last kept row
+    pub evaluations: usize,
+    pub resumed_seconds: f32,
+    pub rows: usize,
+}
+
+// model is the optimizer's model before the first logged generation
+pub fn new<M: Model>(sinks: Vec<Sink>, model: &M) -> Logger {
+    let now = Instant::now();
+
+    Logger {
+        sinks,
+        start: now,
+        last: now,
+        previous: models::flatten(model),
+        evaluations: 0,
+        resumed_seconds: 0.0,
+        rows: 0,
+    }
+}
+
+// best, mean, median and population standard deviation
+// NaN fitness sorts lowest, best stays a number while any candidate has one
+pub fn summary(fitness: &[f32]) -> (f32, f32, f32, f32) {
+    assert!(!fitness.is_empty(), "Cannot summarize no fitness.");
+
+    let n = fitness.len() as f32;
+    let mut sorted = fitness.to_vec();
+    sorted.sort_by(|a, b| fitness_shaping::compare(*a, *b));
+    let mean = fitness.iter().sum::<f32>() / n;
+    let middle = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] + sorted[middle]) / 2.0
+    } else {
+        sorted[middle]
+    };
+    let std = (fitness.iter().map(|f| (f - mean).powi(2)).sum::<f32>() / n).sqrt();
+
+    (sorted[sorted.len() - 1], mean, median, std)
+}
+
+pub fn norm(values: &[f32]) -> f32 {
+    values.iter().map(|v| v * v).sum::<f32>().sqrt()
+}
+
+pub fn parameter_norms<M: Model>(model: &M) -> Vec<(String, f32)> {
+    model
+        .parameters()
+        .into_iter()
+        .map(|(name, m)| (name, norm(&m.value)))
+        .collect()
+}
+
+// metrics of one generation after tell, written to every sink
+pub fn log<M: Model>(
+    a: &mut Logger,
+    generation: usize,
+    model: &M,
+    fitness: &[f32],
+    sigma: Option<f32>,
+) -> Metrics {
+    let (best_fitness, mean_fitness, median_fitness, std_fitness) = summary(fitness);
+    let current = models::flatten(model);
+    let update: Vec<f32> = current
+        .iter()
+        .zip(a.previous.iter())
+        .map(|(x, y)| x - y)
+        .collect();
+    let now = Instant::now();
+    a.evaluations += fitness.len();
+
+    let b = Metrics {
+        generation,
+        best_fitness,
+        mean_fitness,
+        median_fitness,
+        std_fitness,
+        parameter_norms: parameter_norms(model),
+        update_norm: norm(&update),
+        sigma,
+        seconds: a.resumed_seconds + now.duration_since(a.start).as_secs_f32(),
+        evaluations: a.evaluations,
+        evaluations_per_second: fitness.len() as f32
+            / now.duration_since(a.last).as_secs_f32().max(1e-9),
+    };
+    a.previous = current;
+    a.last = now;
+
+    for sink in a.sinks.iter() {
+        match sink {
+            Sink::Table => write_table(&b, a.rows),
+            Sink::Csv(file_name) => write_csv(&b, file_name),
+            Sink::Jsonl(file_name) => write_jsonl(&b, file_name),
+        }
+    }
+    a.rows += 1;
+
+    b
+}
+
+// same as evolution::run, with every generation going through the logger
+pub fn run<M: Model, E: Evolve<M> + ?Sized, F: Fn(&M) -> f32>(
+    evolve: &mut E,
+    generations: usize,
+    fitness: F,
+    logger: &mut Logger,
+) -> M {
+    for generation in 0..generations {
+        let candidates = evolve.ask();
+        let candidate_fitness: Vec<f32> = candidates.iter().map(&fitness).collect();
+        evolve.tell(&candidate_fitness);
+        log(
+            logger,
+            generation,
+            &evolve.model(),
+            &candidate_fitness,
+            evolve.sigma(),
+        );
+    }
+
+    evolve.model()
+}
+
+// drops logged generations from generation on, e.g. the ones after the state a run resumes from,
+// and continues the cumulative evaluations and seconds of the last generation kept
+pub fn truncate(a: &mut Logger, generation: usize) {
+    for sink in a.sinks.iter() {
+        let (file_name, header) = match sink {
+            Sink::Table => continue,
+            Sink::Csv(file_name) => (file_name, 1),
+            Sink::Jsonl(file_name) => (file_name, 0),
+        };
+        let Ok(text) = fs::read_to_string(file_name) else {
+            continue;
+        };
+
+        let kept: Vec<&str> = text
+            .lines()
+            .enumerate()
+            .filter(|(i, line)| *i < header || logged_generation(line) < generation)
+            .map(|(_, line)| line)
+            .collect();
+        if kept.len() > header {
+            let (evaluations, seconds) = logged_totals(kept[0], kept[kept.len() - 1]);
+            a.evaluations = evaluations;
+            a.resumed_seconds = seconds;
+        }
+
+        let mut kept = kept.join("\n");
+        if !kept.is_empty() {
+            kept.push('\n');
+        }
+        fs::write(file_name, kept).unwrap();
+    }
+}
+
+// evaluations and seconds of a json line, or of a csv line under its header
+fn logged_totals(header: &str, line: &str) -> (usize, f32) {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(line) {
+        return (
+            value["evaluations"].as_u64().unwrap_or(0) as usize,
+            value["seconds"].as_f64().unwrap_or(0.0) as f32,
+        );
+    }
+
+    let fields: Vec<&str> = line.split(',').collect();
+    let field = |name: &str| {
+        header
+            .split(',')
+            .position(|column| column == name)
+            .and_then(|i| fields.get(i))
+            .copied()
+            .unwrap_or("0")
+    };
+
+    (
+        field("evaluations").parse().unwrap_or(0),
+        field("seconds").parse().unwrap_or(0.0),
+    )
+}
+
+// first csv field, or the generation field of a json line
+fn logged_generation(line: &str) -> usize {
+    match serde_json::from_str::<serde_json::Value>(line) {
+        Ok(value) => value["generation"].as_u64().unwrap_or(0) as usize,
+        Err(_) => line.split(',').next().unwrap().parse().unwrap_or(0),
+    }
+}
+
+pub const COLUMNS: [&str; 10] = [
+    "generation",
+    "best_fitness",
+    "mean_fitness",
+    "median_fitness",
+    "std_fitness",
+    "update_norm",
+    "sigma",
+    "seconds",
+    "evaluations",
+    "evaluations_per_second",
+];
+
+fn write_table(a: &Metrics, rows: usize) {
+    // header again every 20 rows so it stays on screen
+    if rows.is_multiple_of(20) {
+        println!(
+            "{:>10} {:>12} {:>12} {:>12} {:>10} {:>10} {:>10} {:>9} {:>10}",
+            "generation", "best", "mean", "median", "std", "update", "sigma", "seconds", "evals/s"
+        );
+    }
+    println!(
+        "{:>10} {:>12.4} {:>12.4} {:>12.4} {:>10.4} {:>10.4} {:>10} {:>9.2} {:>10.1}",
+        a.generation,
+        a.best_fitness,
+        a.mean_fitness,
+        a.median_fitness,
+        a.std_fitness,
+        a.update_norm,
+        a.sigma.map_or("-".to_string(), |s| format!("{:.4}", s)),
+        a.seconds,
+        a.evaluations_per_second
+    );
+}
+
+fn write_csv(a: &Metrics, file_name: &str) {
+    let mut f = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_name)
+        .unwrap();
+    if f.metadata().unwrap().len() == 0 {
+        let norms: Vec<String> = a
+            .parameter_norms
+            .iter()
+            .map(|(name, _)| "norm.".to_owned() + name)
+            .collect();
+        writeln!(f, "{},{}", COLUMNS.join(","), norms.join(",")).unwrap();
+    }
+
+    let norms: Vec<String> = a
+        .parameter_norms
+        .iter()
+        .map(|(_, n)| n.to_string())
+        .collect();
+    writeln!(
+        f,
+        "{},{},{},{},{},{},{},{},{},{},{}",
+        a.generation,
+        a.best_fitness,
+        a.mean_fitness,
+        a.median_fitness,
+        a.std_fitness,
+        a.update_norm,
+        a.sigma.map_or(String::new(), |s| s.to_string()),
+        a.seconds,
+        a.evaluations,
+        a.evaluations_per_second,
+        norms.join(",")
+    )
+    .unwrap();
+}
+
+fn write_jsonl(a: &Metrics, file_name: &str) {
+    let mut f = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_name)
+        .unwrap();
+
+    let norms: serde_json::Map<String, serde_json::Value> = a
+        .parameter_norms
+        .iter()
+        .map(|(name, n)| (name.clone(), json!(n)))
+        .collect();
+    let line = json!({
+        "generation": a.generation,
+        "best_fitness": a.best_fitness,
+        "mean_fitness": a.mean_fitness,
+        "median_fitness": a.median_fitness,
+        "std_fitness": a.std_fitness,
+        "update_norm": a.update_norm,
+        "sigma": a.sigma,
+        "seconds": a.seconds,
+        "evaluations": a.evaluations,
+        "evaluations_per_second": a.evaluations_per_second,
+        "parameter_norms": norms,
+    });
+    writeln!(f, "{}", line).unwrap();
+}
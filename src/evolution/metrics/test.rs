@@ -0,0 +1,101 @@
+#[cfg(test)]
+use crate::evolution::benchmark;
+#[cfg(test)]
+use crate::evolution::metrics;
+#[cfg(test)]
+use crate::evolution::snes;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn summary_test() {
+    let (best, mean, median, std) = metrics::summary(&[3.0, 1.0, 2.0, 6.0]);
+    assert_eq!(best, 6.0);
+    assert_eq!(mean, 3.0);
+    assert_eq!(median, 2.5);
+    assert!((std - 3.5_f32.sqrt()).abs() < 1e-6);
+
+    assert_eq!(metrics::summary(&[4.0, -1.0, 2.0]).2, 2.0);
+
+    // a diverged candidate does not stop the run
+    let (best, mean, median, _) = metrics::summary(&[3.0, f32::NAN, 1.0]);
+    assert_eq!(best, 3.0);
+    assert!(mean.is_nan());
+    assert_eq!(median, 1.0);
+}
+
+#[test]
+#[should_panic]
+fn summary_panic_test() {
+    let _ = metrics::summary(&[]);
+}
+
+#[test]
+fn log_test() {
+    let a = xor::new();
+    let mut b = xor::new();
+    b.fc1.weights.value[0] = 3.0;
+    b.fc2.bias.value[1] = 4.0;
+
+    let mut logger = metrics::new(vec![], &a);
+    let m = metrics::log(&mut logger, 0, &b, &[1.0, 2.0], Some(0.5));
+    assert_eq!(m.update_norm, 5.0);
+    assert_eq!(m.evaluations, 2);
+    assert_eq!(m.sigma, Some(0.5));
+    assert_eq!(m.parameter_norms[0], ("fc1.weights".to_string(), 3.0));
+    assert_eq!(m.parameter_norms.len(), b.parameters().len());
+
+    // no change since the last generation
+    let m = metrics::log(&mut logger, 1, &b, &[1.0], None);
+    assert_eq!(m.update_norm, 0.0);
+    assert_eq!(m.evaluations, 3);
+}
+
+#[test]
+fn sinks_test() {
+    fs::create_dir_all("metrics_sinks_test").unwrap();
+    let sinks = vec![
+        metrics::Sink::Csv("metrics_sinks_test/metrics.csv".to_string()),
+        metrics::Sink::Jsonl("metrics_sinks_test/metrics.jsonl".to_string()),
+    ];
+    let model = xor::new_gaussian_noise().scalar(0.5);
+    let mut a = snes::new(&model, 0.1, 0);
+    let mut logger = metrics::new(sinks.clone(), &model);
+    metrics::run(&mut a, 4, benchmark::xor, &mut logger);
+
+    // a second logger appends without another header, after dropping generations from 2 on,
+    // and keeps counting evaluations and seconds from generation 1
+    let mut logger = metrics::new(sinks, &model);
+    metrics::truncate(&mut logger, 2);
+    let evaluations = logger.evaluations;
+    let resumed_seconds = logger.resumed_seconds;
+    let csv = fs::read_to_string("metrics_sinks_test/metrics.csv").unwrap();
+    let jsonl = fs::read_to_string("metrics_sinks_test/metrics.jsonl").unwrap();
+    fs::remove_dir_all("metrics_sinks_test").unwrap();
+
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("generation,best_fitness,mean_fitness,median_fitness,std_fitness"));
+    assert!(lines[0].ends_with("norm.fc2.weights,norm.fc2.bias"));
+    assert!(lines[2].starts_with("1,"));
+    assert_eq!(lines[1].split(',').count(), lines[0].split(',').count());
+
+    let lines: Vec<serde_json::Value> = jsonl
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["generation"], 1);
+    assert_eq!(lines[1]["evaluations"], evaluations);
+    assert_eq!(evaluations, 2 * a.population_size);
+    assert_eq!(
+        lines[1]["seconds"].as_f64().unwrap() as f32,
+        resumed_seconds
+    );
+    assert!(lines[1]["sigma"].as_f64().unwrap() > 0.0);
+    assert!(lines[1]["parameter_norms"]["fc1.weights"].as_f64().unwrap() > 0.0);
+}
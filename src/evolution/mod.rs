@@ -0,0 +1,167 @@
+pub mod benchmark;
+pub mod cem;
+pub mod differential_evolution;
+pub mod es;
+pub mod fitness_shaping;
+pub mod hall_of_fame;
+pub mod islands;
+pub mod metrics;
+pub mod nsga2;
+pub mod pepg;
+pub mod rating;
+pub mod snes;
+pub mod test;
+pub mod xnes;
+
+use crate::matrix;
+use crate::models::Model;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Ordering;
+use std::fs;
+
+// population based optimizers share an ask and tell loop
+// ask returns the candidates of this generation, tell takes their fitness in the same order
+// higher fitness is better
+pub trait Evolve<M: Model> {
+    fn ask(&mut self) -> Vec<M>;
+    fn tell(&mut self, fitness: &[f32]);
+    // current estimate of the solution
+    fn model(&self) -> M;
+    // models from another population with their fitness, used for migration between islands
+    fn inject(&mut self, migrants: &[M], fitness: &[f32]);
+    // mean step size of the search distribution, None for optimizers without one
+    fn sigma(&self) -> Option<f32>;
+    // complete state after a tell, resuming from it repeats the same generations
+    // load works in place so boxed optimizers can be restored, self gives the shapes
+    fn save(&self, dir_name: &str);
+    fn load(&mut self, dir_name: &str);
+}
+
+// fitness of every candidate is its mean score in a round robin against the others,
+// score(a, b) is the result for a in [0, 1]
+pub fn tournament<M: Model, F: Fn(&M, &M) -> f32>(candidates: &[M], score: F) -> Vec<f32> {
+    let n = candidates.len();
+    if n < 2 {
+        return vec![0.0; n];
+    }
+
+    let mut fitness = vec![0.0; n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let s = score(&candidates[i], &candidates[j]);
+            fitness[i] += s;
+            fitness[j] += 1.0 - s;
+        }
+    }
+
+    fitness.iter().map(|f| f / (n - 1) as f32).collect()
+}
+
+// same as run with the fitness of a generation coming from a tournament between its candidates
+pub fn run_tournament<M: Model, E: Evolve<M>, F: Fn(&M, &M) -> f32>(
+    evolve: &mut E,
+    generations: usize,
+    score: F,
+) -> M {
+    for _ in 0..generations {
+        let candidates = evolve.ask();
+        let candidate_fitness = tournament(&candidates, &score);
+        evolve.tell(&candidate_fitness);
+    }
+
+    evolve.model()
+}
+
+// index of the first highest fitness, NaN is worse than any number
+pub fn best_index(fitness: &[f32]) -> usize {
+    let mut best = 0;
+    for i in 1..fitness.len() {
+        if fitness_shaping::compare(fitness[i], fitness[best]) == Ordering::Greater {
+            best = i;
+        }
+    }
+
+    best
+}
+
+// migrant with the highest fitness, None when there are none
+// single model optimizers inject by moving their model or mean onto it
+pub fn best_migrant<'a, M: Model>(migrants: &'a [M], fitness: &[f32]) -> Option<&'a M> {
+    assert!(
+        migrants.len() == fitness.len(),
+        "Every migrant needs a fitness."
+    );
+    if migrants.is_empty() {
+        return None;
+    }
+
+    Some(&migrants[best_index(fitness)])
+}
+
+pub fn run<M: Model, E: Evolve<M>, F: Fn(&M) -> f32>(
+    evolve: &mut E,
+    generations: usize,
+    fitness: F,
+) -> M {
+    for _ in 0..generations {
+        let candidates = evolve.ask();
+        let candidate_fitness: Vec<f32> = candidates.iter().map(&fitness).collect();
+        evolve.tell(&candidate_fitness);
+    }
+
+    evolve.model()
+}
+
+// position in the stream as text, restoring it continues with the same numbers
+pub fn save_rng(rng: &ChaCha8Rng, file_name: &str) {
+    let seed: String = rng
+        .get_seed()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    fs::write(
+        file_name,
+        format!("{}\n{}\n{}\n", seed, rng.get_stream(), rng.get_word_pos()),
+    )
+    .unwrap();
+}
+
+pub fn load_rng(file_name: &str) -> ChaCha8Rng {
+    let text = fs::read_to_string(file_name).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines.len() == 3, "Rng state is incorrect.");
+
+    let mut seed = [0; 32];
+    for (i, byte) in seed.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&lines[0][2 * i..2 * i + 2], 16).unwrap();
+    }
+    let mut rng = ChaCha8Rng::from_seed(seed);
+    rng.set_stream(lines[1].parse().unwrap());
+    rng.set_word_pos(lines[2].parse().unwrap());
+
+    rng
+}
+
+// flat parameter vectors in the binary format of matrix::save
+pub fn save_values(values: &[f32], file_name: &str) {
+    // matrices cannot be empty, e.g. the fitness of a population not yet evaluated
+    if values.is_empty() {
+        fs::write(file_name, []).unwrap();
+        return;
+    }
+
+    let mut a = matrix::new(1, values.len());
+    a.value = values.to_vec();
+    matrix::save(&a, file_name);
+}
+
+// length comes from the file, so it also restores vectors that were empty
+pub fn load_values(file_name: &str) -> Vec<f32> {
+    let n = fs::metadata(file_name).unwrap().len() as usize / 4;
+    if n == 0 {
+        return vec![];
+    }
+
+    matrix::load(&matrix::new(1, n), file_name).value
+}
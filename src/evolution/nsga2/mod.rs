@@ -0,0 +1,257 @@
+pub mod test;
+
+use crate::evolution::fitness_shaping;
+use crate::models;
+use crate::models::Model;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Ordering;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+
+// multi-objective evolution (Deb et al. 2002), every objective is maximized
+// parents and offspring are sorted into non-dominated fronts and the best fronts survive,
+// ties in the last front that fits are broken by crowding distance
+#[derive(Clone)]
+pub struct Nsga2<M: Model> {
+    pub population: Vec<M>,
+    // empty until the first population has been evaluated
+    pub objectives: Vec<Vec<f32>>,
+    pub rank: Vec<usize>,
+    pub crowding: Vec<f32>,
+    pub sigma: f32,
+    // probability of uniform crossover between the two parents of a child
+    pub crossover_rate: f32,
+    pub rng: ChaCha8Rng,
+    pub offspring: Vec<M>,
+}
+
+// population is model + sigma * noise, the first ask returns it for evaluation
+pub fn new<M: Model>(model: &M, population_size: usize, sigma: f32, seed: u64) -> Nsga2<M> {
+    assert!(population_size >= 2, "Population needs at least 2 members.");
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut population: Vec<M> = Vec::with_capacity(population_size);
+    for _ in 0..population_size {
+        let noise = models::gaussian_noise(model, &mut rng);
+        population.push(model.add(&noise.scalar(sigma)));
+    }
+
+    Nsga2 {
+        population,
+        objectives: vec![],
+        rank: vec![],
+        crowding: vec![],
+        sigma,
+        crossover_rate: 0.5,
+        rng,
+        offspring: vec![],
+    }
+}
+
+// a is at least as good in every objective and better in one, NaN is worse than any number
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    assert!(a.len() == b.len(), "Number of objectives must match.");
+
+    let order: Vec<Ordering> = a
+        .iter()
+        .zip(b.iter())
+        .map(|(x, y)| fitness_shaping::compare(*x, *y))
+        .collect();
+    !order.contains(&Ordering::Less) && order.contains(&Ordering::Greater)
+}
+
+// fronts of indices, the first front is not dominated by anything
+pub fn non_dominated_sort(objectives: &[Vec<f32>]) -> Vec<Vec<usize>> {
+    let n = objectives.len();
+    let mut dominated_by_count = vec![0; n];
+    let mut dominates_list: Vec<Vec<usize>> = vec![vec![]; n];
+    for i in 0..n {
+        for j in 0..n {
+            if dominates(&objectives[i], &objectives[j]) {
+                dominates_list[i].push(j);
+            } else if dominates(&objectives[j], &objectives[i]) {
+                dominated_by_count[i] += 1;
+            }
+        }
+    }
+
+    let mut fronts: Vec<Vec<usize>> = vec![];
+    let mut front: Vec<usize> = (0..n).filter(|i| dominated_by_count[*i] == 0).collect();
+    while !front.is_empty() {
+        let mut next: Vec<usize> = vec![];
+        for i in front.iter() {
+            for j in dominates_list[*i].iter() {
+                dominated_by_count[*j] -= 1;
+                if dominated_by_count[*j] == 0 {
+                    next.push(*j);
+                }
+            }
+        }
+        fronts.push(front);
+        front = next;
+    }
+
+    fronts
+}
+
+// per member of the front, the normalized size of the box spanned by its neighbours
+// boundary members are infinite so they are always kept
+pub fn crowding_distance(objectives: &[Vec<f32>], front: &[usize]) -> Vec<f32> {
+    let mut distance = vec![0.0; front.len()];
+    if front.is_empty() {
+        return distance;
+    }
+
+    // values of one objective across the front
+    let columns: Vec<Vec<f32>> = (0..objectives[front[0]].len())
+        .map(|objective| front.iter().map(|i| objectives[*i][objective]).collect())
+        .collect();
+    for values in columns.iter() {
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|a, b| fitness_shaping::compare(values[*a], values[*b]));
+
+        let low = values[order[0]];
+        let high = values[order[order.len() - 1]];
+        distance[order[0]] = f32::INFINITY;
+        distance[order[order.len() - 1]] = f32::INFINITY;
+        if high == low {
+            continue;
+        }
+
+        for k in 1..order.len().saturating_sub(1) {
+            distance[order[k]] += (values[order[k + 1]] - values[order[k - 1]]) / (high - low);
+        }
+    }
+
+    distance
+}
+
+// lower rank wins, then larger crowding distance
+fn tournament<M: Model>(a: &mut Nsga2<M>) -> usize {
+    let i = a.rng.gen_range(0..a.population.len());
+    let j = a.rng.gen_range(0..a.population.len());
+    if a.rank[i] < a.rank[j] || (a.rank[i] == a.rank[j] && a.crowding[i] > a.crowding[j]) {
+        i
+    } else {
+        j
+    }
+}
+
+pub fn ask<M: Model>(a: &mut Nsga2<M>) -> Vec<M> {
+    if a.objectives.is_empty() {
+        return a.population.clone();
+    }
+
+    a.offspring.clear();
+    for _ in 0..a.population.len() {
+        let first = tournament(a);
+        let second = tournament(a);
+
+        let mut values = models::flatten(&a.population[first]);
+        if a.rng.gen::<f32>() < a.crossover_rate {
+            let other = models::flatten(&a.population[second]);
+            for i in 0..values.len() {
+                if a.rng.gen::<bool>() {
+                    values[i] = other[i];
+                }
+            }
+        }
+
+        let child = models::unflatten(&a.population[first], &values);
+        let noise = models::gaussian_noise(&child, &mut a.rng);
+        a.offspring.push(child.add(&noise.scalar(a.sigma)));
+    }
+
+    a.offspring.clone()
+}
+
+pub fn tell<M: Model>(a: &mut Nsga2<M>, objectives: &[Vec<f32>]) {
+    let population_size = a.population.len();
+    let (candidates, candidate_objectives) = if a.objectives.is_empty() {
+        assert!(
+            objectives.len() == population_size,
+            "Every candidate needs objectives."
+        );
+        (a.population.clone(), objectives.to_vec())
+    } else {
+        assert!(
+            objectives.len() == a.offspring.len(),
+            "Every candidate needs objectives."
+        );
+        let mut candidates = a.population.clone();
+        candidates.extend(a.offspring.iter().cloned());
+        let mut candidate_objectives = a.objectives.clone();
+        candidate_objectives.extend(objectives.iter().cloned());
+        (candidates, candidate_objectives)
+    };
+
+    a.population.clear();
+    a.objectives.clear();
+    a.rank.clear();
+    a.crowding.clear();
+    for (rank, front) in non_dominated_sort(&candidate_objectives).iter().enumerate() {
+        if a.population.len() == population_size {
+            break;
+        }
+
+        let distance = crowding_distance(&candidate_objectives, front);
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|x, y| fitness_shaping::compare(distance[*y], distance[*x]));
+        order.truncate(population_size - a.population.len());
+        for k in order {
+            a.population.push(candidates[front[k]].clone());
+            a.objectives.push(candidate_objectives[front[k]].clone());
+            a.rank.push(rank);
+            a.crowding.push(distance[k]);
+        }
+    }
+}
+
+pub fn run<M: Model, F: Fn(&M) -> Vec<f32>>(
+    a: &mut Nsga2<M>,
+    generations: usize,
+    fitness: F,
+) -> Vec<M> {
+    for _ in 0..generations {
+        let candidates = ask(a);
+        let objectives: Vec<Vec<f32>> = candidates.iter().map(&fitness).collect();
+        tell(a, &objectives);
+    }
+
+    pareto_front(a).0
+}
+
+// members of the first front with their objectives
+pub fn pareto_front<M: Model>(a: &Nsga2<M>) -> (Vec<M>, Vec<Vec<f32>>) {
+    let mut models: Vec<M> = vec![];
+    let mut objectives: Vec<Vec<f32>> = vec![];
+    for i in 0..a.population.len() {
+        if a.rank[i] == 0 {
+            models.push(a.population[i].clone());
+            objectives.push(a.objectives[i].clone());
+        }
+    }
+
+    (models, objectives)
+}
+
+// every member of the pareto front is saved to dir_name/<index>,
+// front.csv lists the objectives of each index
+pub fn save_pareto_front<M: Model>(a: &Nsga2<M>, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    let (models, objectives) = pareto_front(a);
+
+    let mut f = File::create(dir_name.to_owned() + "/front.csv").unwrap();
+    let header: Vec<String> = (0..objectives.first().map_or(0, |o| o.len()))
+        .map(|i| "objective".to_owned() + &i.to_string())
+        .collect();
+    writeln!(f, "index,{}", header.join(",")).unwrap();
+    for (i, model) in models.iter().enumerate() {
+        model.save((dir_name.to_owned() + "/" + &i.to_string()).as_str());
+        let values: Vec<String> = objectives[i].iter().map(|v| v.to_string()).collect();
+        writeln!(f, "{},{}", i, values.join(",")).unwrap();
+    }
+}
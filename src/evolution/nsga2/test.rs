@@ -0,0 +1,159 @@
+#[cfg(test)]
+use crate::evolution::nsga2;
+#[cfg(test)]
+use crate::models;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use std::fs;
+
+#[cfg(test)]
+fn distance(a: &xor::XorModel, b: &xor::XorModel) -> f32 {
+    let a = models::flatten(a);
+    let b = models::flatten(b);
+
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+#[test]
+fn dominates_test() {
+    assert!(nsga2::dominates(&[2.0, 1.0], &[1.0, 1.0]));
+    assert!(!nsga2::dominates(&[1.0, 1.0], &[1.0, 1.0]));
+    assert!(!nsga2::dominates(&[2.0, 0.0], &[1.0, 1.0]));
+    // a NaN objective is dominated by any number
+    assert!(nsga2::dominates(&[1.0, 0.0], &[1.0, f32::NAN]));
+    assert!(!nsga2::dominates(&[f32::NAN, 5.0], &[0.0, 0.0]));
+}
+
+#[test]
+#[should_panic]
+fn dominates_panic_test() {
+    let _ = nsga2::dominates(&[1.0], &[1.0, 2.0]);
+}
+
+#[test]
+fn non_dominated_sort_test() {
+    let objectives = vec![
+        vec![1.0, 1.0],
+        vec![3.0, 1.0],
+        vec![1.0, 3.0],
+        vec![2.0, 2.0],
+        vec![0.0, 0.0],
+    ];
+
+    let fronts = nsga2::non_dominated_sort(&objectives);
+    assert_eq!(fronts, [vec![1, 2, 3], vec![0], vec![4]]);
+}
+
+#[test]
+fn crowding_distance_test() {
+    let objectives = vec![
+        vec![0.0, 4.0],
+        vec![1.0, 3.0],
+        vec![3.0, 1.0],
+        vec![4.0, 0.0],
+    ];
+
+    let distance = nsga2::crowding_distance(&objectives, &[0, 1, 2, 3]);
+    assert_eq!(distance[0], f32::INFINITY);
+    assert_eq!(distance[3], f32::INFINITY);
+    // (3 - 0) / 4 for both objectives
+    assert_eq!(distance[1], 1.5);
+    assert_eq!(distance[2], 1.5);
+
+    // NaN objectives sort as the lowest value instead of panicking
+    let objectives = vec![vec![f32::NAN, 1.0], vec![1.0, 0.0], vec![2.0, 2.0]];
+    let distance = nsga2::crowding_distance(&objectives, &[0, 1, 2]);
+    assert_eq!(distance[0], f32::INFINITY);
+    assert_eq!(distance[2], f32::INFINITY);
+}
+
+#[test]
+fn tell_nan_test() {
+    let mut a = nsga2::new(&xor::new(), 4, 0.1, 0);
+
+    nsga2::ask(&mut a);
+    nsga2::tell(
+        &mut a,
+        &[
+            vec![f32::NAN, 0.0],
+            vec![1.0, 0.0],
+            vec![0.0, f32::NAN],
+            vec![0.5, 0.5],
+        ],
+    );
+    assert_eq!(a.population.len(), 4);
+
+    // candidates with NaN are dominated, so they are not on the saved pareto front
+    let (front, objectives) = nsga2::pareto_front(&a);
+    assert_eq!(front.len(), 2);
+    assert!(objectives.iter().flatten().all(|v| !v.is_nan()));
+    assert_eq!(nsga2::non_dominated_sort(&a.objectives)[0].len(), 2);
+}
+
+#[test]
+fn tell_test() {
+    let mut a = nsga2::new(&xor::new(), 4, 0.1, 0);
+
+    let first = nsga2::ask(&mut a);
+    nsga2::tell(
+        &mut a,
+        &[
+            vec![0.0, 0.0],
+            vec![1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![0.5, 0.5],
+        ],
+    );
+    assert_eq!(a.rank, [0, 0, 0, 1]);
+    assert_eq!(distance(&a.population[3], &first[0]), 0.0);
+
+    // offspring dominating everyone push the worst parents out
+    let offspring = nsga2::ask(&mut a);
+    assert_eq!(offspring.len(), 4);
+    nsga2::tell(
+        &mut a,
+        &[
+            vec![2.0, 2.0],
+            vec![-1.0, -1.0],
+            vec![-1.0, -1.0],
+            vec![-1.0, -1.0],
+        ],
+    );
+    assert_eq!(a.objectives[0], [2.0, 2.0]);
+    assert_eq!(distance(&a.population[0], &offspring[0]), 0.0);
+    assert_eq!(a.population.len(), 4);
+    assert!(a.objectives.iter().all(|o| o[0] >= 0.0));
+
+    let (front, objectives) = nsga2::pareto_front(&a);
+    assert_eq!(front.len(), 1);
+    assert_eq!(objectives[0], [2.0, 2.0]);
+}
+
+#[test]
+fn run_save_test() {
+    // two targets pull in different directions, the front spans the space between them
+    let first = xor::new_gaussian_noise();
+    let second = xor::new_gaussian_noise();
+    let mut a = nsga2::new(&xor::new(), 20, 0.2, 1);
+
+    let front = nsga2::run(&mut a, 100, |m| {
+        vec![-distance(m, &first), -distance(m, &second)]
+    });
+    assert!(front.len() > 1);
+    let between = distance(&first, &second);
+    for model in front.iter() {
+        assert!(distance(model, &first) + distance(model, &second) < between * 1.5);
+    }
+
+    nsga2::save_pareto_front(&a, "nsga2_save_test");
+    let csv = fs::read_to_string("nsga2_save_test/front.csv").unwrap();
+    let loaded = front[0].load("nsga2_save_test/0");
+    fs::remove_dir_all("nsga2_save_test").unwrap();
+
+    assert_eq!(csv.lines().count(), front.len() + 1);
+    assert!(csv.starts_with("index,objective0,objective1\n"));
+    assert_eq!(distance(&loaded, &front[0]), 0.0);
+}
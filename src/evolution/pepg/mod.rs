@@ -0,0 +1,151 @@
+pub mod test;
+
+use crate::evolution;
+use crate::evolution::fitness_shaping;
+use crate::evolution::Evolve;
+use crate::models;
+use crate::models::Model;
+use crate::optim::Optimize;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rand_distr::StandardNormal;
+use std::fs;
+
+// parameter-exploring policy gradients with symmetric sampling (Sehnke et al. 2010)
+// candidates come in pairs model + e and model - e, the difference of a pair moves the
+// model and the mean of a pair against the baseline moves the per parameter sigma
+#[derive(Clone)]
+pub struct Pepg<M: Model, O: Optimize<M>> {
+    pub model: M,
+    pub optimizer: O,
+    pub sigma: Vec<f32>,
+    pub sigma_learning_rate: f32,
+    pub population_size: usize,
+    pub fitness_shaping: fitness_shaping::FitnessShaping,
+    pub rng: ChaCha8Rng,
+    // one perturbation per pair, already scaled by sigma
+    pub noise: Vec<Vec<f32>>,
+}
+
+pub fn new<M: Model, O: Optimize<M>>(
+    model: &M,
+    optimizer: O,
+    sigma: f32,
+    sigma_learning_rate: f32,
+    population_size: usize,
+    fitness_shaping: fitness_shaping::FitnessShaping,
+    seed: u64,
+) -> Pepg<M, O> {
+    assert!(
+        population_size != 0 && population_size.is_multiple_of(2),
+        "Symmetric sampling needs an even population size."
+    );
+
+    Pepg {
+        model: model.clone(),
+        optimizer,
+        sigma: vec![sigma; models::num_parameters(model)],
+        sigma_learning_rate,
+        population_size,
+        fitness_shaping,
+        rng: ChaCha8Rng::seed_from_u64(seed),
+        noise: vec![],
+    }
+}
+
+// per parameter sigma in the shape of the model, parameters() names the layers
+pub fn step_sizes<M: Model, O: Optimize<M>>(a: &Pepg<M, O>) -> M {
+    models::unflatten(&a.model, &a.sigma)
+}
+
+impl<M: Model, O: Optimize<M>> Evolve<M> for Pepg<M, O> {
+    fn ask(&mut self) -> Vec<M> {
+        self.noise.clear();
+        for _ in 0..self.population_size / 2 {
+            let e: Vec<f32> = self
+                .sigma
+                .iter()
+                .map(|sigma| {
+                    let s: f32 = self.rng.sample(StandardNormal);
+                    sigma * s
+                })
+                .collect();
+            self.noise.push(e);
+        }
+
+        let mean = models::flatten(&self.model);
+        let mut candidates: Vec<M> = Vec::with_capacity(self.population_size);
+        for e in self.noise.iter() {
+            let positive: Vec<f32> = mean.iter().zip(e.iter()).map(|(m, e)| m + e).collect();
+            let negative: Vec<f32> = mean.iter().zip(e.iter()).map(|(m, e)| m - e).collect();
+            candidates.push(models::unflatten(&self.model, &positive));
+            candidates.push(models::unflatten(&self.model, &negative));
+        }
+
+        candidates
+    }
+
+    fn tell(&mut self, fitness: &[f32]) {
+        assert!(
+            fitness.len() == 2 * self.noise.len(),
+            "Every candidate needs a fitness."
+        );
+
+        let weights = fitness_shaping::shape(self.fitness_shaping, fitness);
+        let baseline: f32 = weights.iter().sum::<f32>() / weights.len() as f32;
+        let num_pairs = self.noise.len() as f32;
+
+        let mut mean_gradient = vec![0.0; self.sigma.len()];
+        let mut sigma_gradient = vec![0.0; self.sigma.len()];
+        for (k, e) in self.noise.iter().enumerate() {
+            let r_t = (weights[2 * k] - weights[2 * k + 1]) / 2.0;
+            let r_s = (weights[2 * k] + weights[2 * k + 1]) / 2.0 - baseline;
+            for i in 0..e.len() {
+                mean_gradient[i] += r_t * e[i] / num_pairs;
+                sigma_gradient[i] +=
+                    r_s * (e[i] * e[i] - self.sigma[i] * self.sigma[i]) / self.sigma[i] / num_pairs;
+            }
+        }
+
+        // limit the change so one generation cannot collapse sigma
+        for (sigma, g) in self.sigma.iter_mut().zip(sigma_gradient.iter()) {
+            *sigma = (*sigma + self.sigma_learning_rate * g).clamp(*sigma * 0.8, *sigma * 1.2);
+        }
+
+        let g = models::unflatten(&self.model, &mean_gradient);
+        self.model = self.optimizer.step(&self.model, &g);
+    }
+
+    fn model(&self) -> M {
+        self.model.clone()
+    }
+
+    fn inject(&mut self, migrants: &[M], fitness: &[f32]) {
+        if let Some(best) = evolution::best_migrant(migrants, fitness) {
+            self.model = best.clone();
+        }
+    }
+
+    fn sigma(&self) -> Option<f32> {
+        Some(self.sigma.iter().sum::<f32>() / self.sigma.len() as f32)
+    }
+
+    fn save(&self, dir_name: &str) {
+        fs::create_dir_all(dir_name).unwrap();
+        self.model.save((dir_name.to_owned() + "/model").as_str());
+        self.optimizer
+            .save((dir_name.to_owned() + "/optimizer").as_str());
+        evolution::save_values(&self.sigma, (dir_name.to_owned() + "/sigma.bin").as_str());
+        evolution::save_rng(&self.rng, (dir_name.to_owned() + "/rng.txt").as_str());
+    }
+
+    fn load(&mut self, dir_name: &str) {
+        self.model = self.model.load((dir_name.to_owned() + "/model").as_str());
+        self.optimizer = self
+            .optimizer
+            .load((dir_name.to_owned() + "/optimizer").as_str());
+        self.sigma = evolution::load_values((dir_name.to_owned() + "/sigma.bin").as_str());
+        self.rng = evolution::load_rng((dir_name.to_owned() + "/rng.txt").as_str());
+    }
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+use crate::evolution;
+#[cfg(test)]
+use crate::evolution::fitness_shaping::FitnessShaping;
+#[cfg(test)]
+use crate::evolution::pepg;
+#[cfg(test)]
+use crate::evolution::Evolve;
+#[cfg(test)]
+use crate::models;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use crate::optim::adam;
+#[cfg(test)]
+use crate::optim::momentum;
+
+#[cfg(test)]
+fn distance(a: &xor::XorModel, b: &xor::XorModel) -> f32 {
+    let a = models::flatten(a);
+    let b = models::flatten(b);
+
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+#[test]
+#[should_panic]
+fn new_odd_panic_test() {
+    let model = xor::new();
+    let _ = pepg::new(
+        &model,
+        momentum::new(&model, 0.1, 0.0),
+        0.1,
+        0.1,
+        5,
+        FitnessShaping::Raw,
+        0,
+    );
+}
+
+#[test]
+fn ask_symmetric_test() {
+    let model = xor::new_gaussian_noise();
+    let mut a = pepg::new(
+        &model,
+        momentum::new(&model, 0.1, 0.0),
+        0.1,
+        0.1,
+        6,
+        FitnessShaping::CenteredRanks,
+        0,
+    );
+
+    let candidates = a.ask();
+    assert_eq!(candidates.len(), 6);
+    assert_eq!(a.noise.len(), 3);
+    for pair in candidates.chunks(2) {
+        let middle = pair[0].add(&pair[1]).scalar(0.5);
+        assert!(distance(&middle, &model) < 1e-8);
+        assert!(distance(&pair[0], &model) > 0.0);
+    }
+}
+
+#[test]
+fn tell_sigma_test() {
+    let model = xor::new();
+    let mut a = pepg::new(
+        &model,
+        momentum::new(&model, 0.0, 0.0),
+        1.0,
+        0.1,
+        4,
+        FitnessShaping::Raw,
+        0,
+    );
+
+    // fitness rewards large perturbations so sigma grows where e^2 > sigma^2
+    a.ask();
+    let fitness: Vec<f32> = a
+        .noise
+        .iter()
+        .flat_map(|e| {
+            let size: f32 = e.iter().map(|v| v * v).sum();
+            [size, size]
+        })
+        .collect();
+    let noise = a.noise.clone();
+    a.tell(&fitness);
+
+    let larger = if fitness[0] > fitness[2] { 0 } else { 1 };
+    for (i, sigma) in a.sigma.iter().enumerate() {
+        if noise[larger][i].abs() > 1.0 && noise[1 - larger][i].abs() < 1.0 {
+            assert!(*sigma > 1.0);
+        }
+        assert!((0.8..=1.2).contains(sigma));
+    }
+
+    let step_sizes = pepg::step_sizes(&a);
+    assert_eq!(step_sizes.parameters()[3].0, "fc2.bias");
+}
+
+#[test]
+#[should_panic]
+fn tell_length_panic_test() {
+    let model = xor::new();
+    let mut a = pepg::new(
+        &model,
+        momentum::new(&model, 0.1, 0.0),
+        0.1,
+        0.1,
+        4,
+        FitnessShaping::Raw,
+        0,
+    );
+
+    a.ask();
+    a.tell(&[1.0, 2.0]);
+}
+
+#[test]
+fn run_test() {
+    let target = xor::new_gaussian_noise();
+    let start = xor::new();
+    let mut a = pepg::new(
+        &start,
+        adam::new(&start, 0.05),
+        0.5,
+        0.05,
+        20,
+        FitnessShaping::CenteredRanks,
+        1,
+    );
+
+    let model = evolution::run(&mut a, 300, |m| -distance(m, &target));
+    assert!(distance(&model, &target) < distance(&start, &target) * 0.05);
+}
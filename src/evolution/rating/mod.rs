@@ -0,0 +1,382 @@
+pub mod test;
+
+use crate::evolution::fitness_shaping;
+use crate::matrix;
+use crate::models::Model;
+use std::f32::consts::PI;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+
+// glicko-2 constants, ratings are shown on the elo scale
+pub const GLICKO_SCALE: f32 = 173.7178;
+pub const INITIAL_RATING: f32 = 1500.0;
+pub const INITIAL_DEVIATION: f32 = 350.0;
+pub const INITIAL_VOLATILITY: f32 = 0.06;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Glicko {
+    pub rating: f32,
+    pub deviation: f32,
+    pub volatility: f32,
+}
+
+pub fn new_glicko() -> Glicko {
+    Glicko {
+        rating: INITIAL_RATING,
+        deviation: INITIAL_DEVIATION,
+        volatility: INITIAL_VOLATILITY,
+    }
+}
+
+// expected score of a rated a against a rated b
+pub fn elo_expected(a: f32, b: f32) -> f32 {
+    1.0 / (1.0 + 10.0_f32.powf((b - a) / 400.0))
+}
+
+// new ratings after a scored s against b, s in [0, 1]
+pub fn elo_update(a: f32, b: f32, s: f32, k: f32) -> (f32, f32) {
+    let expected = elo_expected(a, b);
+
+    (a + k * (s - expected), b - k * (s - expected))
+}
+
+fn g(phi: f32) -> f32 {
+    1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt()
+}
+
+// rating of a after one rating period of games, results are (opponent, score) pairs
+// follows glickman's description of glicko-2, tau limits how fast the volatility changes
+pub fn glicko_update(a: &Glicko, results: &[(Glicko, f32)], tau: f32) -> Glicko {
+    let mu = (a.rating - INITIAL_RATING) / GLICKO_SCALE;
+    let phi = a.deviation / GLICKO_SCALE;
+
+    // players that did not play only become less certain
+    if results.is_empty() {
+        return Glicko {
+            rating: a.rating,
+            deviation: (phi * phi + a.volatility * a.volatility).sqrt() * GLICKO_SCALE,
+            volatility: a.volatility,
+        };
+    }
+
+    let mut v_inverse: f32 = 0.0;
+    let mut improvement: f32 = 0.0;
+    for (opponent, s) in results {
+        let mu_j = (opponent.rating - INITIAL_RATING) / GLICKO_SCALE;
+        let g_j = g(opponent.deviation / GLICKO_SCALE);
+        let expected = 1.0 / (1.0 + (-g_j * (mu - mu_j)).exp());
+        v_inverse += g_j * g_j * expected * (1.0 - expected);
+        improvement += g_j * (s - expected);
+    }
+    let v = 1.0 / v_inverse;
+    let delta = v * improvement;
+
+    // new volatility from the root of f with the illinois method
+    let alpha = (a.volatility * a.volatility).ln();
+    let f = |x: f32| {
+        let e = x.exp();
+        e * (delta * delta - phi * phi - v - e) / (2.0 * (phi * phi + v + e).powi(2))
+            - (x - alpha) / (tau * tau)
+    };
+    let mut lower = alpha;
+    let mut upper = if delta * delta > phi * phi + v {
+        (delta * delta - phi * phi - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(alpha - k * tau) < 0.0 {
+            k += 1.0;
+        }
+        alpha - k * tau
+    };
+    let mut f_lower = f(lower);
+    let mut f_upper = f(upper);
+    // f32 cannot always reach the tolerance, so the iterations are capped
+    for _ in 0..100 {
+        if (upper - lower).abs() <= 1e-6 {
+            break;
+        }
+        let c = lower + (lower - upper) * f_lower / (f_upper - f_lower);
+        let f_c = f(c);
+        if f_c * f_upper <= 0.0 {
+            lower = upper;
+            f_lower = f_upper;
+        } else {
+            f_lower /= 2.0;
+        }
+        upper = c;
+        f_upper = f_c;
+    }
+    let volatility = (lower / 2.0).exp();
+
+    let phi_star = (phi * phi + volatility * volatility).sqrt();
+    let phi_new = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+    let mu_new = mu + phi_new * phi_new * improvement;
+
+    Glicko {
+        rating: mu_new * GLICKO_SCALE + INITIAL_RATING,
+        deviation: phi_new * GLICKO_SCALE,
+        volatility,
+    }
+}
+
+// ratings and results of a set of players, filled in round by round
+pub struct Tournament {
+    pub names: Vec<String>,
+    // 1 per win, 0.5 per draw and 1 per bye
+    pub points: Vec<f32>,
+    pub elo: Vec<f32>,
+    pub glicko: Vec<Glicko>,
+    // sum of the scores of row against column, and the number of games between them
+    pub results: matrix::Matrix,
+    pub games: matrix::Matrix,
+    pub byes: Vec<usize>,
+    pub rounds: usize,
+    pub k: f32,
+    pub tau: f32,
+}
+
+pub fn new(names: &[String], k: f32, tau: f32) -> Tournament {
+    let n = names.len();
+    assert!(n >= 2, "A tournament needs at least 2 players.");
+
+    Tournament {
+        names: names.to_vec(),
+        points: vec![0.0; n],
+        elo: vec![INITIAL_RATING; n],
+        glicko: vec![new_glicko(); n],
+        results: matrix::new(n, n),
+        games: matrix::new(n, n),
+        byes: vec![0; n],
+        rounds: 0,
+        k,
+        tau,
+    }
+}
+
+// every subdirectory of dir_name is a saved model, loaded in name order
+// template gives the architecture, see Model::load
+pub fn load_models<M: Model>(template: &M, dir_name: &str) -> (Vec<String>, Vec<M>) {
+    let mut names: Vec<String> = fs::read_dir(dir_name)
+        .unwrap()
+        .map(|entry| entry.unwrap())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+
+    let models = names
+        .iter()
+        .map(|name| template.load((dir_name.to_owned() + "/" + name).as_str()))
+        .collect();
+
+    (names, models)
+}
+
+// circle method, every player meets every other player once over n - 1 rounds (n rounds if n is odd)
+pub fn round_robin_schedule(n: usize) -> Vec<Vec<(usize, usize)>> {
+    // an odd number of players gets a dummy, whoever meets it sits out the round
+    let size = n + n % 2;
+    let mut order: Vec<usize> = (0..size).collect();
+    let mut rounds = Vec::with_capacity(size - 1);
+    for _ in 0..size - 1 {
+        let mut pairs = Vec::with_capacity(size / 2);
+        for i in 0..size / 2 {
+            let (a, b) = (order[i], order[size - 1 - i]);
+            if a < n && b < n {
+                pairs.push((a, b));
+            }
+        }
+        rounds.push(pairs);
+        order[1..].rotate_right(1);
+    }
+
+    rounds
+}
+
+// pairs players with equal points where possible, avoiding rematches
+// with an odd number of players the lowest ranked player without a bye sits out
+pub fn swiss_pairing(a: &Tournament) -> (Vec<(usize, usize)>, Option<usize>) {
+    let n = a.names.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|i, j| {
+        fitness_shaping::compare(a.points[*j], a.points[*i])
+            .then(fitness_shaping::compare(a.elo[*j], a.elo[*i]))
+            .then(i.cmp(j))
+    });
+
+    let mut bye = None;
+    if !n.is_multiple_of(2) {
+        let fewest = *a.byes.iter().min().unwrap();
+        let index = order.iter().rposition(|i| a.byes[*i] == fewest).unwrap();
+        bye = Some(order.remove(index));
+    }
+
+    let mut pairs = Vec::with_capacity(n / 2);
+    while !order.is_empty() {
+        let first = order.remove(0);
+        // a rematch only when every remaining player was already met
+        let opponent = order
+            .iter()
+            .position(|j| a.games.value[*j * n + first] == 0.0)
+            .unwrap_or(0);
+        pairs.push((first, order.remove(opponent)));
+    }
+
+    (pairs, bye)
+}
+
+// plays every pair once, score(a, b) is the result for a in [0, 1]
+// elo is updated after every game and glicko-2 once per round
+pub fn play_round<M: Model, F: Fn(&M, &M) -> f32>(
+    a: &mut Tournament,
+    models: &[M],
+    pairs: &[(usize, usize)],
+    bye: Option<usize>,
+    score: F,
+) {
+    let n = a.names.len();
+    assert!(models.len() == n, "Every player needs a model.");
+
+    let mut round_results: Vec<Vec<(Glicko, f32)>> = vec![vec![]; n];
+    for (i, j) in pairs {
+        let s = score(&models[*i], &models[*j]);
+        assert!((0.0..=1.0).contains(&s), "Score must be in [0, 1].");
+
+        (a.elo[*i], a.elo[*j]) = elo_update(a.elo[*i], a.elo[*j], s, a.k);
+        round_results[*i].push((a.glicko[*j], s));
+        round_results[*j].push((a.glicko[*i], 1.0 - s));
+        a.points[*i] += s;
+        a.points[*j] += 1.0 - s;
+        // matrices are column major
+        a.results.value[*j * n + *i] += s;
+        a.results.value[*i * n + *j] += 1.0 - s;
+        a.games.value[*j * n + *i] += 1.0;
+        a.games.value[*i * n + *j] += 1.0;
+    }
+    if let Some(i) = bye {
+        a.points[i] += 1.0;
+        a.byes[i] += 1;
+    }
+
+    a.glicko = a
+        .glicko
+        .iter()
+        .zip(round_results.iter())
+        .map(|(rating, results)| glicko_update(rating, results, a.tau))
+        .collect();
+    a.rounds += 1;
+}
+
+pub fn round_robin<M: Model, F: Fn(&M, &M) -> f32>(
+    models: &[M],
+    names: &[String],
+    score: F,
+) -> Tournament {
+    let mut a = new(names, 32.0, 0.5);
+    for pairs in round_robin_schedule(models.len()) {
+        play_round(&mut a, models, &pairs, None, &score);
+    }
+
+    a
+}
+
+// rounds of swiss pairings, far fewer games than a round robin for many players
+pub fn swiss<M: Model, F: Fn(&M, &M) -> f32>(
+    models: &[M],
+    names: &[String],
+    rounds: usize,
+    score: F,
+) -> Tournament {
+    let mut a = new(names, 32.0, 0.5);
+    for _ in 0..rounds {
+        let (pairs, bye) = swiss_pairing(&a);
+        play_round(&mut a, models, &pairs, bye, &score);
+    }
+
+    a
+}
+
+// mean score of row against column, NaN where the pair never played
+pub fn result_matrix(a: &Tournament) -> matrix::Matrix {
+    let mut b = a.results.clone();
+    for (value, games) in b.value.iter_mut().zip(a.games.value.iter()) {
+        *value = if *games == 0.0 {
+            f32::NAN
+        } else {
+            *value / games
+        };
+    }
+
+    b
+}
+
+// player indices from the highest glicko-2 rating down, a NaN rating comes last
+pub fn standings(a: &Tournament) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..a.names.len()).collect();
+    order.sort_by(|i, j| {
+        fitness_shaping::compare(a.glicko[*j].rating, a.glicko[*i].rating).then(i.cmp(j))
+    });
+
+    order
+}
+
+pub fn print(a: &Tournament) {
+    println!(
+        "{:<24} {:>8} {:>8} {:>10} {:>10} {:>10}",
+        "player", "points", "elo", "glicko", "deviation", "volatility"
+    );
+    for i in standings(a) {
+        println!(
+            "{:<24} {:>8.1} {:>8.1} {:>10.1} {:>10.1} {:>10.4}",
+            a.names[i],
+            a.points[i],
+            a.elo[i],
+            a.glicko[i].rating,
+            a.glicko[i].deviation,
+            a.glicko[i].volatility
+        );
+    }
+}
+
+// ratings.csv with one row per player in standings order and results.csv with the
+// mean score of every row player against every column player, empty where they never played
+pub fn save_report(a: &Tournament, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    let n = a.names.len();
+
+    let mut f = File::create(dir_name.to_owned() + "/ratings.csv").unwrap();
+    writeln!(f, "name,points,games,elo,glicko,deviation,volatility").unwrap();
+    for i in standings(a) {
+        let games: f32 = a.games.value[i * n..(i + 1) * n].iter().sum();
+        writeln!(
+            f,
+            "{},{},{},{},{},{},{}",
+            a.names[i],
+            a.points[i],
+            games,
+            a.elo[i],
+            a.glicko[i].rating,
+            a.glicko[i].deviation,
+            a.glicko[i].volatility
+        )
+        .unwrap();
+    }
+
+    let results = result_matrix(a);
+    let mut f = File::create(dir_name.to_owned() + "/results.csv").unwrap();
+    writeln!(f, "name,{}", a.names.join(",")).unwrap();
+    for i in 0..n {
+        let row: Vec<String> = (0..n)
+            .map(|j| results.value[j * n + i])
+            .map(|v| {
+                if v.is_nan() {
+                    String::new()
+                } else {
+                    v.to_string()
+                }
+            })
+            .collect();
+        writeln!(f, "{},{}", a.names[i], row.join(",")).unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+#[cfg(test)]
+use crate::evolution::rating;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use std::fs;
+
+// players are xor models whose first weight is their strength, the stronger player always wins
+#[cfg(test)]
+fn players(n: usize) -> (Vec<String>, Vec<xor::XorModel>) {
+    let names = (0..n)
+        .map(|i| "player".to_owned() + &i.to_string())
+        .collect();
+    let models = (0..n)
+        .map(|i| {
+            let mut model = xor::new();
+            model.parameters_mut()[0].value[0] = i as f32;
+            model
+        })
+        .collect();
+
+    (names, models)
+}
+
+#[cfg(test)]
+fn stronger(a: &xor::XorModel, b: &xor::XorModel) -> f32 {
+    let (a, b) = (a.parameters()[0].1.value[0], b.parameters()[0].1.value[0]);
+    if a > b {
+        1.0
+    } else if a < b {
+        0.0
+    } else {
+        0.5
+    }
+}
+
+#[test]
+fn elo_test() {
+    assert_eq!(rating::elo_expected(1500.0, 1500.0), 0.5);
+    assert!((rating::elo_expected(1900.0, 1500.0) - 10.0 / 11.0).abs() < 1e-5);
+
+    // rating is exchanged, the upset moves more points
+    let (a, b) = rating::elo_update(1500.0, 1500.0, 1.0, 32.0);
+    assert_eq!((a, b), (1516.0, 1484.0));
+    let (a, _) = rating::elo_update(1500.0, 1900.0, 1.0, 32.0);
+    assert!((a - 1500.0 - 32.0 * 10.0 / 11.0).abs() < 1e-3);
+}
+
+#[test]
+fn glicko_test() {
+    // worked example from glickman's description of glicko-2
+    let player = rating::Glicko {
+        rating: 1500.0,
+        deviation: 200.0,
+        volatility: 0.06,
+    };
+    let opponent = |rating: f32, deviation: f32| rating::Glicko {
+        rating,
+        deviation,
+        volatility: 0.06,
+    };
+    let results = [
+        (opponent(1400.0, 30.0), 1.0),
+        (opponent(1550.0, 100.0), 0.0),
+        (opponent(1700.0, 300.0), 0.0),
+    ];
+
+    let b = rating::glicko_update(&player, &results, 0.5);
+    assert!((b.rating - 1464.06).abs() < 0.1);
+    assert!((b.deviation - 151.52).abs() < 0.1);
+    assert!((b.volatility - 0.05999).abs() < 1e-4);
+
+    // no games only grows the deviation
+    let c = rating::glicko_update(&player, &[], 0.5);
+    assert_eq!(c.rating, 1500.0);
+    assert!(c.deviation > 200.0);
+}
+
+#[test]
+fn round_robin_schedule_test() {
+    for n in [2, 5, 6] {
+        let rounds = rating::round_robin_schedule(n);
+        assert_eq!(rounds.len(), n + n % 2 - 1);
+
+        let mut met = vec![0; n * n];
+        for pairs in rounds {
+            // nobody plays twice in a round
+            let mut seen = vec![false; n];
+            for (a, b) in pairs {
+                assert!(!seen[a] && !seen[b]);
+                seen[a] = true;
+                seen[b] = true;
+                met[a * n + b] += 1;
+                met[b * n + a] += 1;
+            }
+        }
+        for a in 0..n {
+            for b in 0..n {
+                assert_eq!(met[a * n + b], if a == b { 0 } else { 1 });
+            }
+        }
+    }
+}
+
+#[test]
+fn round_robin_test() {
+    let (names, models) = players(4);
+    let a = rating::round_robin(&models, &names, stronger);
+
+    assert_eq!(a.rounds, 3);
+    assert_eq!(a.points, vec![0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(rating::standings(&a), vec![3, 2, 1, 0]);
+    assert!(a.elo[3] > a.elo[2] && a.elo[1] > a.elo[0]);
+    assert!(a.glicko[3].deviation < rating::INITIAL_DEVIATION);
+
+    let results = rating::result_matrix(&a);
+    // row 3 against column 0, column major
+    assert_eq!(results.value[3], 1.0);
+    assert_eq!(results.value[12], 0.0);
+    assert!(results.value[0].is_nan());
+}
+
+#[test]
+fn nan_standings_test() {
+    let (names, _) = players(3);
+    let mut a = rating::new(&names, 32.0, 0.5);
+    a.points = vec![1.0, f32::NAN, 2.0];
+    a.glicko[0].rating = f32::NAN;
+
+    // NaN sorts last instead of panicking
+    assert_eq!(rating::standings(&a), vec![1, 2, 0]);
+    let (pairs, bye) = rating::swiss_pairing(&a);
+    assert_eq!(pairs.len(), 1);
+    assert!(bye.is_some());
+}
+
+#[test]
+fn swiss_test() {
+    let (names, models) = players(5);
+    let a = rating::swiss(&models, &names, 3, stronger);
+
+    assert_eq!(a.rounds, 3);
+    // one bye per round, never twice for the same player
+    assert_eq!(a.byes.iter().sum::<usize>(), 3);
+    assert!(a.byes.iter().all(|b| *b <= 1));
+    // no rematches in 3 rounds of 5 players
+    assert!(a.games.value.iter().all(|g| *g <= 1.0));
+    assert_eq!(rating::standings(&a)[0], 4);
+    assert_eq!(a.points[4], 3.0);
+}
+
+#[test]
+fn save_report_test() {
+    let (names, models) = players(3);
+    let a = rating::round_robin(&models, &names, stronger);
+
+    rating::save_report(&a, "rating_save_report_test");
+    let ratings = fs::read_to_string("rating_save_report_test/ratings.csv").unwrap();
+    let results = fs::read_to_string("rating_save_report_test/results.csv").unwrap();
+    fs::remove_dir_all("rating_save_report_test").unwrap();
+
+    let lines: Vec<&str> = ratings.lines().collect();
+    assert_eq!(
+        lines[0],
+        "name,points,games,elo,glicko,deviation,volatility"
+    );
+    assert!(lines[1].starts_with("player2,2,2,"));
+    let lines: Vec<&str> = results.lines().collect();
+    assert_eq!(lines[0], "name,player0,player1,player2");
+    assert_eq!(lines[1], "player0,,0,0");
+}
+
+#[test]
+fn load_models_test() {
+    let (_, models) = players(3);
+    for (i, model) in models.iter().enumerate().rev() {
+        model.save(("rating_load_models_test/model".to_owned() + &i.to_string()).as_str());
+    }
+    fs::write("rating_load_models_test/notes.txt", "not a model").unwrap();
+
+    let (names, loaded) = rating::load_models(&xor::new(), "rating_load_models_test");
+    fs::remove_dir_all("rating_load_models_test").unwrap();
+
+    assert_eq!(names, vec!["model0", "model1", "model2"]);
+    assert_eq!(loaded[2].parameters()[0].1.value[0], 2.0);
+}
+
+#[test]
+#[should_panic]
+fn new_panic_test() {
+    let _ = rating::new(&["alone".to_string()], 32.0, 0.5);
+}
@@ -0,0 +1,117 @@
+pub mod test;
+
+use crate::evolution;
+use crate::evolution::fitness_shaping;
+use crate::evolution::Evolve;
+use crate::models;
+use crate::models::Model;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rand_distr::StandardNormal;
+use std::fs;
+
+// separable natural evolution strategies (Schaul et al. 2011)
+// a diagonal gaussian over the flattened parameters, every parameter adapts its own sigma
+#[derive(Clone)]
+pub struct Snes<M: Model> {
+    // gives the shape of the model, its values are not used
+    pub template: M,
+    pub mean: Vec<f32>,
+    pub sigma: Vec<f32>,
+    pub population_size: usize,
+    pub mean_learning_rate: f32,
+    pub sigma_learning_rate: f32,
+    pub rng: ChaCha8Rng,
+    pub samples: Vec<Vec<f32>>,
+}
+
+// population size and learning rates default to the values from the paper
+pub fn new<M: Model>(model: &M, sigma: f32, seed: u64) -> Snes<M> {
+    let d = models::num_parameters(model) as f32;
+
+    Snes {
+        template: model.clone(),
+        mean: models::flatten(model),
+        sigma: vec![sigma; d as usize],
+        population_size: 4 + (3.0 * d.ln()).floor() as usize,
+        mean_learning_rate: 1.0,
+        sigma_learning_rate: (3.0 + d.ln()) / (5.0 * d.sqrt()),
+        rng: ChaCha8Rng::seed_from_u64(seed),
+        samples: vec![],
+    }
+}
+
+// per parameter sigma in the shape of the model, parameters() names the layers
+pub fn step_sizes<M: Model>(a: &Snes<M>) -> M {
+    models::unflatten(&a.template, &a.sigma)
+}
+
+impl<M: Model> Evolve<M> for Snes<M> {
+    fn ask(&mut self) -> Vec<M> {
+        self.samples.clear();
+        for _ in 0..self.population_size {
+            let s: Vec<f32> = (0..self.mean.len())
+                .map(|_| self.rng.sample(StandardNormal))
+                .collect();
+            self.samples.push(s);
+        }
+
+        self.samples
+            .iter()
+            .map(|s| {
+                let z: Vec<f32> = (0..s.len())
+                    .map(|i| self.mean[i] + self.sigma[i] * s[i])
+                    .collect();
+                models::unflatten(&self.template, &z)
+            })
+            .collect()
+    }
+
+    fn tell(&mut self, fitness: &[f32]) {
+        assert!(
+            fitness.len() == self.samples.len(),
+            "Every candidate needs a fitness."
+        );
+
+        let utilities = fitness_shaping::utilities(fitness);
+        for i in 0..self.mean.len() {
+            let mut mean_gradient: f32 = 0.0;
+            let mut sigma_gradient: f32 = 0.0;
+            for (k, s) in self.samples.iter().enumerate() {
+                mean_gradient += utilities[k] * s[i];
+                sigma_gradient += utilities[k] * (s[i] * s[i] - 1.0);
+            }
+
+            self.mean[i] += self.mean_learning_rate * self.sigma[i] * mean_gradient;
+            self.sigma[i] *= (self.sigma_learning_rate / 2.0 * sigma_gradient).exp();
+        }
+    }
+
+    fn model(&self) -> M {
+        models::unflatten(&self.template, &self.mean)
+    }
+
+    fn inject(&mut self, migrants: &[M], fitness: &[f32]) {
+        if let Some(best) = evolution::best_migrant(migrants, fitness) {
+            self.mean = models::flatten(best);
+        }
+    }
+
+    fn sigma(&self) -> Option<f32> {
+        Some(self.sigma.iter().sum::<f32>() / self.sigma.len() as f32)
+    }
+
+    fn save(&self, dir_name: &str) {
+        fs::create_dir_all(dir_name).unwrap();
+        evolution::save_values(&self.mean, (dir_name.to_owned() + "/mean.bin").as_str());
+        evolution::save_values(&self.sigma, (dir_name.to_owned() + "/sigma.bin").as_str());
+        evolution::save_rng(&self.rng, (dir_name.to_owned() + "/rng.txt").as_str());
+    }
+
+    fn load(&mut self, dir_name: &str) {
+        self.mean = evolution::load_values((dir_name.to_owned() + "/mean.bin").as_str());
+        self.sigma = evolution::load_values((dir_name.to_owned() + "/sigma.bin").as_str());
+        self.rng = evolution::load_rng((dir_name.to_owned() + "/rng.txt").as_str());
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+use crate::evolution;
+#[cfg(test)]
+use crate::evolution::snes;
+#[cfg(test)]
+use crate::evolution::Evolve;
+#[cfg(test)]
+use crate::models;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+
+#[cfg(test)]
+fn distance(a: &xor::XorModel, b: &xor::XorModel) -> f32 {
+    let a = models::flatten(a);
+    let b = models::flatten(b);
+
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+#[test]
+fn new_test() {
+    let model = xor::new_gaussian_noise();
+    let a = snes::new(&model, 0.1, 0);
+
+    // 52 parameters
+    assert_eq!(a.population_size, 4 + (3.0 * 52_f32.ln()).floor() as usize);
+    assert_eq!(a.mean, models::flatten(&model));
+    assert_eq!(a.sigma, vec![0.1; 52]);
+    assert_eq!(distance(&a.model(), &model), 0.0);
+}
+
+#[test]
+fn ask_seed_test() {
+    let model = xor::new_gaussian_noise();
+
+    let first = snes::new(&model, 0.1, 3).ask();
+    let second = snes::new(&model, 0.1, 3).ask();
+    let third = snes::new(&model, 0.1, 4).ask();
+    for i in 0..first.len() {
+        assert_eq!(distance(&first[i], &second[i]), 0.0);
+        assert!(distance(&first[i], &third[i]) > 0.0);
+    }
+}
+
+#[test]
+#[should_panic]
+fn tell_length_panic_test() {
+    let mut a = snes::new(&xor::new(), 0.1, 0);
+
+    a.ask();
+    a.tell(&[1.0, 2.0]);
+}
+
+#[test]
+fn step_sizes_test() {
+    let target = xor::new_gaussian_noise();
+    let mut a = snes::new(&xor::new(), 0.5, 1);
+
+    // fitness ignores fc1, so its sigma should stay large while the other layers converge
+    let mut fixed = target.clone();
+    fixed.fc1 = xor::new().fc1;
+    evolution::run(&mut a, 100, |m| {
+        let mut b = m.clone();
+        b.fc1 = fixed.fc1.clone();
+        -distance(&b, &fixed)
+    });
+
+    let step_sizes = snes::step_sizes(&a);
+    let names: Vec<String> = step_sizes
+        .parameters()
+        .into_iter()
+        .map(|(n, _)| n)
+        .collect();
+    assert_eq!(
+        names,
+        ["fc1.weights", "fc1.bias", "fc2.weights", "fc2.bias"]
+    );
+
+    let means = models::parameter_means(&step_sizes);
+    assert!(means[0].1 > means[2].1);
+    assert!(means[1].1 > means[3].1);
+}
+
+#[test]
+fn run_test() {
+    let target = xor::new_gaussian_noise();
+    let start = xor::new();
+    let mut a = snes::new(&start, 0.5, 1);
+
+    let model = evolution::run(&mut a, 500, |m| -distance(m, &target));
+    assert!(distance(&model, &target) < distance(&start, &target) * 0.01);
+}
+
+#[test]
+fn inject_test() {
+    let mut a = snes::new(&xor::new(), 0.1, 0);
+    let b = xor::new_gaussian_noise();
+    let c = xor::new_gaussian_noise();
+
+    a.inject(&[b.clone(), c], &[2.0, 1.0]);
+    assert_eq!(distance(&a.model(), &b), 0.0);
+}
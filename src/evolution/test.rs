@@ -0,0 +1,189 @@
+#[cfg(test)]
+use crate::evolution;
+#[cfg(test)]
+use crate::evolution::benchmark;
+#[cfg(test)]
+use crate::evolution::cem;
+#[cfg(test)]
+use crate::evolution::differential_evolution;
+#[cfg(test)]
+use crate::evolution::es;
+#[cfg(test)]
+use crate::evolution::fitness_shaping::FitnessShaping;
+#[cfg(test)]
+use crate::evolution::islands;
+#[cfg(test)]
+use crate::evolution::pepg;
+#[cfg(test)]
+use crate::evolution::snes;
+#[cfg(test)]
+use crate::evolution::xnes;
+#[cfg(test)]
+use crate::evolution::Evolve;
+#[cfg(test)]
+use crate::models;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use crate::optim::adam;
+#[cfg(test)]
+use rand::Rng;
+#[cfg(test)]
+use rand::SeedableRng;
+#[cfg(test)]
+use rand_chacha::ChaCha8Rng;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn best_index_test() {
+    assert_eq!(evolution::best_index(&[1.0, 3.0, -2.0, 3.0]), 1);
+    assert_eq!(evolution::best_index(&[f32::NAN, -2.0, f32::NAN]), 1);
+}
+
+#[test]
+fn best_migrant_test() {
+    let migrants = vec![xor::new(), xor::new_gaussian_noise()];
+
+    let best = evolution::best_migrant(&migrants, &[0.0, 1.0]).unwrap();
+    assert_eq!(models::flatten(best), models::flatten(&migrants[1]));
+    assert!(evolution::best_migrant::<xor::XorModel>(&[], &[]).is_none());
+}
+
+#[test]
+#[should_panic]
+fn best_migrant_length_panic_test() {
+    let _ = evolution::best_migrant(&[xor::new()], &[0.0, 1.0]);
+}
+
+#[test]
+fn tournament_test() {
+    let mut better = xor::new();
+    better.fc1.weights.value[0] = 1.0;
+    let candidates = vec![xor::new(), better, xor::new()];
+
+    // the candidate with the larger first weight always wins
+    let fitness = evolution::tournament(&candidates, |a, b| {
+        let (x, y) = (a.fc1.weights.value[0], b.fc1.weights.value[0]);
+        if x > y {
+            1.0
+        } else if x < y {
+            0.0
+        } else {
+            0.5
+        }
+    });
+    assert_eq!(fitness, [0.25, 1.0, 0.25]);
+    assert_eq!(evolution::tournament(&candidates[..1], |_, _| 1.0), [0.0]);
+}
+
+#[test]
+fn run_tournament_test() {
+    // the larger the sum of parameters the better, relative to the other candidates
+    let sum = |a: &xor::XorModel| models::flatten(a).iter().sum::<f32>();
+    let mut a = snes::new(&xor::new(), 0.1, 0);
+
+    let model =
+        evolution::run_tournament(&mut a, 50, |a, b| if sum(a) > sum(b) { 1.0 } else { 0.0 });
+    assert!(sum(&model) > 1.0);
+}
+
+// every optimizer, seed changes all of their random state
+#[cfg(test)]
+fn optimizers(seed: u64) -> Vec<Box<dyn Evolve<xor::XorModel>>> {
+    let model = xor::new_gaussian_noise().scalar(0.5);
+    let islands: Vec<Box<dyn Evolve<xor::XorModel>>> = vec![
+        Box::new(snes::new(&model, 0.1, seed)),
+        Box::new(cem::new(&model, 0.1, 10, 0.2, seed + 1)),
+    ];
+
+    vec![
+        Box::new(es::new(
+            &model,
+            adam::new(&model, 0.05),
+            0.1,
+            10,
+            true,
+            FitnessShaping::CenteredRanks,
+            seed,
+        )),
+        Box::new(pepg::new(
+            &model,
+            adam::new(&model, 0.05),
+            0.1,
+            0.2,
+            10,
+            FitnessShaping::CenteredRanks,
+            seed,
+        )),
+        Box::new(snes::new(&model, 0.1, seed)),
+        Box::new(xnes::new(&model, 0.1, seed)),
+        Box::new(cem::new(&model, 0.1, 10, 0.2, seed)),
+        Box::new(differential_evolution::new(
+            &model,
+            10,
+            0.1,
+            differential_evolution::Strategy::Jade,
+            seed,
+        )),
+        Box::new(islands::new(
+            islands,
+            islands::Topology::Ring,
+            2,
+            1,
+            islands::MigrantSelection::Random,
+            seed,
+        )),
+    ]
+}
+
+#[test]
+fn save_load_test() {
+    let generations = |evolve: &mut Box<dyn Evolve<xor::XorModel>>| {
+        for _ in 0..3 {
+            let candidates = evolve.ask();
+            let fitness: Vec<f32> = candidates.iter().map(benchmark::xor).collect();
+            evolve.tell(&fitness);
+        }
+        models::flatten(&evolve.model())
+    };
+
+    for (mut a, mut b) in optimizers(1).into_iter().zip(optimizers(2)) {
+        generations(&mut a);
+        a.save("evolution_save_load_test");
+        b.load("evolution_save_load_test");
+        fs::remove_dir_all("evolution_save_load_test").unwrap();
+
+        // continuing from the saved state gives the same generations
+        assert_eq!(generations(&mut a), generations(&mut b));
+    }
+}
+
+#[test]
+fn save_load_rng_test() {
+    let mut a = ChaCha8Rng::seed_from_u64(3);
+    a.set_stream(5);
+    let _: f32 = a.gen();
+
+    evolution::save_rng(&a, "evolution_save_load_rng_test.txt");
+    let mut b = evolution::load_rng("evolution_save_load_rng_test.txt");
+    fs::remove_file("evolution_save_load_rng_test.txt").unwrap();
+
+    let x: Vec<f32> = (0..5).map(|_| a.gen()).collect();
+    let y: Vec<f32> = (0..5).map(|_| b.gen()).collect();
+    assert_eq!(x, y);
+}
+
+#[test]
+fn save_load_values_test() {
+    evolution::save_values(&[1.5, -2.0], "evolution_save_load_values_test.bin");
+    let a = evolution::load_values("evolution_save_load_values_test.bin");
+    evolution::save_values(&[], "evolution_save_load_values_test.bin");
+    let b = evolution::load_values("evolution_save_load_values_test.bin");
+    fs::remove_file("evolution_save_load_values_test.bin").unwrap();
+
+    assert_eq!(a, vec![1.5, -2.0]);
+    assert!(b.is_empty());
+}
@@ -0,0 +1,164 @@
+pub mod test;
+
+use crate::evolution;
+use crate::evolution::fitness_shaping;
+use crate::evolution::Evolve;
+use crate::matrix;
+use crate::models;
+use crate::models::Model;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rand_distr::StandardNormal;
+use std::fs;
+
+// exponential natural evolution strategies (Glasmachers et al. 2010)
+// a full gaussian over the flattened parameters, candidates are mean + sigma * b * s
+// b is d x d so this is only practical for small models like XorModel
+#[derive(Clone)]
+pub struct Xnes<M: Model> {
+    // gives the shape of the model, its values are not used
+    pub template: M,
+    pub mean: Vec<f32>,
+    pub sigma: f32,
+    // covariance factor with determinant 1, covariance = sigma^2 * b * b^T
+    pub b: matrix::Matrix,
+    pub population_size: usize,
+    pub mean_learning_rate: f32,
+    pub sigma_learning_rate: f32,
+    pub b_learning_rate: f32,
+    pub rng: ChaCha8Rng,
+    pub samples: Vec<matrix::Matrix>,
+}
+
+// population size and learning rates default to the values from the paper
+pub fn new<M: Model>(model: &M, sigma: f32, seed: u64) -> Xnes<M> {
+    let d = models::num_parameters(model);
+    let learning_rate = 3.0 * (3.0 + (d as f32).ln()) / (5.0 * d as f32 * (d as f32).sqrt());
+
+    Xnes {
+        template: model.clone(),
+        mean: models::flatten(model),
+        sigma,
+        b: matrix::identity(d),
+        population_size: 4 + (3.0 * (d as f32).ln()).floor() as usize,
+        mean_learning_rate: 1.0,
+        sigma_learning_rate: learning_rate,
+        b_learning_rate: learning_rate,
+        rng: ChaCha8Rng::seed_from_u64(seed),
+        samples: vec![],
+    }
+}
+
+// marginal standard deviation of every parameter in the shape of the model,
+// sigma * sqrt(diag(b * b^T)), parameters() names the layers
+pub fn step_sizes<M: Model>(a: &Xnes<M>) -> M {
+    let d = a.mean.len();
+    let mut b: Vec<f32> = vec![0.0; d];
+    for (i, value) in b.iter_mut().enumerate() {
+        let mut sum: f32 = 0.0;
+        for j in 0..d {
+            sum += a.b.value[j * d + i].powi(2);
+        }
+        *value = a.sigma * sum.sqrt();
+    }
+
+    models::unflatten(&a.template, &b)
+}
+
+impl<M: Model> Evolve<M> for Xnes<M> {
+    fn ask(&mut self) -> Vec<M> {
+        self.samples.clear();
+        for _ in 0..self.population_size {
+            let mut s = matrix::new(self.mean.len(), 1);
+            for value in s.value.iter_mut() {
+                *value = self.rng.sample(StandardNormal);
+            }
+            self.samples.push(s);
+        }
+
+        self.samples
+            .iter()
+            .map(|s| {
+                let bs = matrix::multiply(&self.b, s);
+                let z: Vec<f32> = (0..bs.value.len())
+                    .map(|i| self.mean[i] + self.sigma * bs.value[i])
+                    .collect();
+                models::unflatten(&self.template, &z)
+            })
+            .collect()
+    }
+
+    fn tell(&mut self, fitness: &[f32]) {
+        assert!(
+            fitness.len() == self.samples.len(),
+            "Every candidate needs a fitness."
+        );
+
+        let d = self.mean.len();
+        let utilities = fitness_shaping::utilities(fitness);
+
+        // natural gradients in local coordinates
+        // g_delta = sum(u * s), g_m = sum(u * (s * s^T - i))
+        let mut g_delta = matrix::new(d, 1);
+        let mut g_m = matrix::new(d, d);
+        for (k, s) in self.samples.iter().enumerate() {
+            g_delta = matrix::add(&g_delta, &matrix::scalar(s, utilities[k]));
+            let outer = matrix::multiply(s, &matrix::transpose(s));
+            let centered = matrix::add(&outer, &matrix::scalar(&matrix::identity(d), -1.0));
+            g_m = matrix::add(&g_m, &matrix::scalar(&centered, utilities[k]));
+        }
+
+        // split g_m into a scale part for sigma and a shape part for b
+        let mut trace: f32 = 0.0;
+        for i in 0..d {
+            trace += g_m.value[i * d + i];
+        }
+        let g_sigma = trace / d as f32;
+        let g_b = matrix::add(&g_m, &matrix::scalar(&matrix::identity(d), -g_sigma));
+
+        let step = matrix::multiply(&self.b, &g_delta);
+        for i in 0..d {
+            self.mean[i] += self.mean_learning_rate * self.sigma * step.value[i];
+        }
+        self.sigma *= (self.sigma_learning_rate / 2.0 * g_sigma).exp();
+        self.b = matrix::multiply(
+            &self.b,
+            &matrix::exponential(&matrix::scalar(&g_b, self.b_learning_rate / 2.0)),
+        );
+    }
+
+    fn model(&self) -> M {
+        models::unflatten(&self.template, &self.mean)
+    }
+
+    fn inject(&mut self, migrants: &[M], fitness: &[f32]) {
+        if let Some(best) = evolution::best_migrant(migrants, fitness) {
+            self.mean = models::flatten(best);
+        }
+    }
+
+    // the covariance factor has determinant 1, so sigma is the overall step size
+    fn sigma(&self) -> Option<f32> {
+        Some(self.sigma)
+    }
+
+    fn save(&self, dir_name: &str) {
+        fs::create_dir_all(dir_name).unwrap();
+        evolution::save_values(&self.mean, (dir_name.to_owned() + "/mean.bin").as_str());
+        fs::write(dir_name.to_owned() + "/sigma.txt", self.sigma.to_string()).unwrap();
+        matrix::save(&self.b, (dir_name.to_owned() + "/b.bin").as_str());
+        evolution::save_rng(&self.rng, (dir_name.to_owned() + "/rng.txt").as_str());
+    }
+
+    fn load(&mut self, dir_name: &str) {
+        self.mean = evolution::load_values((dir_name.to_owned() + "/mean.bin").as_str());
+        self.sigma = fs::read_to_string(dir_name.to_owned() + "/sigma.txt")
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        self.b = matrix::load(&self.b, (dir_name.to_owned() + "/b.bin").as_str());
+        self.rng = evolution::load_rng((dir_name.to_owned() + "/rng.txt").as_str());
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+use crate::evolution;
+#[cfg(test)]
+use crate::evolution::xnes;
+#[cfg(test)]
+use crate::evolution::Evolve;
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::models;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use rand::SeedableRng;
+#[cfg(test)]
+use rand_chacha::ChaCha8Rng;
+
+#[cfg(test)]
+fn distance(a: &xor::XorModel, b: &xor::XorModel) -> f32 {
+    let a = models::flatten(a);
+    let b = models::flatten(b);
+
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+#[test]
+fn new_test() {
+    let model = xor::new_gaussian_noise();
+    let a = xnes::new(&model, 0.1, 0);
+
+    assert_eq!(a.b.rows, 52);
+    assert_eq!(a.b.value, matrix::identity(52).value);
+    assert_eq!(a.sigma, 0.1);
+    assert_eq!(distance(&a.model(), &model), 0.0);
+}
+
+#[test]
+fn step_sizes_test() {
+    let mut a = xnes::new(&xor::new(), 0.5, 0);
+    a.b.value[0] = 2.0;
+
+    let step_sizes = xnes::step_sizes(&a);
+    let parameters = step_sizes.parameters();
+    assert_eq!(parameters[0].0, "fc1.weights");
+    assert_eq!(parameters[0].1.value[0], 1.0);
+    assert_eq!(parameters[0].1.value[1], 0.5);
+    assert_eq!(parameters[3].0, "fc2.bias");
+    assert_eq!(parameters[3].1.value, [0.5, 0.5]);
+}
+
+#[test]
+fn ask_seed_test() {
+    let model = xor::new_gaussian_noise();
+
+    let first = xnes::new(&model, 0.1, 3).ask();
+    let second = xnes::new(&model, 0.1, 3).ask();
+    let third = xnes::new(&model, 0.1, 4).ask();
+    for i in 0..first.len() {
+        assert_eq!(distance(&first[i], &second[i]), 0.0);
+        assert!(distance(&first[i], &third[i]) > 0.0);
+    }
+}
+
+#[test]
+#[should_panic]
+fn tell_length_panic_test() {
+    let mut a = xnes::new(&xor::new(), 0.1, 0);
+
+    a.ask();
+    a.tell(&[1.0, 2.0]);
+}
+
+#[test]
+fn run_test() {
+    // seeded target, some unseeded draws needed more than 300 generations
+    let target = models::gaussian_noise(&xor::new(), &mut ChaCha8Rng::seed_from_u64(0));
+    let start = xor::new();
+    let mut a = xnes::new(&start, 0.5, 1);
+
+    let model = evolution::run(&mut a, 300, |m| -distance(m, &target));
+    assert!(distance(&model, &target) < distance(&start, &target) * 0.1);
+}
@@ -0,0 +1,193 @@
+pub mod test;
+
+use crate::games::Game;
+use crate::matrix;
+
+pub const ROWS: usize = 6;
+pub const COLUMNS: usize = 7;
+
+// cells are 1 for the first player, -1 for the second and 0 when empty
+// cell index is row * 7 + column with row 0 at the top, moves are columns
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Board {
+    pub cells: [i8; ROWS * COLUMNS],
+    // player to move, 1 or -1
+    pub player: i8,
+}
+
+pub fn new() -> Board {
+    Board {
+        cells: [0; ROWS * COLUMNS],
+        player: 1,
+    }
+}
+
+pub fn print(a: &Board) {
+    for row in 0..ROWS {
+        let mut line = String::new();
+        for column in 0..COLUMNS {
+            line.push(match a.cells[row * COLUMNS + column] {
+                1 => 'X',
+                -1 => 'O',
+                _ => '.',
+            });
+        }
+        println!("{}", line);
+    }
+    println!("0123456");
+    println!();
+}
+
+pub fn legal_moves(a: &Board) -> Vec<usize> {
+    if winner(a).is_some() {
+        return vec![];
+    }
+
+    (0..COLUMNS)
+        .filter(|column| a.cells[*column] == 0)
+        .collect()
+}
+
+// the stone falls to the lowest empty cell of the column
+pub fn play(a: &Board, column: usize) -> Board {
+    assert!(column < COLUMNS, "Move is off the board.");
+    assert!(a.cells[column] == 0, "Column is full.");
+    assert!(winner(a).is_none(), "Game is already over.");
+
+    let mut b = *a;
+    let mut row = ROWS - 1;
+    while b.cells[row * COLUMNS + column] != 0 {
+        row -= 1;
+    }
+    b.cells[row * COLUMNS + column] = a.player;
+    b.player = -a.player;
+
+    b
+}
+
+// every line of 4 cells on the board, built at compile time since winner and heuristic
+// run at every search node
+pub const WINDOWS: [[usize; 4]; 69] = windows();
+
+pub const fn windows() -> [[usize; 4]; 69] {
+    let mut b = [[0; 4]; 69];
+    let mut n = 0;
+    // right, down, down right, down left
+    let steps: [(i32, i32); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+    let mut s = 0;
+    while s < steps.len() {
+        let (row_step, column_step) = steps[s];
+        let mut row = 0;
+        while row < ROWS as i32 {
+            let mut column = 0;
+            while column < COLUMNS as i32 {
+                let end_row = row + 3 * row_step;
+                let end_column = column + 3 * column_step;
+                if end_row >= 0
+                    && end_row < ROWS as i32
+                    && end_column >= 0
+                    && end_column < COLUMNS as i32
+                {
+                    let mut k = 0;
+                    while k < 4 {
+                        let r = row + k * row_step;
+                        let c = column + k * column_step;
+                        b[n][k as usize] = r as usize * COLUMNS + c as usize;
+                        k += 1;
+                    }
+                    n += 1;
+                }
+                column += 1;
+            }
+            row += 1;
+        }
+        s += 1;
+    }
+
+    b
+}
+
+// Some(1) or Some(-1) for four in a row, Some(0) for a full board and None while the game goes on
+pub fn winner(a: &Board) -> Option<i8> {
+    for window in WINDOWS.iter() {
+        let first = a.cells[window[0]];
+        if first != 0 && window.iter().all(|index| a.cells[*index] == first) {
+            return Some(first);
+        }
+    }
+
+    if a.cells.iter().all(|cell| *cell != 0) {
+        return Some(0);
+    }
+
+    None
+}
+
+// 3 planes of 6 x 7 as seen by the player to move: own stones, opponent stones, empty cells
+pub fn encode(a: &Board) -> Vec<matrix::Matrix> {
+    let mut planes = vec![
+        matrix::new(ROWS, COLUMNS),
+        matrix::new(ROWS, COLUMNS),
+        matrix::new(ROWS, COLUMNS),
+    ];
+    for row in 0..ROWS {
+        for column in 0..COLUMNS {
+            let cell = a.cells[row * COLUMNS + column];
+            let plane = if cell == a.player {
+                0
+            } else if cell == -a.player {
+                1
+            } else {
+                2
+            };
+            // matrices are column major
+            planes[plane].value[column * ROWS + row] = 1.0;
+        }
+    }
+
+    planes
+}
+
+// open lines for the player to move minus open lines for the opponent,
+// lines with more stones count more and the center column is worth a little extra
+pub fn heuristic(a: &Board) -> f32 {
+    let mut score: f32 = 0.0;
+    for window in WINDOWS.iter() {
+        let own = window.iter().filter(|i| a.cells[**i] == a.player).count();
+        let opponent = window.iter().filter(|i| a.cells[**i] == -a.player).count();
+        let value = |stones: usize| match stones {
+            2 => 2.0,
+            3 => 10.0,
+            _ => 0.0,
+        };
+        if opponent == 0 {
+            score += value(own);
+        } else if own == 0 {
+            score -= value(opponent);
+        }
+    }
+
+    for row in 0..ROWS {
+        score += 3.0 * a.cells[row * COLUMNS + COLUMNS / 2] as f32 * a.player as f32;
+    }
+
+    score
+}
+
+impl Game for Board {
+    fn legal_moves(&self) -> Vec<usize> {
+        legal_moves(self)
+    }
+
+    fn play(&self, index: usize) -> Board {
+        play(self, index)
+    }
+
+    fn winner(&self) -> Option<i8> {
+        winner(self)
+    }
+
+    fn player(&self) -> i8 {
+        self.player
+    }
+}
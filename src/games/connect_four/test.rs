@@ -0,0 +1,116 @@
+#[cfg(test)]
+use crate::games;
+#[cfg(test)]
+use crate::games::connect_four;
+
+#[cfg(test)]
+fn board(moves: &[usize]) -> connect_four::Board {
+    let mut a = connect_four::new();
+    for column in moves {
+        a = connect_four::play(&a, *column);
+    }
+
+    a
+}
+
+#[test]
+fn new_test() {
+    let a = connect_four::new();
+
+    assert_eq!(a.player, 1);
+    assert_eq!(connect_four::legal_moves(&a), [0, 1, 2, 3, 4, 5, 6]);
+    assert_eq!(connect_four::winner(&a), None);
+}
+
+#[test]
+fn print_test() {
+    // pass = does not panic
+    connect_four::print(&board(&[3, 3, 4]));
+}
+
+#[test]
+fn play_test() {
+    let a = board(&[3, 3, 4]);
+
+    // stones stack from the bottom row
+    assert_eq!(a.cells[5 * 7 + 3], 1);
+    assert_eq!(a.cells[4 * 7 + 3], -1);
+    assert_eq!(a.cells[5 * 7 + 4], 1);
+    assert_eq!(a.player, -1);
+}
+
+#[test]
+#[should_panic]
+fn play_full_column_panic_test() {
+    let _ = board(&[0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn windows_test() {
+    // 24 horizontal, 21 vertical and 12 of each diagonal
+    assert_eq!(connect_four::windows().len(), 69);
+    // every entry is filled in, the last down left diagonal starts in row 2 of the last column
+    assert!(connect_four::WINDOWS.iter().all(|w| w[0] != w[1]));
+    assert_eq!(connect_four::WINDOWS[68], [20, 26, 32, 38]);
+}
+
+#[test]
+fn winner_test() {
+    // horizontal
+    let a = board(&[0, 0, 1, 1, 2, 2, 3]);
+    assert_eq!(connect_four::winner(&a), Some(1));
+    assert!(connect_four::legal_moves(&a).is_empty());
+
+    // vertical for the second player
+    let b = board(&[0, 1, 0, 1, 0, 1, 2, 1]);
+    assert_eq!(connect_four::winner(&b), Some(-1));
+
+    // diagonal up to the right
+    let c = board(&[0, 1, 1, 2, 2, 3, 2, 3, 3, 6, 3]);
+    assert_eq!(connect_four::winner(&c), Some(1));
+}
+
+#[test]
+fn draw_test() {
+    let a = board(&[
+        1, 0, 0, 0, 0, 0, 1, 0, 3, 1, 1, 2, 1, 2, 1, 3, 2, 3, 2, 3, 2, 2, 3, 4, 3, 4, 6, 4, 4, 5,
+        5, 5, 5, 4, 5, 6, 6, 4, 6, 6, 5, 6,
+    ]);
+
+    assert_eq!(connect_four::winner(&a), Some(0));
+    assert!(connect_four::legal_moves(&a).is_empty());
+}
+
+#[test]
+fn encode_test() {
+    let a = board(&[3]);
+
+    // O to move, X is the opponent at the bottom of column 3
+    let planes = connect_four::encode(&a);
+    assert_eq!(planes.len(), 3);
+    assert_eq!(planes[0].rows, 6);
+    assert_eq!(planes[0].columns, 7);
+    assert_eq!(planes[0].value.iter().sum::<f32>(), 0.0);
+    assert_eq!(planes[1].value[3 * 6 + 5], 1.0);
+    assert_eq!(planes[2].value.iter().sum::<f32>(), 41.0);
+}
+
+#[test]
+fn heuristic_test() {
+    // the side with three open in a row is ahead
+    let a = board(&[1, 1, 2, 2, 3]);
+    assert!(connect_four::heuristic(&a) < 0.0);
+    assert!(connect_four::heuristic(&board(&[1, 1, 2, 2, 3, 6])) > 0.0);
+}
+
+#[test]
+fn negamax_test() {
+    // X wins at 4 or 0 on the bottom row
+    let a = board(&[1, 1, 2, 2, 3, 3]);
+    let best = games::best_move(&a, 2, &connect_four::heuristic);
+    assert!(best == 0 || best == 4);
+
+    // O has to block the vertical three
+    let b = board(&[0, 6, 0, 6, 0]);
+    assert_eq!(games::best_move(&b, 4, &connect_four::heuristic), 0);
+}
@@ -0,0 +1,79 @@
+pub mod connect_four;
+pub mod othello;
+pub mod test;
+pub mod tic_tac_toe;
+
+// two player, zero sum board game with players 1 and -1
+pub trait Game: Clone {
+    // empty once the game is over
+    fn legal_moves(&self) -> Vec<usize>;
+    fn play(&self, index: usize) -> Self;
+    // Some(1) or Some(-1) for a win, Some(0) for a draw and None while the game goes on
+    fn winner(&self) -> Option<i8>;
+    // player to move
+    fn player(&self) -> i8;
+}
+
+// score of a won game, more than any heuristic should return
+pub const WIN: f32 = 1000.0;
+
+// value of a position for the player to move, searched depth moves ahead with alpha-beta
+// pruning, heuristic scores the positions at the search horizon for the player to move
+pub fn negamax<G: Game, H: Fn(&G) -> f32>(
+    game: &G,
+    depth: usize,
+    mut alpha: f32,
+    beta: f32,
+    heuristic: &H,
+) -> f32 {
+    if let Some(winner) = game.winner() {
+        // faster wins and slower losses score higher
+        return if winner == 0 {
+            0.0
+        } else if winner == game.player() {
+            WIN + depth as f32
+        } else {
+            -WIN - depth as f32
+        };
+    }
+    if depth == 0 {
+        return heuristic(game);
+    }
+
+    let mut best = f32::NEG_INFINITY;
+    for index in game.legal_moves() {
+        let value = -negamax(&game.play(index), depth - 1, -beta, -alpha, heuristic);
+        best = best.max(value);
+        alpha = alpha.max(value);
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    best
+}
+
+// highest scoring move of a negamax search, the first one on ties
+pub fn best_move<G: Game, H: Fn(&G) -> f32>(game: &G, depth: usize, heuristic: &H) -> usize {
+    let moves = game.legal_moves();
+    assert!(!moves.is_empty(), "Game is already over.");
+    assert!(depth != 0, "Search depth cannot be zero.");
+
+    let mut best = moves[0];
+    let mut alpha = f32::NEG_INFINITY;
+    for index in moves {
+        let value = -negamax(
+            &game.play(index),
+            depth - 1,
+            f32::NEG_INFINITY,
+            -alpha,
+            heuristic,
+        );
+        if value > alpha {
+            alpha = value;
+            best = index;
+        }
+    }
+
+    best
+}
@@ -0,0 +1,265 @@
+pub mod test;
+
+use crate::games::Game;
+use crate::matrix;
+
+pub const SIZE: usize = 8;
+// move index of a pass, only legal when no disc can be placed
+pub const PASS: usize = SIZE * SIZE;
+
+const DIRECTIONS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+// classic disc square weights, corners are strong and the squares next to them weak
+const WEIGHTS: [f32; SIZE * SIZE] = [
+    100.0, -20.0, 10.0, 5.0, 5.0, 10.0, -20.0, 100.0, //
+    -20.0, -50.0, -2.0, -2.0, -2.0, -2.0, -50.0, -20.0, //
+    10.0, -2.0, -1.0, -1.0, -1.0, -1.0, -2.0, 10.0, //
+    5.0, -2.0, -1.0, -1.0, -1.0, -1.0, -2.0, 5.0, //
+    5.0, -2.0, -1.0, -1.0, -1.0, -1.0, -2.0, 5.0, //
+    10.0, -2.0, -1.0, -1.0, -1.0, -1.0, -2.0, 10.0, //
+    -20.0, -50.0, -2.0, -2.0, -2.0, -2.0, -50.0, -20.0, //
+    100.0, -20.0, 10.0, 5.0, 5.0, 10.0, -20.0, 100.0,
+];
+
+// cells are 1 for black, -1 for white and 0 when empty, black moves first
+// cell index is row * 8 + column
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Board {
+    pub cells: [i8; SIZE * SIZE],
+    // player to move, 1 or -1
+    pub player: i8,
+}
+
+pub fn new() -> Board {
+    let mut cells = [0; SIZE * SIZE];
+    cells[3 * SIZE + 3] = -1;
+    cells[3 * SIZE + 4] = 1;
+    cells[4 * SIZE + 3] = 1;
+    cells[4 * SIZE + 4] = -1;
+
+    Board { cells, player: 1 }
+}
+
+pub fn print(a: &Board) {
+    println!(" 01234567");
+    for row in 0..SIZE {
+        let mut line = row.to_string();
+        for column in 0..SIZE {
+            line.push(match a.cells[row * SIZE + column] {
+                1 => 'X',
+                -1 => 'O',
+                _ => '.',
+            });
+        }
+        println!("{}", line);
+    }
+    println!();
+}
+
+// discs of the opponent that placing at index would flip for player
+pub fn flips(a: &Board, index: usize, player: i8) -> Vec<usize> {
+    let mut b: Vec<usize> = vec![];
+    if a.cells[index] != 0 {
+        return b;
+    }
+
+    let (row, column) = ((index / SIZE) as i32, (index % SIZE) as i32);
+    for (row_step, column_step) in DIRECTIONS {
+        let mut line: Vec<usize> = vec![];
+        let (mut r, mut c) = (row + row_step, column + column_step);
+        while r >= 0 && r < SIZE as i32 && c >= 0 && c < SIZE as i32 {
+            let cell = a.cells[r as usize * SIZE + c as usize];
+            if cell == -player {
+                line.push(r as usize * SIZE + c as usize);
+            } else {
+                // a line only flips when it ends in one of the player's discs
+                if cell == player {
+                    b.append(&mut line);
+                }
+                break;
+            }
+            r += row_step;
+            c += column_step;
+        }
+    }
+
+    b
+}
+
+// same test as flips being non empty, without collecting the discs
+fn flips_any(a: &Board, index: usize, player: i8) -> bool {
+    if a.cells[index] != 0 {
+        return false;
+    }
+
+    let (row, column) = ((index / SIZE) as i32, (index % SIZE) as i32);
+    for (row_step, column_step) in DIRECTIONS {
+        let (mut r, mut c) = (row + row_step, column + column_step);
+        let mut seen = false;
+        while r >= 0 && r < SIZE as i32 && c >= 0 && c < SIZE as i32 {
+            let cell = a.cells[r as usize * SIZE + c as usize];
+            if cell != -player {
+                if cell == player && seen {
+                    return true;
+                }
+                break;
+            }
+            seen = true;
+            r += row_step;
+            c += column_step;
+        }
+    }
+
+    false
+}
+
+// whether player has any placement, stops at the first one
+pub fn has_move(a: &Board, player: i8) -> bool {
+    (0..SIZE * SIZE).any(|index| flips_any(a, index, player))
+}
+
+fn placements(a: &Board, player: i8) -> Vec<usize> {
+    (0..SIZE * SIZE)
+        .filter(|index| !flips(a, *index, player).is_empty())
+        .collect()
+}
+
+// placements that flip at least one disc, [PASS] when there are none but the
+// opponent can still move, empty once neither player can move
+pub fn legal_moves(a: &Board) -> Vec<usize> {
+    let moves = placements(a, a.player);
+    if !moves.is_empty() {
+        return moves;
+    }
+    if !has_move(a, -a.player) {
+        return vec![];
+    }
+
+    vec![PASS]
+}
+
+pub fn play(a: &Board, index: usize) -> Board {
+    let mut b = *a;
+    b.player = -a.player;
+    if index == PASS {
+        assert!(
+            legal_moves(a) == [PASS],
+            "Passing is only legal without placements."
+        );
+        return b;
+    }
+
+    assert!(index < SIZE * SIZE, "Move is off the board.");
+    let flipped = flips(a, index, a.player);
+    assert!(!flipped.is_empty(), "Move does not flip any discs.");
+    b.cells[index] = a.player;
+    for i in flipped {
+        b.cells[i] = a.player;
+    }
+
+    b
+}
+
+// (black discs, white discs)
+pub fn count(a: &Board) -> (usize, usize) {
+    (
+        a.cells.iter().filter(|cell| **cell == 1).count(),
+        a.cells.iter().filter(|cell| **cell == -1).count(),
+    )
+}
+
+// once neither player can move the player with more discs wins, None while the game goes on
+pub fn winner(a: &Board) -> Option<i8> {
+    if has_move(a, a.player) || has_move(a, -a.player) {
+        return None;
+    }
+
+    let (black, white) = count(a);
+    Some(match black.cmp(&white) {
+        std::cmp::Ordering::Greater => 1,
+        std::cmp::Ordering::Less => -1,
+        std::cmp::Ordering::Equal => 0,
+    })
+}
+
+// 3 planes of 8 x 8 as seen by the player to move: own discs, opponent discs, legal placements
+pub fn encode(a: &Board) -> Vec<matrix::Matrix> {
+    let mut planes = vec![
+        matrix::new(SIZE, SIZE),
+        matrix::new(SIZE, SIZE),
+        matrix::new(SIZE, SIZE),
+    ];
+    for index in 0..SIZE * SIZE {
+        let (row, column) = (index / SIZE, index % SIZE);
+        // matrices are column major
+        let i = column * SIZE + row;
+        if a.cells[index] == a.player {
+            planes[0].value[i] = 1.0;
+        } else if a.cells[index] == -a.player {
+            planes[1].value[i] = 1.0;
+        }
+    }
+    for index in placements(a, a.player) {
+        planes[2].value[(index % SIZE) * SIZE + index / SIZE] = 1.0;
+    }
+
+    planes
+}
+
+// disc square weights of the player to move minus those of the opponent
+pub fn heuristic(a: &Board) -> f32 {
+    let mut score: f32 = 0.0;
+    for (index, cell) in a.cells.iter().enumerate() {
+        score += WEIGHTS[index] * (*cell * a.player) as f32;
+    }
+
+    score
+}
+
+// baseline that takes the move flipping the most discs
+pub fn greedy_move(a: &Board) -> usize {
+    let moves = legal_moves(a);
+    assert!(!moves.is_empty(), "Game is already over.");
+
+    let mut best = moves[0];
+    let mut most = 0;
+    for index in moves {
+        if index == PASS {
+            return PASS;
+        }
+        let flipped = flips(a, index, a.player).len();
+        if flipped > most {
+            most = flipped;
+            best = index;
+        }
+    }
+
+    best
+}
+
+impl Game for Board {
+    fn legal_moves(&self) -> Vec<usize> {
+        legal_moves(self)
+    }
+
+    fn play(&self, index: usize) -> Board {
+        play(self, index)
+    }
+
+    fn winner(&self) -> Option<i8> {
+        winner(self)
+    }
+
+    fn player(&self) -> i8 {
+        self.player
+    }
+}
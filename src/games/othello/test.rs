@@ -0,0 +1,162 @@
+#[cfg(test)]
+use crate::games;
+#[cfg(test)]
+use crate::games::othello;
+
+#[cfg(test)]
+fn play_game(black_depth: usize, white_depth: usize) -> othello::Board {
+    let mut a = othello::new();
+    while othello::winner(&a).is_none() {
+        let depth = if a.player == 1 {
+            black_depth
+        } else {
+            white_depth
+        };
+        let index = if depth == 0 {
+            othello::greedy_move(&a)
+        } else {
+            games::best_move(&a, depth, &othello::heuristic)
+        };
+        a = othello::play(&a, index);
+    }
+
+    a
+}
+
+#[test]
+fn new_test() {
+    let a = othello::new();
+
+    assert_eq!(othello::count(&a), (2, 2));
+    assert_eq!(a.player, 1);
+    assert_eq!(othello::legal_moves(&a), [19, 26, 37, 44]);
+    assert_eq!(othello::winner(&a), None);
+}
+
+#[test]
+fn print_test() {
+    // pass = does not panic
+    othello::print(&othello::new());
+}
+
+#[test]
+fn play_test() {
+    // black at d3 flips d4
+    let a = othello::play(&othello::new(), 19);
+
+    assert_eq!(a.cells[19], 1);
+    assert_eq!(a.cells[27], 1);
+    assert_eq!(othello::count(&a), (4, 1));
+    assert_eq!(a.player, -1);
+}
+
+#[test]
+fn flips_test() {
+    // one placement flipping lines in two directions
+    let mut a = othello::new();
+    a.cells = [0; 64];
+    a.cells[0] = 1;
+    a.cells[1] = -1;
+    a.cells[10] = -1;
+    a.cells[18] = 1;
+    // open ended lines do not flip
+    a.cells[11] = -1;
+
+    let mut flipped = othello::flips(&a, 2, 1);
+    flipped.sort();
+    assert_eq!(flipped, [1, 10]);
+    assert_eq!(othello::flips(&a, 9, 1), Vec::<usize>::new());
+    assert_eq!(othello::flips(&a, 0, 1), Vec::<usize>::new());
+}
+
+#[test]
+fn has_move_test() {
+    // agrees with flips on every position of a greedy game
+    let mut a = othello::new();
+    while othello::winner(&a).is_none() {
+        for player in [1, -1] {
+            let any = (0..64).any(|i| !othello::flips(&a, i, player).is_empty());
+            assert_eq!(othello::has_move(&a, player), any);
+        }
+        a = othello::play(&a, othello::greedy_move(&a));
+    }
+    assert!(!othello::has_move(&a, 1) && !othello::has_move(&a, -1));
+}
+
+#[test]
+#[should_panic]
+fn play_illegal_panic_test() {
+    let _ = othello::play(&othello::new(), 0);
+}
+
+#[test]
+fn pass_test() {
+    // white has nothing to flip, black does
+    let mut a = othello::new();
+    a.cells = [0; 64];
+    a.cells[0] = 1;
+    a.cells[1] = -1;
+    a.player = -1;
+
+    assert_eq!(othello::legal_moves(&a), [othello::PASS]);
+    assert!(!othello::has_move(&a, -1));
+    assert!(othello::has_move(&a, 1));
+    assert_eq!(othello::winner(&a), None);
+    let b = othello::play(&a, othello::PASS);
+    assert_eq!(b.player, 1);
+    assert_eq!(b.cells, a.cells);
+    assert_eq!(othello::legal_moves(&b), [2]);
+}
+
+#[test]
+#[should_panic]
+fn pass_illegal_panic_test() {
+    let _ = othello::play(&othello::new(), othello::PASS);
+}
+
+#[test]
+fn winner_test() {
+    // no one can move, black has more discs
+    let mut a = othello::new();
+    a.cells = [0; 64];
+    a.cells[0] = 1;
+    a.cells[1] = 1;
+    a.cells[63] = -1;
+
+    assert!(othello::legal_moves(&a).is_empty());
+    assert_eq!(othello::winner(&a), Some(1));
+}
+
+#[test]
+fn encode_test() {
+    let a = othello::new();
+
+    let planes = othello::encode(&a);
+    assert_eq!(planes.len(), 3);
+    // d5 (row 4, column 3) is black, column major index 3 * 8 + 4
+    assert_eq!(planes[0].value[3 * 8 + 4], 1.0);
+    assert_eq!(planes[1].value[3 * 8 + 3], 1.0);
+    assert_eq!(planes[2].value.iter().sum::<f32>(), 4.0);
+    // legal placement d3 at row 2, column 3
+    assert_eq!(planes[2].value[3 * 8 + 2], 1.0);
+}
+
+#[test]
+fn greedy_move_test() {
+    let a = othello::new();
+    let index = othello::greedy_move(&a);
+
+    assert!(othello::legal_moves(&a).contains(&index));
+}
+
+#[test]
+fn alpha_beta_test() {
+    // a full game ends with every disc accounted for
+    let a = play_game(2, 0);
+    let (black, white) = othello::count(&a);
+    assert!(black + white <= 64);
+
+    // searching ahead with the disc square weights beats the greedy player with either colour
+    assert_eq!(othello::winner(&play_game(3, 0)), Some(1));
+    assert_eq!(othello::winner(&play_game(0, 3)), Some(-1));
+}
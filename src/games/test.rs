@@ -0,0 +1,52 @@
+#[cfg(test)]
+use crate::games;
+#[cfg(test)]
+use crate::games::tic_tac_toe;
+#[cfg(test)]
+use crate::games::Game;
+
+#[cfg(test)]
+fn board(moves: &[usize]) -> tic_tac_toe::Board {
+    let mut a = tic_tac_toe::new();
+    for index in moves {
+        a = a.play(*index);
+    }
+
+    a
+}
+
+#[test]
+fn negamax_test() {
+    // a full search of tic-tac-toe is a draw
+    let value = games::negamax(
+        &tic_tac_toe::new(),
+        9,
+        f32::NEG_INFINITY,
+        f32::INFINITY,
+        &|_: &tic_tac_toe::Board| 0.0,
+    );
+    assert_eq!(value, 0.0);
+
+    // X to move wins on the top row
+    let a = board(&[0, 3, 1, 4]);
+    let value = games::negamax(&a, 1, f32::NEG_INFINITY, f32::INFINITY, &|_| 0.0);
+    assert_eq!(value, games::WIN);
+}
+
+#[test]
+fn best_move_test() {
+    // X takes the win at 2 instead of blocking at 5
+    let a = board(&[0, 3, 1, 4]);
+    assert_eq!(games::best_move(&a, 3, &|_| 0.0), 2);
+
+    // O has to block X at 2
+    let b = board(&[0, 4, 1]);
+    assert_eq!(games::best_move(&b, 2, &|_| 0.0), 2);
+}
+
+#[test]
+#[should_panic]
+fn best_move_game_over_panic_test() {
+    let a = board(&[0, 3, 1, 4, 2]);
+    let _ = games::best_move(&a, 1, &|_| 0.0);
+}
@@ -0,0 +1,121 @@
+pub mod test;
+
+use crate::games::Game;
+use crate::matrix;
+
+// cells are 1 for the first player, -1 for the second and 0 when empty
+// cell index is row * 3 + column
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Board {
+    pub cells: [i8; 9],
+    // player to move, 1 or -1
+    pub player: i8,
+}
+
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+pub fn new() -> Board {
+    Board {
+        cells: [0; 9],
+        player: 1,
+    }
+}
+
+pub fn print(a: &Board) {
+    for row in 0..3 {
+        let mut line = String::new();
+        for column in 0..3 {
+            line.push(match a.cells[row * 3 + column] {
+                1 => 'X',
+                -1 => 'O',
+                _ => '.',
+            });
+        }
+        println!("{}", line);
+    }
+    println!();
+}
+
+pub fn legal_moves(a: &Board) -> Vec<usize> {
+    if winner(a).is_some() {
+        return vec![];
+    }
+
+    (0..9).filter(|i| a.cells[*i] == 0).collect()
+}
+
+pub fn play(a: &Board, index: usize) -> Board {
+    assert!(index < 9, "Move is off the board.");
+    assert!(a.cells[index] == 0, "Cell is already taken.");
+    assert!(winner(a).is_none(), "Game is already over.");
+
+    let mut b = *a;
+    b.cells[index] = a.player;
+    b.player = -a.player;
+
+    b
+}
+
+// Some(1) or Some(-1) for a win, Some(0) for a draw and None while the game goes on
+pub fn winner(a: &Board) -> Option<i8> {
+    for line in LINES.iter() {
+        let first = a.cells[line[0]];
+        if first != 0 && first == a.cells[line[1]] && first == a.cells[line[2]] {
+            return Some(first);
+        }
+    }
+
+    if a.cells.iter().all(|cell| *cell != 0) {
+        return Some(0);
+    }
+
+    None
+}
+
+// 3 planes of 3 x 3 as seen by the player to move: own stones, opponent stones, empty cells
+pub fn encode(a: &Board) -> Vec<matrix::Matrix> {
+    let mut planes = vec![matrix::new(3, 3), matrix::new(3, 3), matrix::new(3, 3)];
+    for row in 0..3 {
+        for column in 0..3 {
+            let cell = a.cells[row * 3 + column];
+            let plane = if cell == a.player {
+                0
+            } else if cell == -a.player {
+                1
+            } else {
+                2
+            };
+            // matrices are column major
+            planes[plane].value[column * 3 + row] = 1.0;
+        }
+    }
+
+    planes
+}
+
+impl Game for Board {
+    fn legal_moves(&self) -> Vec<usize> {
+        legal_moves(self)
+    }
+
+    fn play(&self, index: usize) -> Board {
+        play(self, index)
+    }
+
+    fn winner(&self) -> Option<i8> {
+        winner(self)
+    }
+
+    fn player(&self) -> i8 {
+        self.player
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+use crate::games::tic_tac_toe;
+
+#[test]
+fn new_test() {
+    let a = tic_tac_toe::new();
+
+    assert_eq!(a.cells, [0; 9]);
+    assert_eq!(a.player, 1);
+    assert_eq!(tic_tac_toe::legal_moves(&a), [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(tic_tac_toe::winner(&a), None);
+}
+
+#[test]
+fn print_test() {
+    let a = tic_tac_toe::play(&tic_tac_toe::new(), 4);
+    // pass = does not panic
+    tic_tac_toe::print(&a);
+}
+
+#[test]
+fn play_test() {
+    let a = tic_tac_toe::play(&tic_tac_toe::new(), 4);
+    let b = tic_tac_toe::play(&a, 0);
+
+    assert_eq!(b.cells, [-1, 0, 0, 0, 1, 0, 0, 0, 0]);
+    assert_eq!(b.player, 1);
+    assert_eq!(tic_tac_toe::legal_moves(&b), [1, 2, 3, 5, 6, 7, 8]);
+}
+
+#[test]
+#[should_panic]
+fn play_taken_panic_test() {
+    let a = tic_tac_toe::play(&tic_tac_toe::new(), 4);
+    let _ = tic_tac_toe::play(&a, 4);
+}
+
+#[test]
+fn winner_test() {
+    let mut a = tic_tac_toe::new();
+    for index in [0, 3, 1, 4, 2] {
+        a = tic_tac_toe::play(&a, index);
+    }
+    assert_eq!(tic_tac_toe::winner(&a), Some(1));
+    assert!(tic_tac_toe::legal_moves(&a).is_empty());
+
+    // X O X / X O O / O X X
+    let mut b = tic_tac_toe::new();
+    for index in [0, 1, 2, 4, 3, 5, 7, 6, 8] {
+        b = tic_tac_toe::play(&b, index);
+    }
+    assert_eq!(tic_tac_toe::winner(&b), Some(0));
+}
+
+#[test]
+fn encode_test() {
+    let a = tic_tac_toe::play(&tic_tac_toe::new(), 1);
+
+    // O to move, X is the opponent
+    let planes = tic_tac_toe::encode(&a);
+    assert_eq!(planes.len(), 3);
+    assert_eq!(planes[0].value, [0.0; 9]);
+    assert_eq!(
+        planes[1].value,
+        [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0]
+    );
+    assert_eq!(
+        planes[2].value,
+        [1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0]
+    );
+}
@@ -1,31 +1,22 @@
+pub mod cli;
+pub mod config;
+pub mod environments;
+pub mod evolution;
+pub mod games;
 pub mod matrix;
 pub mod models;
 pub mod nn;
+pub mod optim;
+pub mod play;
+pub mod plot;
 
-use crate::models::tic_tac_toe;
-use std::time::Instant;
+use std::env;
+use std::process;
 
 fn main() {
-    let mut input: Vec<matrix::Matrix> = Vec::new();
-    for _ in 0..3 {
-        input.push(matrix::new_gaussian_noise(3, 3));
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(e) = cli::run(&args) {
+        eprintln!("error: {}\n\n{}", e, cli::USAGE);
+        process::exit(1);
     }
-
-    let mut ttt = tic_tac_toe::new_gaussian_noise();
-
-    let now = Instant::now();
-    let mut output = tic_tac_toe::feedforward(&ttt, &input);
-    println!("{}", now.elapsed().as_micros() as f64 / 1000000.0);
-
-    matrix::print(&output);
-
-    let mut a = tic_tac_toe::new_gaussian_noise();
-    a = tic_tac_toe::scalar(&a, 0.1);
-    ttt = tic_tac_toe::add(&ttt, &a);
-
-    output = tic_tac_toe::feedforward(&ttt, &input);
-
-    matrix::print(&output);
-
-    println!("{}", now.elapsed().as_micros() as f64 / 1000000.0);
 }
@@ -116,6 +116,62 @@ pub fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
     c
 }
 
+// c = a * b, returns (dL/da, dL/db) from dL/dc
+pub fn multiply_backward(a: &Matrix, b: &Matrix, grad: &Matrix) -> (Matrix, Matrix) {
+    assert!(
+        grad.rows == a.rows && grad.columns == b.columns,
+        "Gradient size is incorrect."
+    );
+
+    (multiply(grad, &transpose(b)), multiply(&transpose(a), grad))
+}
+
+pub fn transpose(a: &Matrix) -> Matrix {
+    let mut b = new(a.columns, a.rows);
+
+    for i in 0..a.rows {
+        for j in 0..a.columns {
+            b.value[i * b.rows + j] = a.value[j * a.rows + i];
+        }
+    }
+
+    b
+}
+
+pub fn identity(n: usize) -> Matrix {
+    let mut a = new(n, n);
+    for i in 0..n {
+        a.value[i * n + i] = 1.0;
+    }
+
+    a
+}
+
+// e^a of a square matrix, taylor series after scaling a down then squaring back up
+pub fn exponential(a: &Matrix) -> Matrix {
+    assert!(a.rows == a.columns, "Matrix must be square.");
+
+    let norm = a.value.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let mut squarings = 0;
+    while norm / 2_f32.powi(squarings) > 0.5 {
+        squarings += 1;
+    }
+
+    let scaled = scalar(a, 1.0 / 2_f32.powi(squarings));
+    let mut b = identity(a.rows);
+    let mut term = identity(a.rows);
+    for i in 1..12 {
+        term = scalar(&multiply(&term, &scaled), 1.0 / i as f32);
+        b = add(&b, &term);
+    }
+
+    for _ in 0..squarings {
+        b = multiply(&b, &b);
+    }
+
+    b
+}
+
 pub fn add(a: &Matrix, b: &Matrix) -> Matrix {
     assert!(a.rows == b.rows, "Matrix sizes are incorrect.");
     assert!(a.columns == b.columns, "Matrix sizes are incorrect.");
@@ -129,6 +185,35 @@ pub fn add(a: &Matrix, b: &Matrix) -> Matrix {
     c
 }
 
+// c = a + b, returns (dL/da, dL/db) from dL/dc
+pub fn add_backward(grad: &Matrix) -> (Matrix, Matrix) {
+    (grad.clone(), grad.clone())
+}
+
+// c = a + b where the 1 x columns row b is added to every row of a, e.g. a bias over a batch
+pub fn add_row(a: &Matrix, b: &Matrix) -> Matrix {
+    assert!(b.rows == 1, "Matrix sizes are incorrect.");
+    assert!(a.columns == b.columns, "Matrix sizes are incorrect.");
+
+    let mut c = a.clone();
+    for i in 0..c.value.len() {
+        c.value[i] += b.value[i / a.rows];
+    }
+
+    c
+}
+
+// c = add_row(a, b), returns (dL/da, dL/db) from dL/dc
+// b was added to every row so its gradient sums over the rows
+pub fn add_row_backward(grad: &Matrix) -> (Matrix, Matrix) {
+    let mut grad_b = new(1, grad.columns);
+    for i in 0..grad.value.len() {
+        grad_b.value[i / grad.rows] += grad.value[i];
+    }
+
+    (grad.clone(), grad_b)
+}
+
 pub fn scalar(a: &Matrix, s: f32) -> Matrix {
     let mut b = a.clone();
 
@@ -154,6 +239,31 @@ pub fn element_wise_add(a: &Matrix, e: f32) -> Matrix {
     b
 }
 
+pub fn element_wise_multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    assert!(a.rows == b.rows, "Matrix sizes are incorrect.");
+    assert!(a.columns == b.columns, "Matrix sizes are incorrect.");
+
+    let mut c = new(a.rows, a.columns);
+
+    for i in 0..c.value.len() {
+        c.value[i] = a.value[i] * b.value[i];
+    }
+
+    c
+}
+
+// returns columns start..end, columns are contiguous since values are column major
+pub fn slice_columns(a: &Matrix, start: usize, end: usize) -> Matrix {
+    assert!(start < end, "Column range is empty.");
+    assert!(end <= a.columns, "Column range is out of bounds.");
+
+    let mut b = new(a.rows, end - start);
+    b.value
+        .copy_from_slice(&a.value[start * a.rows..end * a.rows]);
+
+    b
+}
+
 pub fn mean(a: &Matrix) -> f32 {
     let mut sum: f32 = 0.0;
     for i in 0..a.rows * a.columns {
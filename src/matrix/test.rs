@@ -51,6 +51,124 @@ fn multiply_panic_test() {
     let _ = matrix::multiply(&b, &a);
 }
 
+#[test]
+fn multiply_backward_test() {
+    let mut a = matrix::new(2, 3);
+    let mut b = matrix::new(3, 2);
+    let mut grad = matrix::new(2, 2);
+    a.value = vec![4.0, 6.0, 1.0, 9.0, 7.0, 3.0];
+    b.value = vec![2.0, 8.0, 3.0, 3.0, 2.0, 8.0];
+    grad.value = vec![1.0, 2.0, -1.0, 0.5];
+
+    let (grad_a, grad_b) = matrix::multiply_backward(&a, &b, &grad);
+    assert_eq!(grad_a.rows, 2);
+    assert_eq!(grad_a.columns, 3);
+    assert_eq!(grad_b.rows, 3);
+    assert_eq!(grad_b.columns, 2);
+
+    // dL/da = grad * b^T, dL/db = a^T * grad
+    assert_eq!(grad_a.value, [-1.0, 5.5, 6.0, 17.0, -5.0, 10.0]);
+    assert_eq!(grad_b.value, [16.0, 19.0, 13.0, -1.0, 3.5, -5.5]);
+}
+
+#[test]
+#[should_panic]
+fn multiply_backward_panic_test() {
+    let a = matrix::new(2, 3);
+    let b = matrix::new(3, 2);
+    let grad = matrix::new(3, 3);
+
+    let _ = matrix::multiply_backward(&a, &b, &grad);
+}
+
+#[test]
+fn transpose_test() {
+    let mut a = matrix::new(2, 3);
+    a.value = vec![4.0, 6.0, 1.0, 9.0, 7.0, 3.0];
+
+    let b = matrix::transpose(&a);
+    assert_eq!(b.rows, 3);
+    assert_eq!(b.columns, 2);
+    assert_eq!(b.value, [4.0, 1.0, 7.0, 6.0, 9.0, 3.0]);
+}
+
+#[test]
+fn add_backward_test() {
+    let mut grad = matrix::new(1, 3);
+    grad.value = vec![1.0, -2.0, 3.0];
+
+    let (grad_a, grad_b) = matrix::add_backward(&grad);
+    assert_eq!(grad_a.value, [1.0, -2.0, 3.0]);
+    assert_eq!(grad_b.value, [1.0, -2.0, 3.0]);
+}
+
+#[test]
+fn add_row_test() {
+    let mut a = matrix::new(2, 3);
+    a.value = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let mut b = matrix::new(1, 3);
+    b.value = vec![10.0, 20.0, 30.0];
+
+    // column major, every row gets b
+    let c = matrix::add_row(&a, &b);
+    assert_eq!(c.value, [11.0, 12.0, 23.0, 24.0, 35.0, 36.0]);
+    assert_eq!(matrix::add_row(&b, &b).value, matrix::add(&b, &b).value);
+}
+
+#[test]
+#[should_panic]
+fn add_row_panic_test() {
+    let _ = matrix::add_row(&matrix::new(2, 3), &matrix::new(2, 3));
+}
+
+#[test]
+fn add_row_backward_test() {
+    let mut grad = matrix::new(2, 3);
+    grad.value = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+
+    let (grad_a, grad_b) = matrix::add_row_backward(&grad);
+    assert_eq!(grad_a.value, grad.value);
+    assert_eq!((grad_b.rows, grad_b.columns), (1, 3));
+    assert_eq!(grad_b.value, [3.0, 7.0, 11.0]);
+}
+
+#[test]
+fn identity_test() {
+    let a = matrix::identity(2);
+
+    assert_eq!(a.rows, 2);
+    assert_eq!(a.columns, 2);
+    assert_eq!(a.value, [1.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn exponential_test() {
+    // diagonal matrices exponentiate element wise
+    let mut a = matrix::new(2, 2);
+    a.value = vec![1.0, 0.0, 0.0, -2.0];
+
+    let b = matrix::exponential(&a);
+    assert!((b.value[0] - 1_f32.exp()).abs() < 1e-4);
+    assert!(b.value[1].abs() < 1e-6);
+    assert!(b.value[2].abs() < 1e-6);
+    assert!((b.value[3] - (-2_f32).exp()).abs() < 1e-4);
+
+    // rotation generator gives a rotation matrix
+    a.value = vec![0.0, 0.5, -0.5, 0.0];
+    let c = matrix::exponential(&a);
+    let expected = [0.5_f32.cos(), 0.5_f32.sin(), -0.5_f32.sin(), 0.5_f32.cos()];
+    for (value, e) in c.value.iter().zip(expected.iter()) {
+        assert!((value - e).abs() < 1e-5);
+    }
+}
+
+#[test]
+#[should_panic]
+fn exponential_panic_test() {
+    let a = matrix::new(2, 3);
+    let _ = matrix::exponential(&a);
+}
+
 #[test]
 fn add_test() {
     let mut a = matrix::new(2, 3);
@@ -108,6 +226,47 @@ fn element_wise_add_test() {
     assert_eq!(b.value, [9.0, 11.0, 6.0, 14.0, 12.0, 8.0]);
 }
 
+#[test]
+fn element_wise_multiply_test() {
+    let mut a = matrix::new(2, 3);
+    let mut b = matrix::new(2, 3);
+    a.value = vec![4.0, 6.0, 1.0, 9.0, 7.0, 3.0];
+    b.value = vec![2.0, 8.0, 3.0, -3.0, 2.0, 0.5];
+
+    let c = matrix::element_wise_multiply(&a, &b);
+    assert_eq!(c.rows, 2);
+    assert_eq!(c.columns, 3);
+    assert_eq!(c.value, [8.0, 48.0, 3.0, -27.0, 14.0, 1.5]);
+}
+
+#[test]
+#[should_panic]
+fn element_wise_multiply_panic_test() {
+    let a = matrix::new(2, 3);
+    let b = matrix::new(3, 2);
+
+    let _ = matrix::element_wise_multiply(&a, &b);
+}
+
+#[test]
+fn slice_columns_test() {
+    let mut a = matrix::new(2, 3);
+    a.value = vec![4.0, 6.0, 1.0, 9.0, 7.0, 3.0];
+
+    let b = matrix::slice_columns(&a, 1, 3);
+    assert_eq!(b.rows, 2);
+    assert_eq!(b.columns, 2);
+    assert_eq!(b.value, [1.0, 9.0, 7.0, 3.0]);
+}
+
+#[test]
+#[should_panic]
+fn slice_columns_panic_test() {
+    let a = matrix::new(2, 3);
+
+    let _ = matrix::slice_columns(&a, 2, 4);
+}
+
 #[test]
 fn mean_test() {
     let mut a = matrix::new(2, 2);
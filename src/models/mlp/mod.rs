@@ -0,0 +1,217 @@
+use crate::matrix;
+use crate::models::Model;
+use crate::nn::activations::Activation;
+use crate::nn::layers::batch_norm;
+use crate::nn::layers::fully_connected;
+use crate::nn::layers::layer_norm;
+use std::fs;
+
+// normalization of the output of a layer
+#[derive(Clone)]
+pub enum Norm {
+    Layer(layer_norm::LayerNorm),
+    Batch(batch_norm::BatchNorm),
+}
+
+// fully connected layers with any activation, the architecture comes from an experiment config
+// norms[i] is applied to the output of layers[i]
+#[derive(Clone)]
+pub struct Mlp {
+    pub layers: Vec<fully_connected::FullyConnected<Activation>>,
+    pub norms: Vec<Option<Norm>>,
+}
+
+// layer_norm or batch_norm over features outputs
+pub fn norm_from_name(name: &str, features: usize) -> Option<Norm> {
+    match name {
+        "layer_norm" => Some(Norm::Layer(layer_norm::new(features))),
+        "batch_norm" => Some(Norm::Batch(batch_norm::new(features))),
+        _ => None,
+    }
+}
+
+// layers are (outputs, activation) in order, an empty hidden list is a linear policy
+pub fn new(inputs: usize, layers: &[(usize, Activation)]) -> Mlp {
+    assert!(!layers.is_empty(), "Mlp needs at least one layer.");
+
+    let mut b = Mlp {
+        layers: vec![],
+        norms: vec![],
+    };
+    let mut size = inputs;
+    for (outputs, activation) in layers {
+        b.layers
+            .push(fully_connected::new(size, *outputs, activation.clone()));
+        b.norms.push(None);
+        size = *outputs;
+    }
+
+    b
+}
+
+fn norm_feedforward(norm: &Option<Norm>, input: &matrix::Matrix) -> matrix::Matrix {
+    match norm {
+        Some(Norm::Layer(n)) => layer_norm::feedforward(n, input),
+        Some(Norm::Batch(n)) => batch_norm::feedforward(n, input),
+        None => input.clone(),
+    }
+}
+
+pub fn feedforward(a: &Mlp, input: &matrix::Matrix) -> matrix::Matrix {
+    let mut output = input.clone();
+    for (layer, norm) in a.layers.iter().zip(a.norms.iter()) {
+        output = fully_connected::feedforward(layer, &output);
+        output = norm_feedforward(norm, &output);
+    }
+
+    output
+}
+
+// inputs are 1 x inputs rows, e.g. observations of the environment, every batch norm gets the
+// statistics of its own input with the layers before it already normalized
+pub fn collect_statistics(a: &Mlp, inputs: &[matrix::Matrix]) -> Mlp {
+    let mut b = a.clone();
+    let mut outputs: Vec<matrix::Matrix> = inputs.to_vec();
+    for i in 0..b.layers.len() {
+        outputs = outputs
+            .iter()
+            .map(|output| fully_connected::feedforward(&b.layers[i], output))
+            .collect();
+        if let Some(Norm::Batch(n)) = &b.norms[i] {
+            b.norms[i] = Some(Norm::Batch(batch_norm::collect_statistics(n, &outputs)));
+        }
+        outputs = outputs
+            .iter()
+            .map(|output| norm_feedforward(&b.norms[i], output))
+            .collect();
+    }
+
+    b
+}
+
+pub fn add(a: &Mlp, b: &Mlp) -> Mlp {
+    let mut c = a.clone();
+    for (layer, other) in c.layers.iter_mut().zip(b.layers.iter()) {
+        *layer = fully_connected::add(layer, other);
+    }
+    for (norm, other) in c.norms.iter_mut().zip(b.norms.iter()) {
+        *norm = match (&*norm, other) {
+            (Some(Norm::Layer(n)), Some(Norm::Layer(o))) => {
+                Some(Norm::Layer(layer_norm::add(n, o)))
+            }
+            (Some(Norm::Batch(n)), Some(Norm::Batch(o))) => {
+                Some(Norm::Batch(batch_norm::add(n, o)))
+            }
+            (None, None) => None,
+            _ => panic!("Mlp norms do not match."),
+        };
+    }
+
+    c
+}
+
+pub fn scalar(a: &Mlp, s: f32) -> Mlp {
+    let mut b = a.clone();
+    for layer in b.layers.iter_mut() {
+        *layer = fully_connected::scalar(layer, s);
+    }
+    for norm in b.norms.iter_mut() {
+        *norm = match &*norm {
+            Some(Norm::Layer(n)) => Some(Norm::Layer(layer_norm::scalar(n, s))),
+            Some(Norm::Batch(n)) => Some(Norm::Batch(batch_norm::scalar(n, s))),
+            None => None,
+        };
+    }
+
+    b
+}
+
+pub fn save(a: &Mlp, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    for (i, layer) in a.layers.iter().enumerate() {
+        fully_connected::save(
+            layer,
+            (dir_name.to_owned() + "/layers" + &i.to_string()).as_str(),
+        );
+    }
+    for (i, norm) in a.norms.iter().enumerate() {
+        let norm_dir = dir_name.to_owned() + "/norms" + &i.to_string();
+        match norm {
+            Some(Norm::Layer(n)) => layer_norm::save(n, &norm_dir),
+            Some(Norm::Batch(n)) => batch_norm::save(n, &norm_dir),
+            None => {}
+        }
+    }
+}
+
+// a gives the architecture
+pub fn load(a: &Mlp, dir_name: &str) -> Mlp {
+    let mut b = a.clone();
+    for (i, layer) in b.layers.iter_mut().enumerate() {
+        *layer = fully_connected::load(
+            layer,
+            (dir_name.to_owned() + "/layers" + &i.to_string()).as_str(),
+        );
+    }
+    for (i, norm) in b.norms.iter_mut().enumerate() {
+        let norm_dir = dir_name.to_owned() + "/norms" + &i.to_string();
+        *norm = match &*norm {
+            Some(Norm::Layer(n)) => Some(Norm::Layer(layer_norm::load(n, &norm_dir))),
+            Some(Norm::Batch(n)) => Some(Norm::Batch(batch_norm::load(n, &norm_dir))),
+            None => None,
+        };
+    }
+
+    b
+}
+
+impl Model for Mlp {
+    fn add(&self, b: &Mlp) -> Mlp {
+        add(self, b)
+    }
+
+    fn scalar(&self, s: f32) -> Mlp {
+        scalar(self, s)
+    }
+
+    fn parameters(&self) -> Vec<(String, &matrix::Matrix)> {
+        let mut b: Vec<(String, &matrix::Matrix)> = Vec::new();
+        for (i, (layer, norm)) in self.layers.iter().zip(self.norms.iter()).enumerate() {
+            for (name, m) in fully_connected::parameters(layer) {
+                b.push(("layers".to_owned() + &i.to_string() + "." + name, m));
+            }
+            let norm_parameters = match norm {
+                Some(Norm::Layer(n)) => layer_norm::parameters(n),
+                Some(Norm::Batch(n)) => batch_norm::parameters(n),
+                None => vec![],
+            };
+            for (name, m) in norm_parameters {
+                b.push(("norms".to_owned() + &i.to_string() + "." + name, m));
+            }
+        }
+
+        b
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut matrix::Matrix> {
+        let mut b = Vec::new();
+        for (layer, norm) in self.layers.iter_mut().zip(self.norms.iter_mut()) {
+            b.append(&mut fully_connected::parameters_mut(layer));
+            match norm {
+                Some(Norm::Layer(n)) => b.append(&mut layer_norm::parameters_mut(n)),
+                Some(Norm::Batch(n)) => b.append(&mut batch_norm::parameters_mut(n)),
+                None => {}
+            }
+        }
+
+        b
+    }
+
+    fn save(&self, dir_name: &str) {
+        save(self, dir_name);
+    }
+
+    fn load(&self, dir_name: &str) -> Mlp {
+        load(self, dir_name)
+    }
+}
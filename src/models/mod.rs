@@ -1,2 +1,105 @@
+pub mod connect_four;
+pub mod mlp;
+pub mod othello;
+pub mod test;
 pub mod tic_tac_toe;
 pub mod xor;
+
+use crate::matrix;
+use crate::nn::activations::Activate;
+use crate::nn::layers::fully_connected;
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+// lets optimizers work on any model without knowing its layers
+// parameters and parameters_mut must list the matrices in the same order
+pub trait Model: Clone {
+    fn add(&self, b: &Self) -> Self;
+    fn scalar(&self, s: f32) -> Self;
+    fn parameters(&self) -> Vec<(String, &matrix::Matrix)>;
+    fn parameters_mut(&mut self) -> Vec<&mut matrix::Matrix>;
+    fn save(&self, dir_name: &str);
+    fn load(&self, dir_name: &str) -> Self;
+}
+
+// a single layer is enough for linear policies, e.g. on the classic control environments
+impl<T: Activate + Clone> Model for fully_connected::FullyConnected<T> {
+    fn add(&self, b: &Self) -> Self {
+        fully_connected::add(self, b)
+    }
+
+    fn scalar(&self, s: f32) -> Self {
+        fully_connected::scalar(self, s)
+    }
+
+    fn parameters(&self) -> Vec<(String, &matrix::Matrix)> {
+        fully_connected::parameters(self)
+            .into_iter()
+            .map(|(name, m)| (name.to_string(), m))
+            .collect()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut matrix::Matrix> {
+        fully_connected::parameters_mut(self)
+    }
+
+    fn save(&self, dir_name: &str) {
+        fully_connected::save(self, dir_name);
+    }
+
+    fn load(&self, dir_name: &str) -> Self {
+        fully_connected::load(self, dir_name)
+    }
+}
+
+// model shaped gaussian noise, drawn from rng so a seeded run can be repeated
+pub fn gaussian_noise<M: Model, R: Rng>(a: &M, rng: &mut R) -> M {
+    let mut b = a.clone();
+    for m in b.parameters_mut() {
+        for value in m.value.iter_mut() {
+            *value = rng.sample(StandardNormal);
+        }
+    }
+
+    b
+}
+
+pub fn num_parameters<M: Model>(a: &M) -> usize {
+    a.parameters().iter().map(|(_, m)| m.value.len()).sum()
+}
+
+// every parameter in a single vector, in the order of Model::parameters
+pub fn flatten<M: Model>(a: &M) -> Vec<f32> {
+    let mut b: Vec<f32> = Vec::with_capacity(num_parameters(a));
+    for (_, m) in a.parameters() {
+        b.extend_from_slice(&m.value);
+    }
+
+    b
+}
+
+// inverse of flatten, a gives the shape of the model
+pub fn unflatten<M: Model>(a: &M, values: &[f32]) -> M {
+    assert!(
+        values.len() == num_parameters(a),
+        "Number of values and number of parameters must match."
+    );
+
+    let mut b = a.clone();
+    let mut start = 0;
+    for m in b.parameters_mut() {
+        let end = start + m.value.len();
+        m.value.copy_from_slice(&values[start..end]);
+        start = end;
+    }
+
+    b
+}
+
+// mean of every parameter matrix by name, e.g. to compare step sizes between layers
+pub fn parameter_means<M: Model>(a: &M) -> Vec<(String, f32)> {
+    a.parameters()
+        .into_iter()
+        .map(|(name, m)| (name, matrix::mean(m)))
+        .collect()
+}
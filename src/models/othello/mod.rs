@@ -0,0 +1,230 @@
+use crate::games::othello;
+use crate::matrix;
+use crate::models::Model;
+use crate::nn::activations::no_activation;
+use crate::nn::activations::param_relu;
+use crate::nn::layers::conv2d;
+use crate::nn::layers::fully_connected;
+use crate::nn::layers::res2d;
+use std::fs;
+
+// same layout as ConnectFour, input is the 3 x 8 x 8 plane encoding
+// and output is one score per square
+#[derive(Clone)]
+pub struct Othello {
+    pub conv1: conv2d::Conv2D<param_relu::ParamRelu>,
+    pub res1: res2d::Res2D<param_relu::ParamRelu>,
+    pub res2: res2d::Res2D<param_relu::ParamRelu>,
+    pub fc1: fully_connected::FullyConnected<param_relu::ParamRelu>,
+    pub fc2: fully_connected::FullyConnected<param_relu::ParamRelu>,
+}
+
+const CHANNELS: usize = 32;
+
+fn new_res(gaussian_noise: bool) -> res2d::Res2D<param_relu::ParamRelu> {
+    let (layer, last_layer) = if gaussian_noise {
+        (
+            conv2d::new_gaussian_noise(CHANNELS, CHANNELS, (3, 3), param_relu::new(1.0, 0.001)),
+            conv2d::new_gaussian_noise(CHANNELS, CHANNELS, (3, 3), no_activation::new()),
+        )
+    } else {
+        (
+            conv2d::new(CHANNELS, CHANNELS, (3, 3), param_relu::new(1.0, 0.001)),
+            conv2d::new(CHANNELS, CHANNELS, (3, 3), no_activation::new()),
+        )
+    };
+
+    res2d::new(
+        &[layer],
+        &last_layer,
+        &[(1, 1), (1, 1)],
+        &[(1, 1, 1, 1), (1, 1, 1, 1)],
+        param_relu::new(1.0, 0.001),
+    )
+}
+
+pub fn new() -> Othello {
+    Othello {
+        conv1: conv2d::new(3, CHANNELS, (3, 3), param_relu::new(1.0, 0.001)),
+        res1: new_res(false),
+        res2: new_res(false),
+        fc1: fully_connected::new(
+            CHANNELS * othello::SIZE * othello::SIZE,
+            64,
+            param_relu::new(0.25, 0.001),
+        ),
+        fc2: fully_connected::new(
+            64,
+            othello::SIZE * othello::SIZE,
+            param_relu::new(0.25, 0.001),
+        ),
+    }
+}
+
+pub fn new_gaussian_noise() -> Othello {
+    Othello {
+        conv1: conv2d::new_gaussian_noise(3, CHANNELS, (3, 3), param_relu::new(1.0, 0.001)),
+        res1: new_res(true),
+        res2: new_res(true),
+        fc1: fully_connected::new_gaussian_noise(
+            CHANNELS * othello::SIZE * othello::SIZE,
+            64,
+            param_relu::new(0.25, 0.001),
+        ),
+        fc2: fully_connected::new_gaussian_noise(
+            64,
+            othello::SIZE * othello::SIZE,
+            param_relu::new(0.25, 0.001),
+        ),
+    }
+}
+
+pub fn print(a: &Othello) {
+    println!("Othello");
+    conv2d::print(&a.conv1);
+    res2d::print(&a.res1);
+    res2d::print(&a.res2);
+    fully_connected::print(&a.fc1);
+    fully_connected::print(&a.fc2);
+}
+
+pub fn feedforward(a: &Othello, input: &[matrix::Matrix]) -> matrix::Matrix {
+    let mut conv_output = conv2d::feedforward(&a.conv1, input, (1, 1), (1, 1, 1, 1));
+    conv_output = res2d::feedforward(&a.res1, &conv_output);
+    conv_output = res2d::feedforward(&a.res2, &conv_output);
+
+    // flatten matrix
+    let mut output = matrix::new(1, CHANNELS * othello::SIZE * othello::SIZE);
+    output.value = vec![];
+    for conv_output_matrix in conv_output.iter() {
+        output.value.extend_from_slice(&conv_output_matrix.value);
+    }
+
+    output = fully_connected::feedforward(&a.fc1, &output);
+    output = fully_connected::feedforward(&a.fc2, &output);
+
+    output
+}
+
+// channel outputs of the conv layer and both residual blocks for input, named by layer
+pub fn feature_maps(a: &Othello, input: &[matrix::Matrix]) -> Vec<(String, Vec<matrix::Matrix>)> {
+    let conv1 = conv2d::feedforward(&a.conv1, input, (1, 1), (1, 1, 1, 1));
+    let res1 = res2d::feedforward(&a.res1, &conv1);
+    let res2 = res2d::feedforward(&a.res2, &res1);
+
+    vec![
+        ("conv1".to_string(), conv1),
+        ("res1".to_string(), res1),
+        ("res2".to_string(), res2),
+    ]
+}
+
+// highest scoring legal square for the player to move, passes when it has to
+pub fn choose_move(a: &Othello, board: &othello::Board) -> usize {
+    let moves = othello::legal_moves(board);
+    assert!(!moves.is_empty(), "Game is already over.");
+    if moves == [othello::PASS] {
+        return othello::PASS;
+    }
+
+    let output = feedforward(a, &othello::encode(board));
+    let mut best = moves[0];
+    for index in moves {
+        if output.value[index] > output.value[best] {
+            best = index;
+        }
+    }
+
+    best
+}
+
+pub fn add(a: &Othello, b: &Othello) -> Othello {
+    let mut c = a.clone();
+    c.conv1 = conv2d::add(&a.conv1, &b.conv1);
+    c.res1 = res2d::add(&a.res1, &b.res1);
+    c.res2 = res2d::add(&a.res2, &b.res2);
+    c.fc1 = fully_connected::add(&a.fc1, &b.fc1);
+    c.fc2 = fully_connected::add(&a.fc2, &b.fc2);
+
+    c
+}
+
+pub fn scalar(a: &Othello, s: f32) -> Othello {
+    let mut b = a.clone();
+    b.conv1 = conv2d::scalar(&a.conv1, s);
+    b.res1 = res2d::scalar(&a.res1, s);
+    b.res2 = res2d::scalar(&a.res2, s);
+    b.fc1 = fully_connected::scalar(&a.fc1, s);
+    b.fc2 = fully_connected::scalar(&a.fc2, s);
+
+    b
+}
+
+pub fn save(a: &Othello, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    conv2d::save(&a.conv1, (dir_name.to_owned() + "/conv1").as_str());
+    res2d::save(&a.res1, (dir_name.to_owned() + "/res1").as_str());
+    res2d::save(&a.res2, (dir_name.to_owned() + "/res2").as_str());
+    fully_connected::save(&a.fc1, (dir_name.to_owned() + "/fc1").as_str());
+    fully_connected::save(&a.fc2, (dir_name.to_owned() + "/fc2").as_str());
+}
+
+pub fn load(dir_name: &str) -> Othello {
+    let mut a = new();
+    a.conv1 = conv2d::load(&a.conv1, (dir_name.to_owned() + "/conv1").as_str());
+    a.res1 = res2d::load(&a.res1, (dir_name.to_owned() + "/res1").as_str());
+    a.res2 = res2d::load(&a.res2, (dir_name.to_owned() + "/res2").as_str());
+    a.fc1 = fully_connected::load(&a.fc1, (dir_name.to_owned() + "/fc1").as_str());
+    a.fc2 = fully_connected::load(&a.fc2, (dir_name.to_owned() + "/fc2").as_str());
+
+    a
+}
+
+impl Model for Othello {
+    fn add(&self, b: &Othello) -> Othello {
+        add(self, b)
+    }
+
+    fn scalar(&self, s: f32) -> Othello {
+        scalar(self, s)
+    }
+
+    fn parameters(&self) -> Vec<(String, &matrix::Matrix)> {
+        let mut b: Vec<(String, &matrix::Matrix)> = Vec::new();
+        for (name, m) in conv2d::parameters(&self.conv1) {
+            b.push(("conv1.".to_owned() + name, m));
+        }
+        for (name, m) in res2d::parameters(&self.res1) {
+            b.push(("res1.".to_owned() + &name, m));
+        }
+        for (name, m) in res2d::parameters(&self.res2) {
+            b.push(("res2.".to_owned() + &name, m));
+        }
+        for (name, m) in fully_connected::parameters(&self.fc1) {
+            b.push(("fc1.".to_owned() + name, m));
+        }
+        for (name, m) in fully_connected::parameters(&self.fc2) {
+            b.push(("fc2.".to_owned() + name, m));
+        }
+
+        b
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut matrix::Matrix> {
+        let mut b = conv2d::parameters_mut(&mut self.conv1);
+        b.append(&mut res2d::parameters_mut(&mut self.res1));
+        b.append(&mut res2d::parameters_mut(&mut self.res2));
+        b.append(&mut fully_connected::parameters_mut(&mut self.fc1));
+        b.append(&mut fully_connected::parameters_mut(&mut self.fc2));
+
+        b
+    }
+
+    fn save(&self, dir_name: &str) {
+        save(self, dir_name);
+    }
+
+    fn load(&self, dir_name: &str) -> Othello {
+        load(dir_name)
+    }
+}
@@ -0,0 +1,304 @@
+#[cfg(test)]
+use crate::games;
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::models;
+#[cfg(test)]
+use crate::models::connect_four;
+#[cfg(test)]
+use crate::models::mlp;
+#[cfg(test)]
+use crate::models::othello;
+#[cfg(test)]
+use crate::models::tic_tac_toe;
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use crate::nn::activations;
+#[cfg(test)]
+use crate::nn::activations::no_activation;
+#[cfg(test)]
+use crate::nn::layers::conv2d;
+#[cfg(test)]
+use crate::nn::layers::fully_connected;
+#[cfg(test)]
+use rand::SeedableRng;
+#[cfg(test)]
+use rand_chacha::ChaCha8Rng;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn gaussian_noise_test() {
+    let model = xor::new();
+
+    let a = models::gaussian_noise(&model, &mut ChaCha8Rng::seed_from_u64(1));
+    let b = models::gaussian_noise(&model, &mut ChaCha8Rng::seed_from_u64(1));
+    let c = models::gaussian_noise(&model, &mut ChaCha8Rng::seed_from_u64(2));
+
+    for (i, (name, m)) in a.parameters().into_iter().enumerate() {
+        assert_eq!(name, model.parameters()[i].0);
+        assert!(m.value.iter().any(|v| *v != 0.0));
+        assert_eq!(m.value, b.parameters()[i].1.value);
+        assert_ne!(m.value, c.parameters()[i].1.value);
+    }
+}
+
+#[test]
+fn num_parameters_test() {
+    // 2 * 10 + 10 + 10 * 2 + 2
+    assert_eq!(models::num_parameters(&xor::new()), 52);
+}
+
+#[test]
+fn flatten_test() {
+    let model = xor::new_gaussian_noise();
+
+    let values = models::flatten(&model);
+    assert_eq!(values.len(), 52);
+    assert_eq!(values[0..20], model.fc1.weights.value[..]);
+    assert_eq!(values[20..30], model.fc1.bias.value[..]);
+    assert_eq!(values[30..50], model.fc2.weights.value[..]);
+    assert_eq!(values[50..52], model.fc2.bias.value[..]);
+
+    let b = models::unflatten(&xor::new(), &values);
+    assert_eq!(models::flatten(&b), values);
+}
+
+#[test]
+#[should_panic]
+fn unflatten_length_panic_test() {
+    let _ = models::unflatten(&xor::new(), &[1.0, 2.0]);
+}
+
+#[test]
+fn parameter_means_test() {
+    let mut model = xor::new();
+    model.fc2.bias.value = vec![1.0, 3.0];
+
+    let means = models::parameter_means(&model);
+    assert_eq!(means.len(), 4);
+    assert_eq!(means[0], ("fc1.weights".to_string(), 0.0));
+    assert_eq!(means[3], ("fc2.bias".to_string(), 2.0));
+}
+
+#[test]
+fn fully_connected_model_test() {
+    let a = fully_connected::new_gaussian_noise(4, 2, no_activation::new());
+
+    let names: Vec<String> = a.parameters().into_iter().map(|(n, _)| n).collect();
+    assert_eq!(names, ["weights", "bias"]);
+    assert_eq!(models::num_parameters(&a), 10);
+
+    a.save("fully_connected_model_test");
+    let b = a.load("fully_connected_model_test");
+    fs::remove_dir_all("fully_connected_model_test").unwrap();
+    assert_eq!(models::flatten(&b), models::flatten(&a));
+}
+
+#[test]
+fn connect_four_test() {
+    let model = connect_four::new_gaussian_noise().scalar(0.1);
+    let board = games::connect_four::new();
+
+    let output = connect_four::feedforward(&model, &games::connect_four::encode(&board));
+    assert_eq!(output.rows, 1);
+    assert_eq!(output.columns, 7);
+    assert!(connect_four::choose_move(&model, &board) < 7);
+
+    let maps = connect_four::feature_maps(&model, &games::connect_four::encode(&board));
+    assert_eq!(maps.len(), 3);
+    assert_eq!(maps[2].0, "res2");
+    assert_eq!(maps[2].1.len(), model.conv1.num_filters);
+    assert_eq!((maps[2].1[0].rows, maps[2].1[0].columns), (6, 7));
+
+    model.save("connect_four_test");
+    let b = connect_four::load("connect_four_test");
+    fs::remove_dir_all("connect_four_test").unwrap();
+    assert_eq!(models::flatten(&b), models::flatten(&model));
+    assert_eq!(b.parameters()[2].0, "res1.layers0.filters");
+}
+
+#[test]
+fn othello_test() {
+    let model = othello::new_gaussian_noise().scalar(0.1);
+    let board = games::othello::new();
+
+    let output = othello::feedforward(&model, &games::othello::encode(&board));
+    assert_eq!(output.columns, 64);
+    let maps = othello::feature_maps(&model, &games::othello::encode(&board));
+    assert_eq!(maps[0].0, "conv1");
+    assert_eq!(maps[1].1[0].value.len(), 64);
+    assert!(games::othello::legal_moves(&board).contains(&othello::choose_move(&model, &board)));
+
+    // passes when there is nothing else to do
+    let mut pass = board;
+    pass.cells = [0; 64];
+    pass.cells[0] = 1;
+    pass.cells[1] = -1;
+    pass.player = -1;
+    assert_eq!(othello::choose_move(&model, &pass), games::othello::PASS);
+}
+
+#[test]
+fn tic_tac_toe_virtual_batch_norm_test() {
+    let model = tic_tac_toe::new_gaussian_noise().scalar(0.1);
+    let input =
+        games::tic_tac_toe::encode(&games::tic_tac_toe::play(&games::tic_tac_toe::new(), 4));
+    assert_eq!(model.reference_batch.len(), tic_tac_toe::REFERENCE_BATCH);
+    assert_eq!(model.parameters()[2].0, "vbn1.gain");
+
+    // the statistics a candidate carries are ignored, feedforward takes them from its own weights
+    let mut stale = model.clone();
+    stale.vbn1.norm.mean.value[0] = 100.0;
+    stale.vbn3.norm.variance.value[5] = 1e-8;
+    let output = tic_tac_toe::feedforward(&model, &input);
+    assert_eq!(tic_tac_toe::feedforward(&stale, &input).value, output.value);
+    let references = tic_tac_toe::set_references(&stale);
+    assert_ne!(references.vbn1.norm.mean.value[0], 100.0);
+
+    // so a large perturbation of a conv layer does not change the scale of the next layers
+    let mut perturbed = model.clone();
+    perturbed.conv2 = conv2d::scalar(&perturbed.conv2, 1000.0);
+    let maps = tic_tac_toe::feature_maps(&model, &input);
+    let perturbed_maps = tic_tac_toe::feature_maps(&perturbed, &input);
+    for (channel, perturbed_channel) in maps[2].1.iter().zip(perturbed_maps[2].1.iter()) {
+        for (val, perturbed_val) in channel.value.iter().zip(perturbed_channel.value.iter()) {
+            assert!((val - perturbed_val).abs() < 1e-2);
+        }
+    }
+
+    model.save("tic_tac_toe_virtual_batch_norm_test");
+    let b = tic_tac_toe::load("tic_tac_toe_virtual_batch_norm_test");
+    fs::remove_dir_all("tic_tac_toe_virtual_batch_norm_test").unwrap();
+    assert_eq!(models::flatten(&b), models::flatten(&model));
+    assert_eq!(tic_tac_toe::feedforward(&b, &input).value, output.value);
+}
+
+#[test]
+fn tic_tac_toe_feature_maps_test() {
+    let model = tic_tac_toe::new_gaussian_noise();
+    let input = games::tic_tac_toe::encode(&games::tic_tac_toe::new());
+
+    // the flattened last map is what the fully connected layers see
+    let maps = tic_tac_toe::feature_maps(&model, &input);
+    assert_eq!(maps.len(), 3);
+    assert_eq!(maps[2].1.len(), 64);
+    let flat: Vec<f32> = maps[2].1.iter().flat_map(|m| m.value.clone()).collect();
+    let mut hidden = matrix::new(1, 576);
+    hidden.value = flat;
+    let output = fully_connected::feedforward(
+        &model.fc2,
+        &fully_connected::feedforward(&model.fc1, &hidden),
+    );
+    assert_eq!(output.value, tic_tac_toe::feedforward(&model, &input).value);
+}
+
+#[test]
+fn mlp_test() {
+    let template = mlp::new(
+        4,
+        &[
+            (8, activations::from_name("tanh").unwrap()),
+            (2, activations::from_name("none").unwrap()),
+        ],
+    );
+    let model = models::gaussian_noise(&template, &mut ChaCha8Rng::seed_from_u64(0));
+
+    assert_eq!(models::num_parameters(&model), 4 * 8 + 8 + 8 * 2 + 2);
+    let names: Vec<String> = model.parameters().into_iter().map(|(n, _)| n).collect();
+    assert_eq!(
+        names,
+        vec![
+            "layers0.weights",
+            "layers0.bias",
+            "layers1.weights",
+            "layers1.bias"
+        ]
+    );
+    let output = mlp::feedforward(&model, &matrix::new_gaussian_noise(1, 4));
+    assert_eq!(output.columns, 2);
+
+    model.save("mlp_test");
+    let b = template.load("mlp_test");
+    fs::remove_dir_all("mlp_test").unwrap();
+    assert_eq!(models::flatten(&b), models::flatten(&model));
+}
+
+#[test]
+fn mlp_norm_test() {
+    let mut template = mlp::new(
+        3,
+        &[
+            (4, activations::from_name("tanh").unwrap()),
+            (4, activations::from_name("relu").unwrap()),
+            (2, activations::from_name("none").unwrap()),
+        ],
+    );
+    template.norms[0] = mlp::norm_from_name("layer_norm", 4);
+    template.norms[1] = mlp::norm_from_name("batch_norm", 4);
+    assert!(mlp::norm_from_name("group_norm", 4).is_none());
+    let model = models::gaussian_noise(&template, &mut ChaCha8Rng::seed_from_u64(0));
+
+    let names: Vec<String> = model.parameters().into_iter().map(|(n, _)| n).collect();
+    assert_eq!(
+        names,
+        vec![
+            "layers0.weights",
+            "layers0.bias",
+            "norms0.gain",
+            "norms0.bias",
+            "layers1.weights",
+            "layers1.bias",
+            "norms1.gain",
+            "norms1.bias",
+            "layers2.weights",
+            "layers2.bias"
+        ]
+    );
+    assert_eq!(
+        models::num_parameters(&model),
+        3 * 4 + 4 + 8 + 4 * 4 + 4 + 8 + 4 * 2 + 2
+    );
+
+    let inputs: Vec<matrix::Matrix> = (0..8).map(|_| matrix::new_gaussian_noise(1, 3)).collect();
+    let model = mlp::collect_statistics(&model, &inputs);
+    let Some(mlp::Norm::Batch(n)) = &model.norms[1] else {
+        panic!("norms1 is a batch norm");
+    };
+    assert_ne!(n.mean.value, [0.0; 4]);
+    // the statistics are kept through the updates of an optimizer
+    let step = mlp::add(&model, &mlp::scalar(&model, 0.5));
+    let Some(mlp::Norm::Batch(m)) = &step.norms[1] else {
+        panic!("norms1 is a batch norm");
+    };
+    assert_eq!(m.mean.value, n.mean.value);
+    assert_eq!(models::flatten(&step), models::flatten(&model.scalar(1.5)));
+
+    // a batch of rows gives the same outputs as feeding them one at a time
+    let mut batch = matrix::new(8, 3);
+    for (row, input) in inputs.iter().enumerate() {
+        for j in 0..3 {
+            batch.value[j * 8 + row] = input.value[j];
+        }
+    }
+    let output = mlp::feedforward(&model, &batch);
+    for (row, input) in inputs.iter().enumerate() {
+        let single = mlp::feedforward(&model, input);
+        for j in 0..2 {
+            assert!((output.value[j * 8 + row] - single.value[j]).abs() < 1e-5);
+        }
+    }
+
+    model.save("mlp_norm_test");
+    let b = template.load("mlp_norm_test");
+    fs::remove_dir_all("mlp_norm_test").unwrap();
+    assert_eq!(models::flatten(&b), models::flatten(&model));
+    let Some(mlp::Norm::Batch(loaded)) = &b.norms[1] else {
+        panic!("norms1 is a batch norm");
+    };
+    assert_eq!(loaded.variance.value, n.variance.value);
+}
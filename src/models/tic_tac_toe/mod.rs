@@ -1,47 +1,126 @@
+use crate::games::tic_tac_toe;
 use crate::matrix;
+use crate::models::Model;
 use crate::nn::activations::param_relu;
 use crate::nn::layers::conv2d;
 use crate::nn::layers::fully_connected;
+use crate::nn::layers::virtual_batch_norm;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use std::fs;
 
+// every conv layer is followed by a virtual batch norm before its activation
 #[derive(Clone)]
 pub struct TicTacToe {
     pub conv1: conv2d::Conv2D<param_relu::ParamRelu>,
+    pub vbn1: virtual_batch_norm::VirtualBatchNorm,
     pub conv2: conv2d::Conv2D<param_relu::ParamRelu>,
+    pub vbn2: virtual_batch_norm::VirtualBatchNorm,
     pub conv3: conv2d::Conv2D<param_relu::ParamRelu>,
+    pub vbn3: virtual_batch_norm::VirtualBatchNorm,
     pub fc1: fully_connected::FullyConnected<param_relu::ParamRelu>,
     pub fc2: fully_connected::FullyConnected<param_relu::ParamRelu>,
+    // encoded positions the virtual batch norm statistics are taken from, not evolved
+    pub reference_batch: Vec<Vec<matrix::Matrix>>,
 }
 
+pub const REFERENCE_BATCH: usize = 16;
+
 pub fn new() -> TicTacToe {
     TicTacToe {
         conv1: conv2d::new(3, 64, (3, 3), param_relu::new(1.0, 0.001)),
+        vbn1: virtual_batch_norm::new(64),
         conv2: conv2d::new(64, 64, (3, 3), param_relu::new(1.0, 0.001)),
+        vbn2: virtual_batch_norm::new(64),
         conv3: conv2d::new(64, 64, (3, 3), param_relu::new(1.0, 0.001)),
+        vbn3: virtual_batch_norm::new(64),
         fc1: fully_connected::new(576, 100, param_relu::new(0.25, 0.001)),
         fc2: fully_connected::new(100, 9, param_relu::new(0.25, 0.001)),
+        reference_batch: reference_batch(),
     }
 }
 
 pub fn new_gaussian_noise() -> TicTacToe {
     TicTacToe {
         conv1: conv2d::new_gaussian_noise(3, 64, (3, 3), param_relu::new(1.0, 0.001)),
+        vbn1: virtual_batch_norm::new_gaussian_noise(64),
         conv2: conv2d::new_gaussian_noise(64, 64, (3, 3), param_relu::new(1.0, 0.001)),
+        vbn2: virtual_batch_norm::new_gaussian_noise(64),
         conv3: conv2d::new_gaussian_noise(64, 64, (3, 3), param_relu::new(1.0, 0.001)),
+        vbn3: virtual_batch_norm::new_gaussian_noise(64),
         fc1: fully_connected::new_gaussian_noise(576, 100, param_relu::new(0.25, 0.001)),
         fc2: fully_connected::new_gaussian_noise(100, 9, param_relu::new(0.25, 0.001)),
+        reference_batch: reference_batch(),
     }
 }
 
-pub fn feedforward(ttt: &TicTacToe, input: &[matrix::Matrix]) -> matrix::Matrix {
-    // conv1
-    let mut conv_output = conv2d::feedforward(&ttt.conv1, input, (1, 1), (1, 1, 1, 1));
+// positions of seeded random games, the same for every model so they are not saved
+pub fn reference_batch() -> Vec<Vec<matrix::Matrix>> {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut batch: Vec<Vec<matrix::Matrix>> = Vec::with_capacity(REFERENCE_BATCH);
+    let mut board = tic_tac_toe::new();
+    while batch.len() < REFERENCE_BATCH {
+        batch.push(tic_tac_toe::encode(&board));
+        let moves = tic_tac_toe::legal_moves(&board);
+        board = if moves.is_empty() {
+            tic_tac_toe::new()
+        } else {
+            tic_tac_toe::play(&board, moves[rng.gen_range(0..moves.len())])
+        };
+    }
+
+    batch
+}
+
+// conv -> virtual batch norm -> conv activation, every conv layer keeps the 3 x 3 board size
+fn conv_vbn(
+    conv: &conv2d::Conv2D<param_relu::ParamRelu>,
+    vbn: &virtual_batch_norm::VirtualBatchNorm,
+    input: &[matrix::Matrix],
+) -> Vec<matrix::Matrix> {
+    virtual_batch_norm::feedforward_conv2d(vbn, conv, input, (1, 1), (1, 1, 1, 1))
+}
 
-    // conv2
-    conv_output = conv2d::feedforward(&ttt.conv2, &conv_output, (1, 1), (1, 1, 1, 1));
+// statistics of every virtual batch norm from the reference batch through the weights of ttt
+// add and scalar keep the statistics of a, so feedforward sets them for every candidate
+pub fn set_references(ttt: &TicTacToe) -> TicTacToe {
+    let mut b = ttt.clone();
+    let mut batch = b.reference_batch.clone();
+
+    b.vbn1 =
+        virtual_batch_norm::set_reference_conv2d(&b.vbn1, &b.conv1, &batch, (1, 1), (1, 1, 1, 1));
+    batch = batch
+        .iter()
+        .map(|x| conv_vbn(&b.conv1, &b.vbn1, x))
+        .collect();
+
+    b.vbn2 =
+        virtual_batch_norm::set_reference_conv2d(&b.vbn2, &b.conv2, &batch, (1, 1), (1, 1, 1, 1));
+    batch = batch
+        .iter()
+        .map(|x| conv_vbn(&b.conv2, &b.vbn2, x))
+        .collect();
+
+    b.vbn3 =
+        virtual_batch_norm::set_reference_conv2d(&b.vbn3, &b.conv3, &batch, (1, 1), (1, 1, 1, 1));
+
+    b
+}
 
-    // conv3
-    conv_output = conv2d::feedforward(&ttt.conv3, &conv_output, (1, 1), (1, 1, 1, 1));
+fn conv_layers(ttt: &TicTacToe, input: &[matrix::Matrix]) -> Vec<Vec<matrix::Matrix>> {
+    let ttt = &set_references(ttt);
+
+    let conv1 = conv_vbn(&ttt.conv1, &ttt.vbn1, input);
+    let conv2 = conv_vbn(&ttt.conv2, &ttt.vbn2, &conv1);
+    let conv3 = conv_vbn(&ttt.conv3, &ttt.vbn3, &conv2);
+
+    vec![conv1, conv2, conv3]
+}
+
+pub fn feedforward(ttt: &TicTacToe, input: &[matrix::Matrix]) -> matrix::Matrix {
+    // conv1, conv2 and conv3, each with its virtual batch norm
+    let conv_output = conv_layers(ttt, input).pop().unwrap();
 
     let mut output = matrix::new(1, 576);
     output.value = vec![];
@@ -61,12 +140,43 @@ pub fn feedforward(ttt: &TicTacToe, input: &[matrix::Matrix]) -> matrix::Matrix
     output
 }
 
+// channel outputs of every conv layer for input, named by layer
+pub fn feature_maps(
+    ttt: &TicTacToe,
+    input: &[matrix::Matrix],
+) -> Vec<(String, Vec<matrix::Matrix>)> {
+    ["conv1", "conv2", "conv3"]
+        .iter()
+        .map(|name| name.to_string())
+        .zip(conv_layers(ttt, input))
+        .collect()
+}
+
+// highest scoring legal move for the player to move
+pub fn choose_move(ttt: &TicTacToe, board: &tic_tac_toe::Board) -> usize {
+    let moves = tic_tac_toe::legal_moves(board);
+    assert!(!moves.is_empty(), "Game is already over.");
+
+    let output = feedforward(ttt, &tic_tac_toe::encode(board));
+    let mut best = moves[0];
+    for index in moves {
+        if output.value[index] > output.value[best] {
+            best = index;
+        }
+    }
+
+    best
+}
+
 pub fn add(a: &TicTacToe, b: &TicTacToe) -> TicTacToe {
     let mut c = a.clone();
 
     c.conv1 = conv2d::add(&c.conv1, &b.conv1);
+    c.vbn1 = virtual_batch_norm::add(&c.vbn1, &b.vbn1);
     c.conv2 = conv2d::add(&c.conv2, &b.conv2);
+    c.vbn2 = virtual_batch_norm::add(&c.vbn2, &b.vbn2);
     c.conv3 = conv2d::add(&c.conv3, &b.conv3);
+    c.vbn3 = virtual_batch_norm::add(&c.vbn3, &b.vbn3);
     c.fc1 = fully_connected::add(&c.fc1, &b.fc1);
     c.fc2 = fully_connected::add(&c.fc2, &b.fc2);
 
@@ -77,8 +187,11 @@ pub fn scalar(a: &TicTacToe, s: f32) -> TicTacToe {
     let mut b = a.clone();
 
     b.conv1 = conv2d::scalar(&b.conv1, s);
+    b.vbn1 = virtual_batch_norm::scalar(&b.vbn1, s);
     b.conv2 = conv2d::scalar(&b.conv2, s);
+    b.vbn2 = virtual_batch_norm::scalar(&b.vbn2, s);
     b.conv3 = conv2d::scalar(&b.conv3, s);
+    b.vbn3 = virtual_batch_norm::scalar(&b.vbn3, s);
     b.fc1 = fully_connected::scalar(&b.fc1, s);
     b.fc2 = fully_connected::scalar(&b.fc2, s);
 
@@ -88,8 +201,11 @@ pub fn scalar(a: &TicTacToe, s: f32) -> TicTacToe {
 pub fn save(a: &TicTacToe, dir_name: &str) {
     fs::create_dir_all(dir_name).unwrap();
     conv2d::save(&a.conv1, (dir_name.to_owned() + "/conv1").as_str());
+    virtual_batch_norm::save(&a.vbn1, (dir_name.to_owned() + "/vbn1").as_str());
     conv2d::save(&a.conv2, (dir_name.to_owned() + "/conv2").as_str());
+    virtual_batch_norm::save(&a.vbn2, (dir_name.to_owned() + "/vbn2").as_str());
     conv2d::save(&a.conv3, (dir_name.to_owned() + "/conv3").as_str());
+    virtual_batch_norm::save(&a.vbn3, (dir_name.to_owned() + "/vbn3").as_str());
     fully_connected::save(&a.fc1, (dir_name.to_owned() + "/fc1").as_str());
     fully_connected::save(&a.fc2, (dir_name.to_owned() + "/fc2").as_str());
 }
@@ -97,10 +213,74 @@ pub fn save(a: &TicTacToe, dir_name: &str) {
 pub fn load(dir_name: &str) -> TicTacToe {
     let mut a = new();
     a.conv1 = conv2d::load(&a.conv1, (dir_name.to_owned() + "/conv1").as_str());
+    a.vbn1 = virtual_batch_norm::load(&a.vbn1, (dir_name.to_owned() + "/vbn1").as_str());
     a.conv2 = conv2d::load(&a.conv2, (dir_name.to_owned() + "/conv2").as_str());
+    a.vbn2 = virtual_batch_norm::load(&a.vbn2, (dir_name.to_owned() + "/vbn2").as_str());
     a.conv3 = conv2d::load(&a.conv3, (dir_name.to_owned() + "/conv3").as_str());
+    a.vbn3 = virtual_batch_norm::load(&a.vbn3, (dir_name.to_owned() + "/vbn3").as_str());
     a.fc1 = fully_connected::load(&a.fc1, (dir_name.to_owned() + "/fc1").as_str());
     a.fc2 = fully_connected::load(&a.fc2, (dir_name.to_owned() + "/fc2").as_str());
 
     a
 }
+
+impl Model for TicTacToe {
+    fn add(&self, b: &TicTacToe) -> TicTacToe {
+        add(self, b)
+    }
+
+    fn scalar(&self, s: f32) -> TicTacToe {
+        scalar(self, s)
+    }
+
+    fn parameters(&self) -> Vec<(String, &matrix::Matrix)> {
+        let mut b: Vec<(String, &matrix::Matrix)> = Vec::new();
+        for (name, m) in conv2d::parameters(&self.conv1) {
+            b.push(("conv1.".to_owned() + name, m));
+        }
+        for (name, m) in virtual_batch_norm::parameters(&self.vbn1) {
+            b.push(("vbn1.".to_owned() + name, m));
+        }
+        for (name, m) in conv2d::parameters(&self.conv2) {
+            b.push(("conv2.".to_owned() + name, m));
+        }
+        for (name, m) in virtual_batch_norm::parameters(&self.vbn2) {
+            b.push(("vbn2.".to_owned() + name, m));
+        }
+        for (name, m) in conv2d::parameters(&self.conv3) {
+            b.push(("conv3.".to_owned() + name, m));
+        }
+        for (name, m) in virtual_batch_norm::parameters(&self.vbn3) {
+            b.push(("vbn3.".to_owned() + name, m));
+        }
+        for (name, m) in fully_connected::parameters(&self.fc1) {
+            b.push(("fc1.".to_owned() + name, m));
+        }
+        for (name, m) in fully_connected::parameters(&self.fc2) {
+            b.push(("fc2.".to_owned() + name, m));
+        }
+
+        b
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut matrix::Matrix> {
+        let mut b = conv2d::parameters_mut(&mut self.conv1);
+        b.append(&mut virtual_batch_norm::parameters_mut(&mut self.vbn1));
+        b.append(&mut conv2d::parameters_mut(&mut self.conv2));
+        b.append(&mut virtual_batch_norm::parameters_mut(&mut self.vbn2));
+        b.append(&mut conv2d::parameters_mut(&mut self.conv3));
+        b.append(&mut virtual_batch_norm::parameters_mut(&mut self.vbn3));
+        b.append(&mut fully_connected::parameters_mut(&mut self.fc1));
+        b.append(&mut fully_connected::parameters_mut(&mut self.fc2));
+
+        b
+    }
+
+    fn save(&self, dir_name: &str) {
+        save(self, dir_name);
+    }
+
+    fn load(&self, dir_name: &str) -> TicTacToe {
+        load(dir_name)
+    }
+}
@@ -1,4 +1,5 @@
 use crate::matrix;
+use crate::models::Model;
 use crate::nn::activations::param_relu;
 use crate::nn::layers::fully_connected;
 use std::fs;
@@ -65,3 +66,40 @@ pub fn load(dir_name: &str) -> XorModel {
 
     a
 }
+
+impl Model for XorModel {
+    fn add(&self, b: &XorModel) -> XorModel {
+        add(self, b)
+    }
+
+    fn scalar(&self, s: f32) -> XorModel {
+        scalar(self, s)
+    }
+
+    fn parameters(&self) -> Vec<(String, &matrix::Matrix)> {
+        let mut b: Vec<(String, &matrix::Matrix)> = Vec::new();
+        for (name, m) in fully_connected::parameters(&self.fc1) {
+            b.push(("fc1.".to_owned() + name, m));
+        }
+        for (name, m) in fully_connected::parameters(&self.fc2) {
+            b.push(("fc2.".to_owned() + name, m));
+        }
+
+        b
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut matrix::Matrix> {
+        let mut b = fully_connected::parameters_mut(&mut self.fc1);
+        b.append(&mut fully_connected::parameters_mut(&mut self.fc2));
+
+        b
+    }
+
+    fn save(&self, dir_name: &str) {
+        save(self, dir_name);
+    }
+
+    fn load(&self, dir_name: &str) -> XorModel {
+        load(dir_name)
+    }
+}
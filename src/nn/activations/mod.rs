@@ -1,9 +1,64 @@
 pub mod no_activation;
 pub mod param_relu;
+pub mod sigmoid;
+pub mod tanh;
+pub mod test;
 
 use crate::matrix;
 
 pub trait Activate {
     fn activate(&self, a: &matrix::Matrix) -> matrix::Matrix;
+    // a is the input that was given to activate, returns dL/da from dL/dactivate(a)
+    fn backward(&self, a: &matrix::Matrix, grad: &matrix::Matrix) -> matrix::Matrix;
     fn print(&self);
 }
+
+// activation picked at runtime, e.g. by name from an experiment config
+#[derive(Clone)]
+pub enum Activation {
+    NoActivation(no_activation::NoActivation),
+    ParamRelu(param_relu::ParamRelu),
+    Sigmoid(sigmoid::Sigmoid),
+    Tanh(tanh::Tanh),
+}
+
+// none, relu, leaky_relu, sigmoid or tanh
+pub fn from_name(name: &str) -> Option<Activation> {
+    match name {
+        "none" => Some(Activation::NoActivation(no_activation::new())),
+        "relu" => Some(Activation::ParamRelu(param_relu::new(1.0, 0.0))),
+        "leaky_relu" => Some(Activation::ParamRelu(param_relu::new(1.0, 0.01))),
+        "sigmoid" => Some(Activation::Sigmoid(sigmoid::new())),
+        "tanh" => Some(Activation::Tanh(tanh::new())),
+        _ => None,
+    }
+}
+
+impl Activate for Activation {
+    fn activate(&self, a: &matrix::Matrix) -> matrix::Matrix {
+        match self {
+            Activation::NoActivation(b) => b.activate(a),
+            Activation::ParamRelu(b) => b.activate(a),
+            Activation::Sigmoid(b) => b.activate(a),
+            Activation::Tanh(b) => b.activate(a),
+        }
+    }
+
+    fn backward(&self, a: &matrix::Matrix, grad: &matrix::Matrix) -> matrix::Matrix {
+        match self {
+            Activation::NoActivation(b) => b.backward(a, grad),
+            Activation::ParamRelu(b) => b.backward(a, grad),
+            Activation::Sigmoid(b) => b.backward(a, grad),
+            Activation::Tanh(b) => b.backward(a, grad),
+        }
+    }
+
+    fn print(&self) {
+        match self {
+            Activation::NoActivation(b) => b.print(),
+            Activation::ParamRelu(b) => b.print(),
+            Activation::Sigmoid(b) => b.print(),
+            Activation::Tanh(b) => b.print(),
+        }
+    }
+}
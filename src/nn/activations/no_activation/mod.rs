@@ -15,6 +15,10 @@ impl Activate for NoActivation {
         a.clone()
     }
 
+    fn backward(&self, _a: &matrix::Matrix, grad: &matrix::Matrix) -> matrix::Matrix {
+        grad.clone()
+    }
+
     fn print(&self) {
         println!("No Activation");
     }
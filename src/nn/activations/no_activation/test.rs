@@ -22,3 +22,16 @@ fn no_activation_test() {
     assert!(b.columns == 2);
     assert!(b.value == [1.0, 2.0, 3.0, 4.0]);
 }
+
+#[test]
+fn no_activation_backward_test() {
+    let na = no_activation::new();
+
+    let mut a = matrix::new(1, 3);
+    a.value = vec![1.0, -2.0, 3.0];
+    let mut grad = matrix::new(1, 3);
+    grad.value = vec![0.5, 0.25, -1.0];
+
+    let b = na.backward(&a, &grad);
+    assert_eq!(b.value, [0.5, 0.25, -1.0]);
+}
@@ -30,6 +30,19 @@ impl Activate for ParamRelu {
         b
     }
 
+    fn backward(&self, a: &matrix::Matrix, grad: &matrix::Matrix) -> matrix::Matrix {
+        let mut b = grad.clone();
+        for i in 0..b.rows * b.columns {
+            if a.value[i] < 0.0 {
+                b.value[i] *= self.negative_slope;
+            } else {
+                b.value[i] *= self.positive_slope;
+            }
+        }
+
+        b
+    }
+
     fn print(&self) {
         println!("Parameterized Relu");
     }
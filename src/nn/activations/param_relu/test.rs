@@ -26,3 +26,16 @@ fn param_relu_test() {
     assert_eq!(b.columns, 3);
     assert_eq!(b.value, expected_output);
 }
+
+#[test]
+fn param_relu_backward_test() {
+    let pr = param_relu::new(0.5, 0.25);
+
+    let mut a = matrix::new(1, 4);
+    a.value = vec![2.0, -4.0, 0.0, -1.0];
+    let mut grad = matrix::new(1, 4);
+    grad.value = vec![1.0, 2.0, 3.0, -4.0];
+
+    let b = pr.backward(&a, &grad);
+    assert_eq!(b.value, [0.5, 0.5, 1.5, -1.0]);
+}
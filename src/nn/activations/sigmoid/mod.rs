@@ -0,0 +1,36 @@
+pub mod test;
+
+use crate::matrix;
+use crate::nn::activations::Activate;
+
+#[derive(Clone)]
+pub struct Sigmoid {}
+
+pub fn new() -> Sigmoid {
+    Sigmoid {}
+}
+
+impl Activate for Sigmoid {
+    fn activate(&self, a: &matrix::Matrix) -> matrix::Matrix {
+        let mut b = a.clone();
+        for i in 0..b.rows * b.columns {
+            b.value[i] = 1.0 / (1.0 + (-b.value[i]).exp());
+        }
+
+        b
+    }
+
+    fn backward(&self, a: &matrix::Matrix, grad: &matrix::Matrix) -> matrix::Matrix {
+        let s = self.activate(a);
+        let mut b = grad.clone();
+        for i in 0..b.rows * b.columns {
+            b.value[i] *= s.value[i] * (1.0 - s.value[i]);
+        }
+
+        b
+    }
+
+    fn print(&self) {
+        println!("Sigmoid");
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::sigmoid;
+#[cfg(test)]
+use crate::nn::activations::Activate;
+
+#[test]
+fn sigmoid_test() {
+    let s = sigmoid::new();
+
+    let mut a = matrix::new(2, 2);
+    a.value = vec![0.0, 2.0, -2.0, 100.0];
+
+    let expected_output = [
+        0.5,
+        1.0 / (1.0 + (-2.0_f32).exp()),
+        1.0 / (1.0 + 2.0_f32.exp()),
+        1.0,
+    ];
+
+    let b = s.activate(&a);
+    assert_eq!(b.rows, 2);
+    assert_eq!(b.columns, 2);
+    assert_eq!(b.value, expected_output);
+}
+
+#[test]
+fn sigmoid_backward_test() {
+    let s = sigmoid::new();
+
+    let mut a = matrix::new(1, 3);
+    a.value = vec![0.0, 1.5, -2.0];
+    let mut grad = matrix::new(1, 3);
+    grad.value = vec![1.0, 2.0, -1.0];
+
+    let b = s.backward(&a, &grad);
+    for i in 0..3 {
+        // central difference
+        let mut up = a.clone();
+        let mut down = a.clone();
+        up.value[i] += 1e-2;
+        down.value[i] -= 1e-2;
+        let expected =
+            (s.activate(&up).value[i] - s.activate(&down).value[i]) / 2e-2 * grad.value[i];
+        assert!((b.value[i] - expected).abs() < 1e-3);
+    }
+    assert_eq!(b.value[0], 0.25);
+}
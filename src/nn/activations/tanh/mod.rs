@@ -0,0 +1,35 @@
+pub mod test;
+
+use crate::matrix;
+use crate::nn::activations::Activate;
+
+#[derive(Clone)]
+pub struct Tanh {}
+
+pub fn new() -> Tanh {
+    Tanh {}
+}
+
+impl Activate for Tanh {
+    fn activate(&self, a: &matrix::Matrix) -> matrix::Matrix {
+        let mut b = a.clone();
+        for i in 0..b.rows * b.columns {
+            b.value[i] = b.value[i].tanh();
+        }
+
+        b
+    }
+
+    fn backward(&self, a: &matrix::Matrix, grad: &matrix::Matrix) -> matrix::Matrix {
+        let mut b = grad.clone();
+        for i in 0..b.rows * b.columns {
+            b.value[i] *= 1.0 - a.value[i].tanh().powi(2);
+        }
+
+        b
+    }
+
+    fn print(&self) {
+        println!("Tanh");
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
+use crate::nn::activations::Activate;
+
+#[test]
+fn tanh_test() {
+    let t = tanh::new();
+
+    let mut a = matrix::new(1, 3);
+    a.value = vec![0.0, 0.5, -3.0];
+
+    let expected_output = [0.0, 0.5_f32.tanh(), (-3.0_f32).tanh()];
+
+    let b = t.activate(&a);
+    assert_eq!(b.rows, 1);
+    assert_eq!(b.columns, 3);
+    assert_eq!(b.value, expected_output);
+}
+
+#[test]
+fn tanh_backward_test() {
+    let t = tanh::new();
+
+    let mut a = matrix::new(1, 3);
+    a.value = vec![0.0, 0.5, -3.0];
+    let mut grad = matrix::new(1, 3);
+    grad.value = vec![2.0, 1.0, -1.0];
+
+    let b = t.backward(&a, &grad);
+    assert_eq!(b.rows, 1);
+    assert_eq!(b.columns, 3);
+    assert_eq!(b.value[0], 2.0);
+    assert!((b.value[1] - (1.0 - 0.5_f32.tanh().powi(2))).abs() < 1e-6);
+    assert!((b.value[2] + (1.0 - 3.0_f32.tanh().powi(2))).abs() < 1e-6);
+}
@@ -0,0 +1,26 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations;
+#[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
+use crate::nn::activations::Activate;
+
+#[test]
+fn from_name_test() {
+    let mut a = matrix::new(1, 2);
+    a.value = vec![-1.0, 2.0];
+
+    let relu = activations::from_name("relu").unwrap();
+    assert_eq!(relu.activate(&a).value, vec![0.0, 2.0]);
+    let none = activations::from_name("none").unwrap();
+    assert_eq!(none.activate(&a).value, a.value);
+
+    // same result as the activation it wraps
+    let b = activations::from_name("tanh").unwrap();
+    assert_eq!(b.activate(&a).value, tanh::new().activate(&a).value);
+    assert_eq!(b.backward(&a, &a).value, tanh::new().backward(&a, &a).value);
+
+    assert!(activations::from_name("softmax").is_none());
+}
@@ -0,0 +1,199 @@
+pub mod test;
+
+use crate::matrix;
+use std::fs;
+
+// inference style batch norm, the statistics are collected once over a set of inputs
+// and then stay fixed, only gain and bias are evolved
+// feedforward works on 1 x features rows, feedforward_2d on conv channels where features = channels
+#[derive(Clone)]
+pub struct BatchNorm {
+    pub gain: matrix::Matrix,
+    pub bias: matrix::Matrix,
+    pub mean: matrix::Matrix,
+    pub variance: matrix::Matrix,
+    pub epsilon: f32,
+}
+
+pub fn new(features: usize) -> BatchNorm {
+    BatchNorm {
+        gain: matrix::element_wise_add(&matrix::new(1, features), 1.0),
+        bias: matrix::new(1, features),
+        mean: matrix::new(1, features),
+        variance: matrix::element_wise_add(&matrix::new(1, features), 1.0),
+        epsilon: 1e-5,
+    }
+}
+
+pub fn new_gaussian_noise(features: usize) -> BatchNorm {
+    BatchNorm {
+        gain: matrix::new_gaussian_noise(1, features),
+        bias: matrix::new_gaussian_noise(1, features),
+        mean: matrix::new(1, features),
+        variance: matrix::element_wise_add(&matrix::new(1, features), 1.0),
+        epsilon: 1e-5,
+    }
+}
+
+pub fn print(a: &BatchNorm) {
+    println!("Batch Norm");
+    println!("Gain");
+    matrix::print(&a.gain);
+    println!("Bias");
+    matrix::print(&a.bias);
+    println!("Mean");
+    matrix::print(&a.mean);
+    println!("Variance");
+    matrix::print(&a.variance);
+    println!("Epsilon: {}", a.epsilon);
+}
+
+// inputs are 1 x features rows, statistics are per feature
+pub fn collect_statistics(a: &BatchNorm, inputs: &[matrix::Matrix]) -> BatchNorm {
+    assert!(
+        !inputs.is_empty(),
+        "Cannot collect statistics from no inputs."
+    );
+
+    let mut b = a.clone();
+    let features = a.gain.columns;
+
+    for feature in 0..features {
+        let mut values = matrix::new(inputs.len(), 1);
+        for (i, input) in inputs.iter().enumerate() {
+            assert!(
+                input.rows == 1 && input.columns == features,
+                "Input columns and number of features must match."
+            );
+            values.value[i] = input.value[feature];
+        }
+        b.mean.value[feature] = matrix::mean(&values);
+        b.variance.value[feature] = matrix::variance(&values, b.mean.value[feature]);
+    }
+
+    b
+}
+
+// inputs are conv outputs, statistics are per channel over every sample and position
+pub fn collect_statistics_2d(a: &BatchNorm, inputs: &[Vec<matrix::Matrix>]) -> BatchNorm {
+    assert!(
+        !inputs.is_empty(),
+        "Cannot collect statistics from no inputs."
+    );
+
+    let mut b = a.clone();
+    let features = a.gain.columns;
+
+    for feature in 0..features {
+        let mut mean: f32 = 0.0;
+        for input in inputs.iter() {
+            assert!(
+                input.len() == features,
+                "Input depth and number of features must match."
+            );
+            mean += matrix::mean(&input[feature]);
+        }
+        mean /= inputs.len() as f32;
+
+        let mut variance: f32 = 0.0;
+        for input in inputs.iter() {
+            variance += matrix::variance(&input[feature], mean);
+        }
+        variance /= inputs.len() as f32;
+
+        b.mean.value[feature] = mean;
+        b.variance.value[feature] = variance;
+    }
+
+    b
+}
+
+pub fn feedforward(batch_norm: &BatchNorm, input: &matrix::Matrix) -> matrix::Matrix {
+    assert!(
+        input.columns == batch_norm.gain.columns,
+        "Input columns and number of features must match."
+    );
+
+    // column major, every row of a batch is normalized with the statistics of its column
+    let mut output = input.clone();
+    for i in 0..output.value.len() {
+        let feature = i / input.rows;
+        let std = (batch_norm.variance.value[feature] + batch_norm.epsilon).sqrt();
+        output.value[i] = (output.value[i] - batch_norm.mean.value[feature]) / std
+            * batch_norm.gain.value[feature]
+            + batch_norm.bias.value[feature];
+    }
+
+    output
+}
+
+pub fn feedforward_2d(batch_norm: &BatchNorm, input: &[matrix::Matrix]) -> Vec<matrix::Matrix> {
+    assert!(
+        input.len() == batch_norm.gain.columns,
+        "Input depth and number of features must match."
+    );
+
+    let mut output: Vec<matrix::Matrix> = Vec::with_capacity(input.len());
+    for (i, channel) in input.iter().enumerate() {
+        let std = (batch_norm.variance.value[i] + batch_norm.epsilon).sqrt();
+        let mut b = matrix::scalar(
+            &matrix::element_wise_add(channel, -batch_norm.mean.value[i]),
+            batch_norm.gain.value[i] / std,
+        );
+        b = matrix::element_wise_add(&b, batch_norm.bias.value[i]);
+        output.push(b);
+    }
+
+    output
+}
+
+// statistics are not evolvable and are kept from a
+pub fn add(a: &BatchNorm, b: &BatchNorm) -> BatchNorm {
+    let mut c = a.clone();
+    c.gain = matrix::add(&a.gain, &b.gain);
+    c.bias = matrix::add(&a.bias, &b.bias);
+
+    c
+}
+
+pub fn scalar(a: &BatchNorm, s: f32) -> BatchNorm {
+    let mut b = a.clone();
+    b.gain = matrix::scalar(&b.gain, s);
+    b.bias = matrix::scalar(&b.bias, s);
+
+    b
+}
+
+// statistics are not parameters, they come from collect_statistics
+pub fn parameters(a: &BatchNorm) -> Vec<(&str, &matrix::Matrix)> {
+    vec![("gain", &a.gain), ("bias", &a.bias)]
+}
+
+// same order as parameters
+pub fn parameters_mut(a: &mut BatchNorm) -> Vec<&mut matrix::Matrix> {
+    vec![&mut a.gain, &mut a.bias]
+}
+
+pub fn save(a: &BatchNorm, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    matrix::save(&a.gain, (dir_name.to_owned() + "/gain.bin").as_str());
+    matrix::save(&a.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+    matrix::save(&a.mean, (dir_name.to_owned() + "/mean.bin").as_str());
+    matrix::save(
+        &a.variance,
+        (dir_name.to_owned() + "/variance.bin").as_str(),
+    );
+}
+
+pub fn load(a: &BatchNorm, dir_name: &str) -> BatchNorm {
+    let mut b = a.clone();
+    b.gain = matrix::load(&b.gain, (dir_name.to_owned() + "/gain.bin").as_str());
+    b.bias = matrix::load(&b.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+    b.mean = matrix::load(&b.mean, (dir_name.to_owned() + "/mean.bin").as_str());
+    b.variance = matrix::load(
+        &b.variance,
+        (dir_name.to_owned() + "/variance.bin").as_str(),
+    );
+
+    b
+}
@@ -0,0 +1,184 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::layers::batch_norm;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn new_test() {
+    let a = batch_norm::new(3);
+
+    assert_eq!(a.gain.value, [1.0, 1.0, 1.0]);
+    assert_eq!(a.bias.value, [0.0, 0.0, 0.0]);
+    assert_eq!(a.mean.value, [0.0, 0.0, 0.0]);
+    assert_eq!(a.variance.value, [1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn new_gaussian_noise_test() {
+    let a = batch_norm::new_gaussian_noise(3);
+
+    assert_ne!(a.gain.value, [0.0, 0.0, 0.0]);
+    assert_ne!(a.bias.value, [0.0, 0.0, 0.0]);
+    assert_eq!(a.mean.value, [0.0, 0.0, 0.0]);
+    assert_eq!(a.variance.value, [1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn print_test() {
+    let a = batch_norm::new_gaussian_noise(3);
+    // pass = does not panic
+    batch_norm::print(&a);
+}
+
+#[test]
+fn collect_statistics_test() {
+    let a = batch_norm::new(2);
+
+    let mut inputs = vec![matrix::new(1, 2), matrix::new(1, 2), matrix::new(1, 2)];
+    inputs[0].value = vec![1.0, 10.0];
+    inputs[1].value = vec![2.0, 20.0];
+    inputs[2].value = vec![3.0, 60.0];
+
+    let b = batch_norm::collect_statistics(&a, &inputs);
+    assert_eq!(b.mean.value, [2.0, 30.0]);
+    assert!((b.variance.value[0] - 2.0 / 3.0).abs() < 1e-6);
+    assert!((b.variance.value[1] - 1400.0 / 3.0).abs() < 1e-3);
+
+    // collected statistics are fixed for every later input
+    let output = batch_norm::feedforward(&b, &inputs[2]);
+    assert!((output.value[0] - 1.0 / (2.0_f32 / 3.0 + b.epsilon).sqrt()).abs() < 1e-4);
+    assert!((output.value[1] - 30.0 / (1400.0_f32 / 3.0 + b.epsilon).sqrt()).abs() < 1e-4);
+}
+
+#[test]
+#[should_panic]
+fn collect_statistics_empty_panic_test() {
+    let a = batch_norm::new(2);
+    let _ = batch_norm::collect_statistics(&a, &[]);
+}
+
+#[test]
+fn feedforward_test() {
+    let mut a = batch_norm::new(2);
+    a.mean.value = vec![1.0, -2.0];
+    a.variance.value = vec![4.0, 0.25];
+    a.gain.value = vec![2.0, 0.5];
+    a.bias.value = vec![1.0, 0.0];
+    a.epsilon = 0.0;
+
+    let mut input = matrix::new(1, 2);
+    input.value = vec![3.0, -1.0];
+
+    let output = batch_norm::feedforward(&a, &input);
+    assert_eq!(
+        output.value,
+        [(3.0 - 1.0) / 2.0 * 2.0 + 1.0, 1.0 / 0.5 * 0.5]
+    );
+
+    // a batch of 2 rows, column major, every row uses the statistics of its column
+    let mut batch = matrix::new(2, 2);
+    batch.value = vec![3.0, 1.0, -1.0, -2.0];
+    let output = batch_norm::feedforward(&a, &batch);
+    assert_eq!(output.value, [3.0, 1.0, 1.0, 0.0]);
+}
+
+#[test]
+fn collect_statistics_2d_test() {
+    let a = batch_norm::new(2);
+
+    let mut first = vec![matrix::new(1, 2), matrix::new(1, 2)];
+    first[0].value = vec![1.0, 3.0];
+    first[1].value = vec![0.0, 0.0];
+    let mut second = vec![matrix::new(1, 2), matrix::new(1, 2)];
+    second[0].value = vec![5.0, 7.0];
+    second[1].value = vec![4.0, 4.0];
+
+    let b = batch_norm::collect_statistics_2d(&a, &[first.clone(), second]);
+    assert_eq!(b.mean.value, [4.0, 2.0]);
+    assert_eq!(b.variance.value, [5.0, 4.0]);
+
+    let output = batch_norm::feedforward_2d(&b, &first);
+    assert_eq!(output.len(), 2);
+    let std = (5.0_f32 + b.epsilon).sqrt();
+    assert!((output[0].value[0] - (1.0 - 4.0) / std).abs() < 1e-5);
+    assert!((output[0].value[1] - (3.0 - 4.0) / std).abs() < 1e-5);
+    assert!((output[1].value[0] + 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn add_test() {
+    let a = batch_norm::collect_statistics(
+        &batch_norm::new_gaussian_noise(3),
+        &[
+            matrix::new_gaussian_noise(1, 3),
+            matrix::new_gaussian_noise(1, 3),
+        ],
+    );
+    let b = batch_norm::new_gaussian_noise(3);
+
+    let c = batch_norm::add(&a, &b);
+    for (i, val) in c.gain.value.iter().enumerate() {
+        assert_eq!(*val, a.gain.value[i] + b.gain.value[i]);
+    }
+    for (i, val) in c.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] + b.bias.value[i]);
+    }
+    assert_eq!(c.mean.value, a.mean.value);
+    assert_eq!(c.variance.value, a.variance.value);
+}
+
+#[test]
+fn scalar_test() {
+    let a = batch_norm::new_gaussian_noise(3);
+
+    let b = batch_norm::scalar(&a, 0.5);
+    for (i, val) in b.gain.value.iter().enumerate() {
+        assert_eq!(*val, a.gain.value[i] * 0.5);
+    }
+    for (i, val) in b.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] * 0.5);
+    }
+    assert_eq!(b.mean.value, a.mean.value);
+    assert_eq!(b.variance.value, a.variance.value);
+}
+
+#[test]
+fn parameters_test() {
+    let mut a = batch_norm::new_gaussian_noise(3);
+
+    let names: Vec<&str> = batch_norm::parameters(&a)
+        .iter()
+        .map(|(name, _)| *name)
+        .collect();
+    // the collected statistics are not evolved
+    assert_eq!(names, ["gain", "bias"]);
+
+    for m in batch_norm::parameters_mut(&mut a) {
+        m.value[0] = 5.0;
+    }
+    assert_eq!(a.gain.value[0], 5.0);
+    assert_eq!(a.bias.value[0], 5.0);
+    assert_eq!(a.mean.value[0], 0.0);
+}
+
+#[test]
+fn save_load_test() {
+    let a = batch_norm::collect_statistics(
+        &batch_norm::new_gaussian_noise(3),
+        &[
+            matrix::new_gaussian_noise(1, 3),
+            matrix::new_gaussian_noise(1, 3),
+        ],
+    );
+
+    batch_norm::save(&a, "batch_norm");
+    let b = batch_norm::load(&batch_norm::new(3), "batch_norm");
+    fs::remove_dir_all("batch_norm").unwrap();
+
+    assert_eq!(b.gain.value, a.gain.value);
+    assert_eq!(b.bias.value, a.bias.value);
+    assert_eq!(b.mean.value, a.mean.value);
+    assert_eq!(b.variance.value, a.variance.value);
+}
@@ -209,6 +209,95 @@ pub fn row2im(a: &matrix::Matrix, window_size: (usize, usize)) -> Vec<matrix::Ma
     b
 }
 
+// input_size.0 = input rows, input_size.1 = input columns
+// window_size.0 = window rows, window_size.1 = window columns
+// filter_size.0 = filter rows, filter_size.1 = filter columns
+// stride_size.0 = row stride, stride_size.1 = column stride
+// padding.0 = top padding, padding.1 = left padding
+// walks the same positions as im2col and sums every gradient back onto the input it was copied from
+pub fn im2col_backward(
+    grad: &matrix::Matrix,
+    input_size: (usize, usize),
+    window_size: (usize, usize),
+    filter_size: (usize, usize),
+    num_channels: usize,
+    stride_size: (usize, usize),
+    padding: (usize, usize),
+) -> Vec<matrix::Matrix> {
+    assert!(
+        grad.rows == filter_size.0 * filter_size.1 * num_channels,
+        "Gradient rows must match filter size * number of channels."
+    );
+    assert!(
+        grad.columns == window_size.0 * window_size.1,
+        "Gradient columns must match window rows * window columns."
+    );
+
+    let mut b: Vec<matrix::Matrix> = Vec::with_capacity(num_channels);
+    for _ in 0..num_channels {
+        b.push(matrix::new(input_size.0, input_size.1));
+    }
+
+    let neg_wr: isize = -(padding.0 as isize);
+    let pos_wr: isize = window_size.0 as isize - padding.0 as isize;
+
+    let neg_wc: isize = -(padding.1 as isize);
+    let pos_wc: isize = window_size.1 as isize - padding.1 as isize;
+
+    let mut inc: usize = 0;
+    for wr in neg_wr..pos_wr {
+        for wc in neg_wc..pos_wc {
+            for channel in b.iter_mut() {
+                for fr in 0..filter_size.0 {
+                    for fc in 0..filter_size.1 {
+                        let row: isize =
+                            neg_wr + fr as isize + (wr - neg_wr) * stride_size.0 as isize;
+                        let column: isize =
+                            neg_wc + fc as isize + (wc - neg_wc) * stride_size.1 as isize;
+
+                        // padding does not receive a gradient
+                        if row >= 0
+                            && column >= 0
+                            && row < input_size.0 as isize
+                            && column < input_size.1 as isize
+                        {
+                            let b_index = column as usize * input_size.0 + row as usize;
+                            channel.value[b_index] += grad.value[inc];
+                        }
+                        inc += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    b
+}
+
+// window_size.0 = window rows, window_size.1 = window columns
+// row2im only moves values around so the backward pass is the inverse move
+pub fn row2im_backward(grad: &[matrix::Matrix], window_size: (usize, usize)) -> matrix::Matrix {
+    let mut b = matrix::new(grad.len(), window_size.0 * window_size.1);
+
+    for (ir, grad_im) in grad.iter().enumerate() {
+        assert!(
+            grad_im.rows == window_size.0 && grad_im.columns == window_size.1,
+            "Gradient must have the window size."
+        );
+        let mut ic = 0;
+        for wr in 0..window_size.0 {
+            for wc in 0..window_size.1 {
+                let b_index = ic * b.rows + ir;
+                let grad_index = wc * grad_im.rows + wr;
+                b.value[b_index] = grad_im.value[grad_index];
+                ic += 1;
+            }
+        }
+    }
+
+    b
+}
+
 // filter_size.0 = filter rows, filter_size.1 = filter columns
 pub fn get_filters(
     a: &matrix::Matrix,
@@ -248,6 +337,22 @@ pub fn feedforward<T: Activate>(
     input: &[matrix::Matrix],
     stride: (usize, usize),
     padding: (usize, usize, usize, usize),
+) -> Vec<matrix::Matrix> {
+    let mut output = pre_activation(conv, input, stride, padding);
+
+    for output_im in output.iter_mut() {
+        *output_im = conv.activation.activate(output_im);
+    }
+
+    output
+}
+
+// feedforward without the activation, used when a normalization sits between the two
+pub fn pre_activation<T: Activate>(
+    conv: &Conv2D<T>,
+    input: &[matrix::Matrix],
+    stride: (usize, usize),
+    padding: (usize, usize, usize, usize),
 ) -> Vec<matrix::Matrix> {
     assert!(
         input.len() == conv.num_channels,
@@ -279,12 +384,72 @@ pub fn feedforward<T: Activate>(
             1.0 / (conv.filter_size.0 * conv.filter_size.1) as f32,
         );
         *output_im = matrix::element_wise_add(output_im, conv.bias.value[i]);
-        *output_im = conv.activation.activate(output_im);
     }
 
     output
 }
 
+// returns the gradient of the filters and bias stored in a Conv2D and dL/dinput
+// from dL/doutput, the layer is ran forward again so only the input has to be kept
+pub fn backward<T: Activate + Clone>(
+    conv: &Conv2D<T>,
+    input: &[matrix::Matrix],
+    stride: (usize, usize),
+    padding: (usize, usize, usize, usize),
+    grad_output: &[matrix::Matrix],
+) -> (Conv2D<T>, Vec<matrix::Matrix>) {
+    assert!(
+        grad_output.len() == conv.num_filters,
+        "Gradient depth and number of filters must match."
+    );
+
+    let window_size = get_window_size(
+        (input[0].rows, input[0].columns),
+        conv.filter_size,
+        stride,
+        padding,
+    );
+    let columns = im2col(
+        input,
+        window_size,
+        conv.filter_size,
+        conv.num_channels,
+        stride,
+        (padding.0, padding.2),
+    );
+    let pre_activation = pre_activation(conv, input, stride, padding);
+
+    let mut gradient = conv.clone();
+    let mut grad_pre_activation: Vec<matrix::Matrix> = Vec::with_capacity(conv.num_filters);
+    for (i, grad_im) in grad_output.iter().enumerate() {
+        let grad_im = conv.activation.backward(&pre_activation[i], grad_im);
+        gradient.bias.value[i] = grad_im.value.iter().sum();
+        grad_pre_activation.push(grad_im);
+    }
+
+    let mut grad_product = row2im_backward(&grad_pre_activation, window_size);
+    grad_product = matrix::scalar(
+        &grad_product,
+        1.0 / (conv.filter_size.0 * conv.filter_size.1) as f32,
+    );
+
+    let (grad_filters, grad_columns) =
+        matrix::multiply_backward(&conv.filters, &columns, &grad_product);
+    gradient.filters = grad_filters;
+
+    let grad_input = im2col_backward(
+        &grad_columns,
+        (input[0].rows, input[0].columns),
+        window_size,
+        conv.filter_size,
+        conv.num_channels,
+        stride,
+        (padding.0, padding.2),
+    );
+
+    (gradient, grad_input)
+}
+
 pub fn add<T: Activate + Clone>(a: &Conv2D<T>, b: &Conv2D<T>) -> Conv2D<T> {
     let mut c = a.clone();
 
@@ -303,6 +468,15 @@ pub fn scalar<T: Activate + Clone>(a: &Conv2D<T>, s: f32) -> Conv2D<T> {
     b
 }
 
+pub fn parameters<T: Activate>(a: &Conv2D<T>) -> Vec<(&str, &matrix::Matrix)> {
+    vec![("filters", &a.filters), ("bias", &a.bias)]
+}
+
+// same order as parameters
+pub fn parameters_mut<T: Activate>(a: &mut Conv2D<T>) -> Vec<&mut matrix::Matrix> {
+    vec![&mut a.filters, &mut a.bias]
+}
+
 pub fn save<T: Activate>(a: &Conv2D<T>, dir_name: &str) {
     fs::create_dir_all(dir_name).unwrap();
     matrix::save(&a.filters, (dir_name.to_owned() + "/filters.bin").as_str());
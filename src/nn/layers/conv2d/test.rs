@@ -3,6 +3,8 @@ use crate::matrix;
 #[cfg(test)]
 use crate::nn::activations::param_relu;
 #[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
 use crate::nn::activations::Activate;
 #[cfg(test)]
 use crate::nn::layers::conv2d;
@@ -298,6 +300,7 @@ fn feedforward_test() {
 }
 
 #[test]
+#[allow(clippy::vec_init_then_push)]
 fn feedforward_activation_test() {
     let pr = param_relu::new(0.5, 0.001);
 
@@ -413,3 +416,159 @@ fn save_load_test() {
     assert!(a.filters.value == b.filters.value);
     assert!(a.bias.value == b.bias.value);
 }
+
+#[test]
+fn pre_activation_test() {
+    let pr = param_relu::new(0.5, 0.001);
+    let conv = conv2d::new_gaussian_noise(2, 3, (2, 2), param_relu::new(0.5, 0.001));
+
+    let input = vec![
+        matrix::new_gaussian_noise(3, 3),
+        matrix::new_gaussian_noise(3, 3),
+    ];
+
+    let pre_activation = conv2d::pre_activation(&conv, &input, (1, 1), (0, 0, 0, 0));
+    let output = conv2d::feedforward(&conv, &input, (1, 1), (0, 0, 0, 0));
+
+    assert!(pre_activation.len() == 3);
+    for (i, m) in pre_activation.iter().enumerate() {
+        assert!(pr.activate(m).value == output[i].value);
+    }
+}
+
+#[test]
+fn im2col_backward_test() {
+    // a gradient of ones counts how many windows each input value was copied into
+    let input = vec![matrix::new(3, 3), matrix::new(3, 3)];
+    let filter_size: (usize, usize) = (2, 2);
+    let stride_size: (usize, usize) = (1, 1);
+    let window_size = conv2d::get_window_size((3, 3), filter_size, stride_size, (0, 0, 0, 0));
+    let columns = conv2d::im2col(&input, window_size, filter_size, 2, stride_size, (0, 0));
+
+    let grad = matrix::element_wise_add(&matrix::new(columns.rows, columns.columns), 1.0);
+    let grad_input = conv2d::im2col_backward(
+        &grad,
+        (3, 3),
+        window_size,
+        filter_size,
+        2,
+        stride_size,
+        (0, 0),
+    );
+
+    assert!(grad_input.len() == 2);
+    for grad_im in grad_input.iter() {
+        assert!(grad_im.rows == 3);
+        assert!(grad_im.columns == 3);
+        assert!(grad_im.value == [1.0, 2.0, 1.0, 2.0, 4.0, 2.0, 1.0, 2.0, 1.0]);
+    }
+
+    // with padding only the real input positions receive a gradient
+    let window_size = conv2d::get_window_size((3, 3), (3, 3), (2, 2), (1, 1, 1, 1));
+    let grad = matrix::element_wise_add(&matrix::new(18, window_size.0 * window_size.1), 1.0);
+    let grad_input = conv2d::im2col_backward(&grad, (3, 3), window_size, (3, 3), 2, (2, 2), (1, 1));
+    for grad_im in grad_input.iter() {
+        assert!(grad_im.value == [1.0, 2.0, 1.0, 2.0, 4.0, 2.0, 1.0, 2.0, 1.0]);
+    }
+}
+
+#[test]
+fn row2im_backward_test() {
+    let mut m = matrix::new(4, 6);
+    m.value = vec![
+        1.0, 7.0, 13.0, 19.0, 2.0, 8.0, 14.0, 20.0, 3.0, 9.0, 15.0, 21.0, 4.0, 10.0, 16.0, 22.0,
+        5.0, 11.0, 17.0, 23.0, 6.0, 12.0, 18.0, 24.0,
+    ];
+
+    let im = conv2d::row2im(&m, (2, 3));
+    let b = conv2d::row2im_backward(&im, (2, 3));
+
+    assert!(b.rows == 4);
+    assert!(b.columns == 6);
+    assert!(b.value == m.value);
+}
+
+#[test]
+#[should_panic]
+fn row2im_backward_window_size_test() {
+    let im = vec![matrix::new(2, 3)];
+
+    let _ = conv2d::row2im_backward(&im, (3, 2));
+}
+
+#[test]
+fn backward_test() {
+    let conv = conv2d::new_gaussian_noise(2, 3, (3, 3), tanh::new());
+    let input = vec![
+        matrix::new_gaussian_noise(5, 5),
+        matrix::new_gaussian_noise(5, 5),
+    ];
+    let (stride, padding) = ((2, 2), (1, 1, 1, 1));
+
+    // loss = sum(output * direction) so dL/doutput = direction
+    let mut direction: Vec<matrix::Matrix> = Vec::new();
+    for _ in 0..3 {
+        direction.push(matrix::new_gaussian_noise(3, 3));
+    }
+    let loss = |c: &conv2d::Conv2D<tanh::Tanh>, x: &[matrix::Matrix]| -> f32 {
+        let output = conv2d::feedforward(c, x, stride, padding);
+        let mut sum: f32 = 0.0;
+        for (i, m) in output.iter().enumerate() {
+            sum += matrix::element_wise_multiply(m, &direction[i])
+                .value
+                .iter()
+                .sum::<f32>();
+        }
+        sum
+    };
+
+    let (gradient, grad_input) = conv2d::backward(&conv, &input, stride, padding, &direction);
+    assert!(gradient.filters.rows == conv.filters.rows);
+    assert!(gradient.filters.columns == conv.filters.columns);
+    assert!(grad_input.len() == 2);
+
+    let h = 1e-2;
+    for i in 0..conv.filters.value.len() {
+        let mut up = conv.clone();
+        let mut down = conv.clone();
+        up.filters.value[i] += h;
+        down.filters.value[i] -= h;
+        let expected = (loss(&up, &input) - loss(&down, &input)) / (2.0 * h);
+        assert!((gradient.filters.value[i] - expected).abs() < 1e-2);
+    }
+    for i in 0..conv.bias.value.len() {
+        let mut up = conv.clone();
+        let mut down = conv.clone();
+        up.bias.value[i] += h;
+        down.bias.value[i] -= h;
+        let expected = (loss(&up, &input) - loss(&down, &input)) / (2.0 * h);
+        assert!((gradient.bias.value[i] - expected).abs() < 1e-2);
+    }
+    for c in 0..input.len() {
+        for i in 0..input[c].value.len() {
+            let mut up = input.clone();
+            let mut down = input.clone();
+            up[c].value[i] += h;
+            down[c].value[i] -= h;
+            let expected = (loss(&conv, &up) - loss(&conv, &down)) / (2.0 * h);
+            assert!((grad_input[c].value[i] - expected).abs() < 1e-2);
+        }
+    }
+}
+
+#[test]
+fn parameters_test() {
+    let mut conv = conv2d::new_gaussian_noise(2, 3, (2, 2), param_relu::new(1.0, 0.001));
+
+    let parameters = conv2d::parameters(&conv);
+    assert!(parameters.len() == 2);
+    assert!(parameters[0].0 == "filters");
+    assert!(parameters[1].0 == "bias");
+    assert!(parameters[1].1.value == conv.bias.value);
+
+    for m in conv2d::parameters_mut(&mut conv) {
+        m.value[0] = 5.0;
+    }
+    assert!(conv.filters.value[0] == 5.0);
+    assert!(conv.bias.value[0] == 5.0);
+}
@@ -0,0 +1,222 @@
+pub mod test;
+
+use crate::matrix;
+use crate::nn::activations::Activate;
+use std::fs;
+
+// continuous-time recurrent neural network
+// time_constants * dy/dt = -y + activation(gains * (y + bias)) * weights + input * input_weights
+// y is the membrane potential of each neuron, the output is the firing rate
+// activation(gains * (y + bias)) after integrating one step of dt
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Integration {
+    Euler,
+    RungeKutta4,
+}
+
+#[derive(Clone)]
+pub struct Ctrnn<T: Activate> {
+    pub input_weights: matrix::Matrix,
+    pub weights: matrix::Matrix,
+    pub time_constants: matrix::Matrix,
+    pub bias: matrix::Matrix,
+    pub gains: matrix::Matrix,
+    pub activation: T,
+    pub integration: Integration,
+    pub dt: f32,
+}
+
+#[derive(Clone)]
+pub struct CtrnnState {
+    pub potentials: matrix::Matrix,
+}
+
+// time constants and gains start at one so an unevolved layer is a plain leaky integrator
+pub fn new<T: Activate>(
+    inputs: usize,
+    neurons: usize,
+    activation: T,
+    integration: Integration,
+    dt: f32,
+) -> Ctrnn<T> {
+    assert!(dt > 0.0, "Time step must be positive.");
+    Ctrnn {
+        input_weights: matrix::new(inputs, neurons),
+        weights: matrix::new(neurons, neurons),
+        time_constants: matrix::element_wise_add(&matrix::new(1, neurons), 1.0),
+        bias: matrix::new(1, neurons),
+        gains: matrix::element_wise_add(&matrix::new(1, neurons), 1.0),
+        activation,
+        integration,
+        dt,
+    }
+}
+
+pub fn new_gaussian_noise<T: Activate>(
+    inputs: usize,
+    neurons: usize,
+    activation: T,
+    integration: Integration,
+    dt: f32,
+) -> Ctrnn<T> {
+    assert!(dt > 0.0, "Time step must be positive.");
+    Ctrnn {
+        input_weights: matrix::new_gaussian_noise(inputs, neurons),
+        weights: matrix::new_gaussian_noise(neurons, neurons),
+        time_constants: matrix::new_gaussian_noise(1, neurons),
+        bias: matrix::new_gaussian_noise(1, neurons),
+        gains: matrix::new_gaussian_noise(1, neurons),
+        activation,
+        integration,
+        dt,
+    }
+}
+
+pub fn new_state<T: Activate>(a: &Ctrnn<T>) -> CtrnnState {
+    CtrnnState {
+        potentials: matrix::new(1, a.weights.rows),
+    }
+}
+
+// call at the start of every episode
+pub fn reset_state(state: &mut CtrnnState) {
+    state.potentials = matrix::new(state.potentials.rows, state.potentials.columns);
+}
+
+pub fn print<T: Activate>(a: &Ctrnn<T>) {
+    println!("Ctrnn Layer");
+    println!("Input Weights");
+    matrix::print(&a.input_weights);
+    println!("Weights");
+    matrix::print(&a.weights);
+    println!("Time Constants");
+    matrix::print(&a.time_constants);
+    println!("Bias");
+    matrix::print(&a.bias);
+    println!("Gains");
+    matrix::print(&a.gains);
+    println!("Integration: {:?}", a.integration);
+    println!("dt: {}", a.dt);
+    print!("Activation: ");
+    a.activation.print();
+}
+
+pub fn firing_rates<T: Activate>(a: &Ctrnn<T>, potentials: &matrix::Matrix) -> matrix::Matrix {
+    let b = matrix::element_wise_multiply(&a.gains, &matrix::add(potentials, &a.bias));
+
+    a.activation.activate(&b)
+}
+
+// evolved time constants are clamped to dt, which keeps the integration stable
+// even when a perturbation pushes a time constant to zero or below
+fn derivative<T: Activate>(
+    a: &Ctrnn<T>,
+    potentials: &matrix::Matrix,
+    external_input: &matrix::Matrix,
+) -> matrix::Matrix {
+    let mut b = matrix::multiply(&firing_rates(a, potentials), &a.weights);
+    b = matrix::add(&b, external_input);
+    b = matrix::add(&b, &matrix::scalar(potentials, -1.0));
+
+    for i in 0..b.value.len() {
+        b.value[i] /= a.time_constants.value[i].max(a.dt);
+    }
+
+    b
+}
+
+pub fn feedforward<T: Activate>(
+    ctrnn: &Ctrnn<T>,
+    state: &mut CtrnnState,
+    input: &matrix::Matrix,
+) -> matrix::Matrix {
+    let external_input = matrix::multiply(input, &ctrnn.input_weights);
+    let y = &state.potentials;
+    let dt = ctrnn.dt;
+
+    state.potentials = match ctrnn.integration {
+        Integration::Euler => {
+            let k1 = derivative(ctrnn, y, &external_input);
+            matrix::add(y, &matrix::scalar(&k1, dt))
+        }
+        Integration::RungeKutta4 => {
+            let k1 = derivative(ctrnn, y, &external_input);
+            let k2 = derivative(
+                ctrnn,
+                &matrix::add(y, &matrix::scalar(&k1, dt / 2.0)),
+                &external_input,
+            );
+            let k3 = derivative(
+                ctrnn,
+                &matrix::add(y, &matrix::scalar(&k2, dt / 2.0)),
+                &external_input,
+            );
+            let k4 = derivative(
+                ctrnn,
+                &matrix::add(y, &matrix::scalar(&k3, dt)),
+                &external_input,
+            );
+
+            let mut k = matrix::add(&k1, &matrix::scalar(&k2, 2.0));
+            k = matrix::add(&k, &matrix::scalar(&k3, 2.0));
+            k = matrix::add(&k, &k4);
+            matrix::add(y, &matrix::scalar(&k, dt / 6.0))
+        }
+    };
+
+    firing_rates(ctrnn, &state.potentials)
+}
+
+pub fn add<T: Activate + Clone>(a: &Ctrnn<T>, b: &Ctrnn<T>) -> Ctrnn<T> {
+    let mut c = a.clone();
+    c.input_weights = matrix::add(&a.input_weights, &b.input_weights);
+    c.weights = matrix::add(&a.weights, &b.weights);
+    c.time_constants = matrix::add(&a.time_constants, &b.time_constants);
+    c.bias = matrix::add(&a.bias, &b.bias);
+    c.gains = matrix::add(&a.gains, &b.gains);
+
+    c
+}
+
+pub fn scalar<T: Activate + Clone>(a: &Ctrnn<T>, s: f32) -> Ctrnn<T> {
+    let mut b = a.clone();
+    b.input_weights = matrix::scalar(&b.input_weights, s);
+    b.weights = matrix::scalar(&b.weights, s);
+    b.time_constants = matrix::scalar(&b.time_constants, s);
+    b.bias = matrix::scalar(&b.bias, s);
+    b.gains = matrix::scalar(&b.gains, s);
+
+    b
+}
+
+pub fn save<T: Activate>(a: &Ctrnn<T>, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    matrix::save(
+        &a.input_weights,
+        (dir_name.to_owned() + "/input_weights.bin").as_str(),
+    );
+    matrix::save(&a.weights, (dir_name.to_owned() + "/weights.bin").as_str());
+    matrix::save(
+        &a.time_constants,
+        (dir_name.to_owned() + "/time_constants.bin").as_str(),
+    );
+    matrix::save(&a.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+    matrix::save(&a.gains, (dir_name.to_owned() + "/gains.bin").as_str());
+}
+
+pub fn load<T: Activate + Clone>(a: &Ctrnn<T>, dir_name: &str) -> Ctrnn<T> {
+    let mut b = a.clone();
+    b.input_weights = matrix::load(
+        &b.input_weights,
+        (dir_name.to_owned() + "/input_weights.bin").as_str(),
+    );
+    b.weights = matrix::load(&b.weights, (dir_name.to_owned() + "/weights.bin").as_str());
+    b.time_constants = matrix::load(
+        &b.time_constants,
+        (dir_name.to_owned() + "/time_constants.bin").as_str(),
+    );
+    b.bias = matrix::load(&b.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+    b.gains = matrix::load(&b.gains, (dir_name.to_owned() + "/gains.bin").as_str());
+
+    b
+}
@@ -0,0 +1,178 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::no_activation;
+#[cfg(test)]
+use crate::nn::activations::sigmoid;
+#[cfg(test)]
+use crate::nn::layers::ctrnn;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn new_test() {
+    let a = ctrnn::new(2, 3, sigmoid::new(), ctrnn::Integration::Euler, 0.1);
+
+    assert_eq!(a.input_weights.rows, 2);
+    assert_eq!(a.input_weights.columns, 3);
+    assert_eq!(a.weights.rows, 3);
+    assert_eq!(a.weights.columns, 3);
+    assert_eq!(a.time_constants.value, [1.0, 1.0, 1.0]);
+    assert_eq!(a.bias.value, [0.0, 0.0, 0.0]);
+    assert_eq!(a.gains.value, [1.0, 1.0, 1.0]);
+    assert_eq!(a.dt, 0.1);
+
+    let state = ctrnn::new_state(&a);
+    assert_eq!(state.potentials.value, [0.0, 0.0, 0.0]);
+}
+
+#[test]
+#[should_panic]
+fn new_dt_panic_test() {
+    let _a = ctrnn::new(2, 3, sigmoid::new(), ctrnn::Integration::Euler, 0.0);
+}
+
+#[test]
+fn new_gaussian_noise_test() {
+    let a = ctrnn::new_gaussian_noise(2, 3, sigmoid::new(), ctrnn::Integration::Euler, 0.1);
+
+    assert_ne!(a.weights.value, [0.0; 9]);
+    assert_ne!(a.time_constants.value, [0.0, 0.0, 0.0]);
+    assert_ne!(a.gains.value, [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn print_test() {
+    let a = ctrnn::new_gaussian_noise(2, 3, sigmoid::new(), ctrnn::Integration::RungeKutta4, 0.1);
+    // pass = does not panic
+    ctrnn::print(&a);
+}
+
+#[test]
+fn feedforward_euler_test() {
+    // single linear neuron: tau * dy/dt = -y + w * (g * (y + b)) + x * v
+    let mut a = ctrnn::new(1, 1, no_activation::new(), ctrnn::Integration::Euler, 0.1);
+    a.input_weights.value = vec![2.0];
+    a.weights.value = vec![0.5];
+    a.time_constants.value = vec![0.5];
+    a.bias.value = vec![1.0];
+    a.gains.value = vec![2.0];
+
+    let mut state = ctrnn::new_state(&a);
+    let mut input = matrix::new(1, 1);
+    input.value = vec![1.0];
+
+    let mut y: f32 = 0.0;
+    for _ in 0..5 {
+        let dy = (-y + 0.5 * (2.0 * (y + 1.0)) + 2.0) / 0.5;
+        y += 0.1 * dy;
+
+        let output = ctrnn::feedforward(&a, &mut state, &input);
+        assert!((state.potentials.value[0] - y).abs() < 1e-5);
+        assert!((output.value[0] - 2.0 * (y + 1.0)).abs() < 1e-5);
+    }
+
+    ctrnn::reset_state(&mut state);
+    assert_eq!(state.potentials.value, [0.0]);
+}
+
+#[test]
+fn feedforward_runge_kutta_test() {
+    // tau * dy/dt = -y + x has the solution y = x * (1 - exp(-t / tau))
+    let mut a = ctrnn::new(
+        1,
+        1,
+        no_activation::new(),
+        ctrnn::Integration::RungeKutta4,
+        0.1,
+    );
+    a.input_weights.value = vec![1.0];
+    a.time_constants.value = vec![0.5];
+
+    let mut state = ctrnn::new_state(&a);
+    let mut input = matrix::new(1, 1);
+    input.value = vec![3.0];
+
+    for _ in 0..10 {
+        ctrnn::feedforward(&a, &mut state, &input);
+    }
+
+    let expected = 3.0 * (1.0 - (-1.0_f32 / 0.5).exp());
+    assert!((state.potentials.value[0] - expected).abs() < 1e-4);
+
+    // euler is noticeably less accurate with the same step size
+    a.integration = ctrnn::Integration::Euler;
+    let mut euler_state = ctrnn::new_state(&a);
+    for _ in 0..10 {
+        ctrnn::feedforward(&a, &mut euler_state, &input);
+    }
+    assert!((euler_state.potentials.value[0] - expected).abs() > 1e-2);
+}
+
+#[test]
+fn feedforward_time_constant_clamp_test() {
+    let mut a = ctrnn::new(1, 1, no_activation::new(), ctrnn::Integration::Euler, 0.1);
+    a.input_weights.value = vec![1.0];
+    a.time_constants.value = vec![-2.0];
+
+    let mut state = ctrnn::new_state(&a);
+    let mut input = matrix::new(1, 1);
+    input.value = vec![1.0];
+
+    // time constant is clamped to dt, so one euler step lands exactly on the input
+    ctrnn::feedforward(&a, &mut state, &input);
+    assert!((state.potentials.value[0] - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn add_test() {
+    let a = ctrnn::new_gaussian_noise(2, 3, sigmoid::new(), ctrnn::Integration::Euler, 0.1);
+    let b = ctrnn::new_gaussian_noise(2, 3, sigmoid::new(), ctrnn::Integration::Euler, 0.1);
+
+    let c = ctrnn::add(&a, &b);
+    for (i, val) in c.weights.value.iter().enumerate() {
+        assert_eq!(*val, a.weights.value[i] + b.weights.value[i]);
+    }
+    for (i, val) in c.time_constants.value.iter().enumerate() {
+        assert_eq!(*val, a.time_constants.value[i] + b.time_constants.value[i]);
+    }
+    for (i, val) in c.gains.value.iter().enumerate() {
+        assert_eq!(*val, a.gains.value[i] + b.gains.value[i]);
+    }
+    assert_eq!(c.dt, a.dt);
+}
+
+#[test]
+fn scalar_test() {
+    let a = ctrnn::new_gaussian_noise(2, 3, sigmoid::new(), ctrnn::Integration::Euler, 0.1);
+
+    let b = ctrnn::scalar(&a, 0.5);
+    for (i, val) in b.input_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.input_weights.value[i] * 0.5);
+    }
+    for (i, val) in b.time_constants.value.iter().enumerate() {
+        assert_eq!(*val, a.time_constants.value[i] * 0.5);
+    }
+    for (i, val) in b.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] * 0.5);
+    }
+    assert_eq!(b.dt, a.dt);
+}
+
+#[test]
+fn save_load_test() {
+    let a = ctrnn::new_gaussian_noise(2, 3, sigmoid::new(), ctrnn::Integration::Euler, 0.1);
+
+    ctrnn::save(&a, "ctrnn");
+    let b = ctrnn::load(
+        &ctrnn::new(2, 3, sigmoid::new(), ctrnn::Integration::Euler, 0.1),
+        "ctrnn",
+    );
+    fs::remove_dir_all("ctrnn").unwrap();
+
+    assert_eq!(b.input_weights.value, a.input_weights.value);
+    assert_eq!(b.weights.value, a.weights.value);
+    assert_eq!(b.time_constants.value, a.time_constants.value);
+    assert_eq!(b.bias.value, a.bias.value);
+    assert_eq!(b.gains.value, a.gains.value);
+}
@@ -45,13 +45,38 @@ pub fn feedforward<T: Activate>(
     fully_connected: &FullyConnected<T>,
     input: &matrix::Matrix,
 ) -> matrix::Matrix {
+    // every row of input is one sample
     let mut output = matrix::multiply(input, &fully_connected.weights);
-    output = matrix::add(&output, &fully_connected.bias);
+    output = matrix::add_row(&output, &fully_connected.bias);
     output = fully_connected.activation.activate(&output);
 
     output
 }
 
+// returns the gradient of the weights and bias stored in a FullyConnected and dL/dinput
+// from dL/doutput, the layer is ran forward again so only the input has to be kept
+pub fn backward<T: Activate + Clone>(
+    fully_connected: &FullyConnected<T>,
+    input: &matrix::Matrix,
+    grad_output: &matrix::Matrix,
+) -> (FullyConnected<T>, matrix::Matrix) {
+    let mut pre_activation = matrix::multiply(input, &fully_connected.weights);
+    pre_activation = matrix::add_row(&pre_activation, &fully_connected.bias);
+
+    let grad_pre_activation = fully_connected
+        .activation
+        .backward(&pre_activation, grad_output);
+    let (grad_product, grad_bias) = matrix::add_row_backward(&grad_pre_activation);
+    let (grad_input, grad_weights) =
+        matrix::multiply_backward(input, &fully_connected.weights, &grad_product);
+
+    let mut gradient = fully_connected.clone();
+    gradient.weights = grad_weights;
+    gradient.bias = grad_bias;
+
+    (gradient, grad_input)
+}
+
 pub fn add<T: Activate + Clone>(a: &FullyConnected<T>, b: &FullyConnected<T>) -> FullyConnected<T> {
     let mut c = a.clone();
     c.weights = matrix::add(&a.weights, &b.weights);
@@ -68,6 +93,15 @@ pub fn scalar<T: Activate + Clone>(a: &FullyConnected<T>, s: f32) -> FullyConnec
     b
 }
 
+pub fn parameters<T: Activate>(a: &FullyConnected<T>) -> Vec<(&str, &matrix::Matrix)> {
+    vec![("weights", &a.weights), ("bias", &a.bias)]
+}
+
+// same order as parameters
+pub fn parameters_mut<T: Activate>(a: &mut FullyConnected<T>) -> Vec<&mut matrix::Matrix> {
+    vec![&mut a.weights, &mut a.bias]
+}
+
 pub fn save<T: Activate>(a: &FullyConnected<T>, dir_name: &str) {
     fs::create_dir_all(dir_name).unwrap();
     matrix::save(&a.weights, (dir_name.to_owned() + "/weights.bin").as_str());
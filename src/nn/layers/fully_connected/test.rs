@@ -3,6 +3,8 @@ use crate::matrix;
 #[cfg(test)]
 use crate::nn::activations::param_relu;
 #[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
 use crate::nn::activations::Activate;
 #[cfg(test)]
 use crate::nn::layers::fully_connected;
@@ -123,3 +125,135 @@ fn save_load_test() {
     assert_eq!(b.weights.value, [1.0, 3.0, 4.0, -5.0, 2.0, -9.0]);
     assert_eq!(b.bias.value, [1.0, 2.0, -4.0]);
 }
+
+#[test]
+fn backward_test() {
+    let a = fully_connected::new_gaussian_noise(3, 2, tanh::new());
+    let input = matrix::new_gaussian_noise(1, 3);
+    // loss = sum(output * direction) so dL/doutput = direction
+    let direction = matrix::new_gaussian_noise(1, 2);
+    let loss = |fc: &fully_connected::FullyConnected<tanh::Tanh>, x: &matrix::Matrix| -> f32 {
+        let output = fully_connected::feedforward(fc, x);
+        matrix::element_wise_multiply(&output, &direction)
+            .value
+            .iter()
+            .sum()
+    };
+
+    let (gradient, grad_input) = fully_connected::backward(&a, &input, &direction);
+    assert_eq!(gradient.weights.rows, 3);
+    assert_eq!(gradient.weights.columns, 2);
+    assert_eq!(gradient.bias.columns, 2);
+    assert_eq!(grad_input.columns, 3);
+
+    let h = 1e-2;
+    for i in 0..a.weights.value.len() {
+        let mut up = a.clone();
+        let mut down = a.clone();
+        up.weights.value[i] += h;
+        down.weights.value[i] -= h;
+        let expected = (loss(&up, &input) - loss(&down, &input)) / (2.0 * h);
+        assert!((gradient.weights.value[i] - expected).abs() < 1e-2);
+    }
+    for i in 0..a.bias.value.len() {
+        let mut up = a.clone();
+        let mut down = a.clone();
+        up.bias.value[i] += h;
+        down.bias.value[i] -= h;
+        let expected = (loss(&up, &input) - loss(&down, &input)) / (2.0 * h);
+        assert!((gradient.bias.value[i] - expected).abs() < 1e-2);
+    }
+    for i in 0..input.value.len() {
+        let mut up = input.clone();
+        let mut down = input.clone();
+        up.value[i] += h;
+        down.value[i] -= h;
+        let expected = (loss(&a, &up) - loss(&a, &down)) / (2.0 * h);
+        assert!((grad_input.value[i] - expected).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn backward_batch_test() {
+    let a = fully_connected::new_gaussian_noise(3, 2, tanh::new());
+    let input = matrix::new_gaussian_noise(4, 3);
+    let direction = matrix::new_gaussian_noise(4, 2);
+
+    let output = fully_connected::feedforward(&a, &input);
+    assert_eq!((output.rows, output.columns), (4, 2));
+
+    let (gradient, grad_input) = fully_connected::backward(&a, &input, &direction);
+    assert_eq!((gradient.weights.rows, gradient.weights.columns), (3, 2));
+    assert_eq!((gradient.bias.rows, gradient.bias.columns), (1, 2));
+    assert_eq!((grad_input.rows, grad_input.columns), (4, 3));
+
+    // the batch gradient is the sum of the gradients of its rows
+    let mut weights = matrix::new(3, 2);
+    let mut bias = matrix::new(1, 2);
+    for row in 0..input.rows {
+        let mut x = matrix::new(1, 3);
+        for j in 0..3 {
+            x.value[j] = input.value[j * input.rows + row];
+        }
+        let mut d = matrix::new(1, 2);
+        for j in 0..2 {
+            d.value[j] = direction.value[j * direction.rows + row];
+        }
+        let (g, g_input) = fully_connected::backward(&a, &x, &d);
+        weights = matrix::add(&weights, &g.weights);
+        bias = matrix::add(&bias, &g.bias);
+        for j in 0..3 {
+            assert!((grad_input.value[j * input.rows + row] - g_input.value[j]).abs() < 1e-5);
+        }
+    }
+    for i in 0..weights.value.len() {
+        assert!((gradient.weights.value[i] - weights.value[i]).abs() < 1e-4);
+    }
+    for i in 0..bias.value.len() {
+        assert!((gradient.bias.value[i] - bias.value[i]).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn backward_sgd_step_test() {
+    let mut a = fully_connected::new_gaussian_noise(2, 1, param_relu::new(1.0, 1.0));
+    let mut input = matrix::new(1, 2);
+    input.value = vec![1.0, -2.0];
+    let target: f32 = 3.0;
+
+    let error = |fc: &fully_connected::FullyConnected<param_relu::ParamRelu>| -> f32 {
+        (fully_connected::feedforward(fc, &input).value[0] - target).powi(2)
+    };
+
+    let start = error(&a);
+    for _ in 0..20 {
+        let output = fully_connected::feedforward(&a, &input);
+        let mut grad_output = matrix::new(1, 1);
+        grad_output.value[0] = 2.0 * (output.value[0] - target);
+
+        // gradients live in a FullyConnected so a step is add and scalar
+        let (gradient, _) = fully_connected::backward(&a, &input, &grad_output);
+        a = fully_connected::add(&a, &fully_connected::scalar(&gradient, -0.05));
+    }
+
+    assert!(error(&a) < start * 0.01 + 1e-6);
+}
+
+#[test]
+fn parameters_test() {
+    let mut a = fully_connected::new_gaussian_noise(2, 3, param_relu::new(1.0, 0.001));
+    let weights = a.weights.value.clone();
+
+    let names: Vec<&str> = fully_connected::parameters(&a)
+        .iter()
+        .map(|(name, _)| *name)
+        .collect();
+    assert_eq!(names, ["weights", "bias"]);
+    assert_eq!(fully_connected::parameters(&a)[0].1.value, weights);
+
+    for m in fully_connected::parameters_mut(&mut a) {
+        m.value[0] = 5.0;
+    }
+    assert_eq!(a.weights.value[0], 5.0);
+    assert_eq!(a.bias.value[0], 5.0);
+}
@@ -0,0 +1,152 @@
+pub mod test;
+
+use crate::matrix;
+use crate::nn::activations::sigmoid;
+use crate::nn::activations::Activate;
+use std::fs;
+
+// gates are stored side by side in the columns of each matrix
+// columns 0..outputs = update gate, outputs..2 * outputs = reset gate,
+// 2 * outputs..3 * outputs = candidate
+// update = sigmoid(input * W_u + hidden * U_u + b_u)
+// reset = sigmoid(input * W_r + hidden * U_r + b_r)
+// candidate = activation(input * W_c + reset * (hidden * U_c) + b_c)
+// hidden = (1 - update) * candidate + update * hidden
+#[derive(Clone)]
+pub struct Gru<T: Activate> {
+    pub input_weights: matrix::Matrix,
+    pub hidden_weights: matrix::Matrix,
+    pub bias: matrix::Matrix,
+    pub activation: T,
+}
+
+#[derive(Clone)]
+pub struct GruState {
+    pub hidden: matrix::Matrix,
+}
+
+pub fn new<T: Activate>(inputs: usize, outputs: usize, activation: T) -> Gru<T> {
+    Gru {
+        input_weights: matrix::new(inputs, 3 * outputs),
+        hidden_weights: matrix::new(outputs, 3 * outputs),
+        bias: matrix::new(1, 3 * outputs),
+        activation,
+    }
+}
+
+pub fn new_gaussian_noise<T: Activate>(inputs: usize, outputs: usize, activation: T) -> Gru<T> {
+    Gru {
+        input_weights: matrix::new_gaussian_noise(inputs, 3 * outputs),
+        hidden_weights: matrix::new_gaussian_noise(outputs, 3 * outputs),
+        bias: matrix::new_gaussian_noise(1, 3 * outputs),
+        activation,
+    }
+}
+
+pub fn new_state<T: Activate>(a: &Gru<T>) -> GruState {
+    GruState {
+        hidden: matrix::new(1, a.hidden_weights.rows),
+    }
+}
+
+// call at the start of every episode
+pub fn reset_state(state: &mut GruState) {
+    state.hidden = matrix::new(state.hidden.rows, state.hidden.columns);
+}
+
+pub fn print<T: Activate>(a: &Gru<T>) {
+    println!("Gru Layer");
+    println!("Input Weights");
+    matrix::print(&a.input_weights);
+    println!("Hidden Weights");
+    matrix::print(&a.hidden_weights);
+    println!("Bias");
+    matrix::print(&a.bias);
+    print!("Activation: ");
+    a.activation.print();
+}
+
+pub fn feedforward<T: Activate>(
+    gru: &Gru<T>,
+    state: &mut GruState,
+    input: &matrix::Matrix,
+) -> matrix::Matrix {
+    let outputs = gru.hidden_weights.rows;
+    let gate_activation = sigmoid::new();
+
+    let x = matrix::add(&matrix::multiply(input, &gru.input_weights), &gru.bias);
+    let h = matrix::multiply(&state.hidden, &gru.hidden_weights);
+
+    let update = gate_activation.activate(&matrix::add(
+        &matrix::slice_columns(&x, 0, outputs),
+        &matrix::slice_columns(&h, 0, outputs),
+    ));
+    let reset = gate_activation.activate(&matrix::add(
+        &matrix::slice_columns(&x, outputs, 2 * outputs),
+        &matrix::slice_columns(&h, outputs, 2 * outputs),
+    ));
+    let candidate = gru.activation.activate(&matrix::add(
+        &matrix::slice_columns(&x, 2 * outputs, 3 * outputs),
+        &matrix::element_wise_multiply(
+            &reset,
+            &matrix::slice_columns(&h, 2 * outputs, 3 * outputs),
+        ),
+    ));
+
+    // (1 - update) * candidate + update * hidden
+    let keep = matrix::element_wise_add(&matrix::scalar(&update, -1.0), 1.0);
+    let output = matrix::add(
+        &matrix::element_wise_multiply(&keep, &candidate),
+        &matrix::element_wise_multiply(&update, &state.hidden),
+    );
+
+    state.hidden = output.clone();
+
+    output
+}
+
+pub fn add<T: Activate + Clone>(a: &Gru<T>, b: &Gru<T>) -> Gru<T> {
+    let mut c = a.clone();
+    c.input_weights = matrix::add(&a.input_weights, &b.input_weights);
+    c.hidden_weights = matrix::add(&a.hidden_weights, &b.hidden_weights);
+    c.bias = matrix::add(&a.bias, &b.bias);
+
+    c
+}
+
+pub fn scalar<T: Activate + Clone>(a: &Gru<T>, s: f32) -> Gru<T> {
+    let mut b = a.clone();
+    b.input_weights = matrix::scalar(&b.input_weights, s);
+    b.hidden_weights = matrix::scalar(&b.hidden_weights, s);
+    b.bias = matrix::scalar(&b.bias, s);
+
+    b
+}
+
+pub fn save<T: Activate>(a: &Gru<T>, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    matrix::save(
+        &a.input_weights,
+        (dir_name.to_owned() + "/input_weights.bin").as_str(),
+    );
+    matrix::save(
+        &a.hidden_weights,
+        (dir_name.to_owned() + "/hidden_weights.bin").as_str(),
+    );
+    matrix::save(&a.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+}
+
+pub fn load<T: Activate + Clone>(a: &Gru<T>, dir_name: &str) -> Gru<T> {
+    let mut b = a.clone();
+    b.input_weights = matrix::load(
+        &b.input_weights,
+        (dir_name.to_owned() + "/input_weights.bin").as_str(),
+    );
+    b.hidden_weights = matrix::load(
+        &b.hidden_weights,
+        (dir_name.to_owned() + "/hidden_weights.bin").as_str(),
+    );
+    b.bias = matrix::load(&b.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+
+    b
+}
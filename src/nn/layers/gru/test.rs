@@ -0,0 +1,123 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
+use crate::nn::layers::gru;
+#[cfg(test)]
+use std::fs;
+
+#[cfg(test)]
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+#[test]
+fn new_test() {
+    let a = gru::new(2, 3, tanh::new());
+
+    assert_eq!(a.input_weights.rows, 2);
+    assert_eq!(a.input_weights.columns, 9);
+    assert_eq!(a.hidden_weights.rows, 3);
+    assert_eq!(a.hidden_weights.columns, 9);
+    assert_eq!(a.bias.rows, 1);
+    assert_eq!(a.bias.columns, 9);
+    assert_eq!(a.bias.value, [0.0; 9]);
+
+    let state = gru::new_state(&a);
+    assert_eq!(state.hidden.rows, 1);
+    assert_eq!(state.hidden.columns, 3);
+}
+
+#[test]
+fn new_gaussian_noise_test() {
+    let a = gru::new_gaussian_noise(2, 3, tanh::new());
+
+    assert_eq!(a.input_weights.rows, 2);
+    assert_eq!(a.input_weights.columns, 9);
+    assert_ne!(a.bias.value, [0.0; 9]);
+}
+
+#[test]
+fn print_test() {
+    let a = gru::new_gaussian_noise(2, 3, tanh::new());
+    // pass = does not panic
+    gru::print(&a);
+}
+
+#[test]
+fn feedforward_test() {
+    let mut a = gru::new(1, 1, tanh::new());
+    // update, reset, candidate
+    a.input_weights.value = vec![0.5, -1.0, 2.0];
+    a.hidden_weights.value = vec![1.0, 0.5, -0.5];
+    a.bias.value = vec![0.1, 0.2, -0.3];
+
+    let mut state = gru::new_state(&a);
+
+    let mut input = matrix::new(1, 1);
+    input.value = vec![1.5];
+
+    let mut h: f32 = 0.0;
+    for _ in 0..3 {
+        let update = sigmoid(1.5 * 0.5 + h * 1.0 + 0.1);
+        let reset = sigmoid(-1.5 + h * 0.5 + 0.2);
+        let candidate = (1.5 * 2.0 + reset * (h * -0.5) - 0.3).tanh();
+        h = (1.0 - update) * candidate + update * h;
+
+        let output = gru::feedforward(&a, &mut state, &input);
+        assert_eq!(output.rows, 1);
+        assert_eq!(output.columns, 1);
+        assert!((output.value[0] - h).abs() < 1e-6);
+        assert_eq!(state.hidden.value, output.value);
+    }
+
+    gru::reset_state(&mut state);
+    assert_eq!(state.hidden.value, [0.0]);
+}
+
+#[test]
+fn add_test() {
+    let a = gru::new_gaussian_noise(2, 3, tanh::new());
+    let b = gru::new_gaussian_noise(2, 3, tanh::new());
+
+    let c = gru::add(&a, &b);
+    for (i, val) in c.input_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.input_weights.value[i] + b.input_weights.value[i]);
+    }
+    for (i, val) in c.hidden_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.hidden_weights.value[i] + b.hidden_weights.value[i]);
+    }
+    for (i, val) in c.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] + b.bias.value[i]);
+    }
+}
+
+#[test]
+fn scalar_test() {
+    let a = gru::new_gaussian_noise(2, 3, tanh::new());
+
+    let b = gru::scalar(&a, 0.5);
+    for (i, val) in b.input_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.input_weights.value[i] * 0.5);
+    }
+    for (i, val) in b.hidden_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.hidden_weights.value[i] * 0.5);
+    }
+    for (i, val) in b.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] * 0.5);
+    }
+}
+
+#[test]
+fn save_load_test() {
+    let a = gru::new_gaussian_noise(2, 3, tanh::new());
+
+    gru::save(&a, "gru");
+    let b = gru::load(&gru::new(2, 3, tanh::new()), "gru");
+    fs::remove_dir_all("gru").unwrap();
+
+    assert_eq!(b.input_weights.value, a.input_weights.value);
+    assert_eq!(b.hidden_weights.value, a.hidden_weights.value);
+    assert_eq!(b.bias.value, a.bias.value);
+}
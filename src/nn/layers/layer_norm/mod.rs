@@ -0,0 +1,136 @@
+pub mod test;
+
+use crate::matrix;
+use std::fs;
+
+// normalizes each input by its own mean and variance, then applies an evolvable gain and bias
+// feedforward works on batches of features rows, each normalized on its own,
+// feedforward_2d on conv channels where features = channels
+#[derive(Clone)]
+pub struct LayerNorm {
+    pub gain: matrix::Matrix,
+    pub bias: matrix::Matrix,
+    pub epsilon: f32,
+}
+
+pub fn new(features: usize) -> LayerNorm {
+    LayerNorm {
+        gain: matrix::element_wise_add(&matrix::new(1, features), 1.0),
+        bias: matrix::new(1, features),
+        epsilon: 1e-5,
+    }
+}
+
+pub fn new_gaussian_noise(features: usize) -> LayerNorm {
+    LayerNorm {
+        gain: matrix::new_gaussian_noise(1, features),
+        bias: matrix::new_gaussian_noise(1, features),
+        epsilon: 1e-5,
+    }
+}
+
+pub fn print(a: &LayerNorm) {
+    println!("Layer Norm");
+    println!("Gain");
+    matrix::print(&a.gain);
+    println!("Bias");
+    matrix::print(&a.bias);
+    println!("Epsilon: {}", a.epsilon);
+}
+
+pub fn feedforward(layer_norm: &LayerNorm, input: &matrix::Matrix) -> matrix::Matrix {
+    assert!(
+        input.columns == layer_norm.gain.columns,
+        "Input columns and number of features must match."
+    );
+
+    // column major, the features of a row are strided by the number of rows
+    let mut output = input.clone();
+    for row in 0..input.rows {
+        let mut values = matrix::new(1, input.columns);
+        for (j, value) in values.value.iter_mut().enumerate() {
+            *value = input.value[j * input.rows + row];
+        }
+        let mean = matrix::mean(&values);
+        let std = (matrix::variance(&values, mean) + layer_norm.epsilon).sqrt();
+
+        for (j, value) in values.value.iter().enumerate() {
+            output.value[j * input.rows + row] =
+                (value - mean) / std * layer_norm.gain.value[j] + layer_norm.bias.value[j];
+        }
+    }
+
+    output
+}
+
+pub fn feedforward_2d(layer_norm: &LayerNorm, input: &[matrix::Matrix]) -> Vec<matrix::Matrix> {
+    assert!(
+        input.len() == layer_norm.gain.columns,
+        "Input depth and number of features must match."
+    );
+
+    // every channel has the same size so the mean of the channel means is the overall mean
+    let mut mean: f32 = 0.0;
+    for channel in input.iter() {
+        mean += matrix::mean(channel);
+    }
+    mean /= input.len() as f32;
+
+    let mut variance: f32 = 0.0;
+    for channel in input.iter() {
+        variance += matrix::variance(channel, mean);
+    }
+    variance /= input.len() as f32;
+    let std = (variance + layer_norm.epsilon).sqrt();
+
+    let mut output: Vec<matrix::Matrix> = Vec::with_capacity(input.len());
+    for (i, channel) in input.iter().enumerate() {
+        let mut b = matrix::scalar(
+            &matrix::element_wise_add(channel, -mean),
+            layer_norm.gain.value[i] / std,
+        );
+        b = matrix::element_wise_add(&b, layer_norm.bias.value[i]);
+        output.push(b);
+    }
+
+    output
+}
+
+pub fn add(a: &LayerNorm, b: &LayerNorm) -> LayerNorm {
+    let mut c = a.clone();
+    c.gain = matrix::add(&a.gain, &b.gain);
+    c.bias = matrix::add(&a.bias, &b.bias);
+
+    c
+}
+
+pub fn scalar(a: &LayerNorm, s: f32) -> LayerNorm {
+    let mut b = a.clone();
+    b.gain = matrix::scalar(&b.gain, s);
+    b.bias = matrix::scalar(&b.bias, s);
+
+    b
+}
+
+pub fn parameters(a: &LayerNorm) -> Vec<(&str, &matrix::Matrix)> {
+    vec![("gain", &a.gain), ("bias", &a.bias)]
+}
+
+// same order as parameters
+pub fn parameters_mut(a: &mut LayerNorm) -> Vec<&mut matrix::Matrix> {
+    vec![&mut a.gain, &mut a.bias]
+}
+
+pub fn save(a: &LayerNorm, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    matrix::save(&a.gain, (dir_name.to_owned() + "/gain.bin").as_str());
+    matrix::save(&a.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+}
+
+pub fn load(a: &LayerNorm, dir_name: &str) -> LayerNorm {
+    let mut b = a.clone();
+    b.gain = matrix::load(&b.gain, (dir_name.to_owned() + "/gain.bin").as_str());
+    b.bias = matrix::load(&b.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+
+    b
+}
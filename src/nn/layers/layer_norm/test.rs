@@ -0,0 +1,168 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::layers::layer_norm;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn new_test() {
+    let a = layer_norm::new(3);
+
+    assert_eq!(a.gain.rows, 1);
+    assert_eq!(a.gain.columns, 3);
+    assert_eq!(a.gain.value, [1.0, 1.0, 1.0]);
+    assert_eq!(a.bias.value, [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn new_gaussian_noise_test() {
+    let a = layer_norm::new_gaussian_noise(3);
+
+    assert_eq!(a.gain.columns, 3);
+    assert_ne!(a.gain.value, [0.0, 0.0, 0.0]);
+    assert_ne!(a.bias.value, [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn print_test() {
+    let a = layer_norm::new_gaussian_noise(3);
+    // pass = does not panic
+    layer_norm::print(&a);
+}
+
+#[test]
+fn feedforward_test() {
+    let mut a = layer_norm::new(4);
+    a.gain.value = vec![1.0, 2.0, 0.5, -1.0];
+    a.bias.value = vec![0.0, 1.0, -1.0, 0.5];
+
+    let mut input = matrix::new(1, 4);
+    input.value = vec![2.0, 4.0, 1.0, 7.0];
+
+    // mean = 3.5, variance = 5.25
+    let std = (5.25_f32 + a.epsilon).sqrt();
+    let expected_output = [
+        (2.0 - 3.5) / std,
+        (4.0 - 3.5) / std * 2.0 + 1.0,
+        (1.0 - 3.5) / std * 0.5 - 1.0,
+        -(7.0 - 3.5) / std + 0.5,
+    ];
+
+    let output = layer_norm::feedforward(&a, &input);
+    assert_eq!(output.rows, 1);
+    assert_eq!(output.columns, 4);
+    for (i, val) in output.value.iter().enumerate() {
+        assert!((val - expected_output[i]).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn feedforward_batch_test() {
+    let a = layer_norm::new_gaussian_noise(4);
+    let mut input = matrix::new(3, 4);
+    input.value = vec![
+        2.0, 0.0, -1.0, 4.0, 10.0, 2.0, 1.0, 20.0, -3.0, 7.0, 30.0, 0.5,
+    ];
+
+    // every row is normalized with its own statistics, as if it was fed alone
+    let output = layer_norm::feedforward(&a, &input);
+    assert_eq!(output.rows, 3);
+    assert_eq!(output.columns, 4);
+    for row in 0..3 {
+        let mut single = matrix::new(1, 4);
+        for j in 0..4 {
+            single.value[j] = input.value[j * 3 + row];
+        }
+        let expected_output = layer_norm::feedforward(&a, &single);
+        for j in 0..4 {
+            assert!((output.value[j * 3 + row] - expected_output.value[j]).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn feedforward_scale_invariance_test() {
+    let a = layer_norm::new_gaussian_noise(5);
+    let input = matrix::new_gaussian_noise(1, 5);
+
+    // a large perturbation of the previous layer does not change the output scale
+    let output = layer_norm::feedforward(&a, &input);
+    let scaled_output = layer_norm::feedforward(&a, &matrix::scalar(&input, 1000.0));
+    for (i, val) in output.value.iter().enumerate() {
+        assert!((val - scaled_output.value[i]).abs() < 1e-3);
+    }
+}
+
+#[test]
+#[should_panic]
+fn feedforward_features_panic_test() {
+    let a = layer_norm::new(3);
+    let _ = layer_norm::feedforward(&a, &matrix::new(1, 4));
+}
+
+#[test]
+fn feedforward_2d_test() {
+    let mut a = layer_norm::new(2);
+    a.gain.value = vec![2.0, 1.0];
+    a.bias.value = vec![0.0, -1.0];
+
+    let mut input = vec![matrix::new(1, 2), matrix::new(1, 2)];
+    input[0].value = vec![2.0, 4.0];
+    input[1].value = vec![1.0, 7.0];
+
+    let std = (5.25_f32 + a.epsilon).sqrt();
+    let output = layer_norm::feedforward_2d(&a, &input);
+    assert_eq!(output.len(), 2);
+
+    let expected_output = [
+        [(2.0 - 3.5) / std * 2.0, (4.0 - 3.5) / std * 2.0],
+        [(1.0 - 3.5) / std - 1.0, (7.0 - 3.5) / std - 1.0],
+    ];
+    for (i, channel) in output.iter().enumerate() {
+        assert_eq!(channel.rows, 1);
+        assert_eq!(channel.columns, 2);
+        for (j, val) in channel.value.iter().enumerate() {
+            assert!((val - expected_output[i][j]).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn add_test() {
+    let a = layer_norm::new_gaussian_noise(3);
+    let b = layer_norm::new_gaussian_noise(3);
+
+    let c = layer_norm::add(&a, &b);
+    for (i, val) in c.gain.value.iter().enumerate() {
+        assert_eq!(*val, a.gain.value[i] + b.gain.value[i]);
+    }
+    for (i, val) in c.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] + b.bias.value[i]);
+    }
+}
+
+#[test]
+fn scalar_test() {
+    let a = layer_norm::new_gaussian_noise(3);
+
+    let b = layer_norm::scalar(&a, 0.5);
+    for (i, val) in b.gain.value.iter().enumerate() {
+        assert_eq!(*val, a.gain.value[i] * 0.5);
+    }
+    for (i, val) in b.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] * 0.5);
+    }
+}
+
+#[test]
+fn save_load_test() {
+    let a = layer_norm::new_gaussian_noise(3);
+
+    layer_norm::save(&a, "layer_norm");
+    let b = layer_norm::load(&layer_norm::new(3), "layer_norm");
+    fs::remove_dir_all("layer_norm").unwrap();
+
+    assert_eq!(b.gain.value, a.gain.value);
+    assert_eq!(b.bias.value, a.bias.value);
+}
@@ -0,0 +1,150 @@
+pub mod test;
+
+use crate::matrix;
+use crate::nn::activations::sigmoid;
+use crate::nn::activations::Activate;
+use std::fs;
+
+// gates are stored side by side in the columns of each matrix
+// columns 0..outputs = input gate, outputs..2 * outputs = forget gate,
+// 2 * outputs..3 * outputs = cell candidate, 3 * outputs..4 * outputs = output gate
+// cell = forget * cell + input * activation(candidate)
+// hidden = output * activation(cell)
+#[derive(Clone)]
+pub struct Lstm<T: Activate> {
+    pub input_weights: matrix::Matrix,
+    pub hidden_weights: matrix::Matrix,
+    pub bias: matrix::Matrix,
+    pub activation: T,
+}
+
+#[derive(Clone)]
+pub struct LstmState {
+    pub hidden: matrix::Matrix,
+    pub cell: matrix::Matrix,
+}
+
+pub fn new<T: Activate>(inputs: usize, outputs: usize, activation: T) -> Lstm<T> {
+    Lstm {
+        input_weights: matrix::new(inputs, 4 * outputs),
+        hidden_weights: matrix::new(outputs, 4 * outputs),
+        bias: matrix::new(1, 4 * outputs),
+        activation,
+    }
+}
+
+pub fn new_gaussian_noise<T: Activate>(inputs: usize, outputs: usize, activation: T) -> Lstm<T> {
+    Lstm {
+        input_weights: matrix::new_gaussian_noise(inputs, 4 * outputs),
+        hidden_weights: matrix::new_gaussian_noise(outputs, 4 * outputs),
+        bias: matrix::new_gaussian_noise(1, 4 * outputs),
+        activation,
+    }
+}
+
+pub fn new_state<T: Activate>(a: &Lstm<T>) -> LstmState {
+    LstmState {
+        hidden: matrix::new(1, a.hidden_weights.rows),
+        cell: matrix::new(1, a.hidden_weights.rows),
+    }
+}
+
+// call at the start of every episode
+pub fn reset_state(state: &mut LstmState) {
+    state.hidden = matrix::new(state.hidden.rows, state.hidden.columns);
+    state.cell = matrix::new(state.cell.rows, state.cell.columns);
+}
+
+pub fn print<T: Activate>(a: &Lstm<T>) {
+    println!("Lstm Layer");
+    println!("Input Weights");
+    matrix::print(&a.input_weights);
+    println!("Hidden Weights");
+    matrix::print(&a.hidden_weights);
+    println!("Bias");
+    matrix::print(&a.bias);
+    print!("Activation: ");
+    a.activation.print();
+}
+
+pub fn feedforward<T: Activate>(
+    lstm: &Lstm<T>,
+    state: &mut LstmState,
+    input: &matrix::Matrix,
+) -> matrix::Matrix {
+    let outputs = lstm.hidden_weights.rows;
+    let gate_activation = sigmoid::new();
+
+    let mut gates = matrix::multiply(input, &lstm.input_weights);
+    gates = matrix::add(
+        &gates,
+        &matrix::multiply(&state.hidden, &lstm.hidden_weights),
+    );
+    gates = matrix::add(&gates, &lstm.bias);
+
+    let input_gate = gate_activation.activate(&matrix::slice_columns(&gates, 0, outputs));
+    let forget_gate =
+        gate_activation.activate(&matrix::slice_columns(&gates, outputs, 2 * outputs));
+    let candidate =
+        lstm.activation
+            .activate(&matrix::slice_columns(&gates, 2 * outputs, 3 * outputs));
+    let output_gate =
+        gate_activation.activate(&matrix::slice_columns(&gates, 3 * outputs, 4 * outputs));
+
+    state.cell = matrix::add(
+        &matrix::element_wise_multiply(&forget_gate, &state.cell),
+        &matrix::element_wise_multiply(&input_gate, &candidate),
+    );
+    let output =
+        matrix::element_wise_multiply(&output_gate, &lstm.activation.activate(&state.cell));
+
+    state.hidden = output.clone();
+
+    output
+}
+
+pub fn add<T: Activate + Clone>(a: &Lstm<T>, b: &Lstm<T>) -> Lstm<T> {
+    let mut c = a.clone();
+    c.input_weights = matrix::add(&a.input_weights, &b.input_weights);
+    c.hidden_weights = matrix::add(&a.hidden_weights, &b.hidden_weights);
+    c.bias = matrix::add(&a.bias, &b.bias);
+
+    c
+}
+
+pub fn scalar<T: Activate + Clone>(a: &Lstm<T>, s: f32) -> Lstm<T> {
+    let mut b = a.clone();
+    b.input_weights = matrix::scalar(&b.input_weights, s);
+    b.hidden_weights = matrix::scalar(&b.hidden_weights, s);
+    b.bias = matrix::scalar(&b.bias, s);
+
+    b
+}
+
+pub fn save<T: Activate>(a: &Lstm<T>, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    matrix::save(
+        &a.input_weights,
+        (dir_name.to_owned() + "/input_weights.bin").as_str(),
+    );
+    matrix::save(
+        &a.hidden_weights,
+        (dir_name.to_owned() + "/hidden_weights.bin").as_str(),
+    );
+    matrix::save(&a.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+}
+
+pub fn load<T: Activate + Clone>(a: &Lstm<T>, dir_name: &str) -> Lstm<T> {
+    let mut b = a.clone();
+    b.input_weights = matrix::load(
+        &b.input_weights,
+        (dir_name.to_owned() + "/input_weights.bin").as_str(),
+    );
+    b.hidden_weights = matrix::load(
+        &b.hidden_weights,
+        (dir_name.to_owned() + "/hidden_weights.bin").as_str(),
+    );
+    b.bias = matrix::load(&b.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+
+    b
+}
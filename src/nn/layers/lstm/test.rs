@@ -0,0 +1,127 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
+use crate::nn::layers::lstm;
+#[cfg(test)]
+use std::fs;
+
+#[cfg(test)]
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+#[test]
+fn new_test() {
+    let a = lstm::new(2, 3, tanh::new());
+
+    assert_eq!(a.input_weights.rows, 2);
+    assert_eq!(a.input_weights.columns, 12);
+    assert_eq!(a.hidden_weights.rows, 3);
+    assert_eq!(a.hidden_weights.columns, 12);
+    assert_eq!(a.bias.rows, 1);
+    assert_eq!(a.bias.columns, 12);
+    assert_eq!(a.bias.value, [0.0; 12]);
+
+    let state = lstm::new_state(&a);
+    assert_eq!(state.hidden.columns, 3);
+    assert_eq!(state.cell.columns, 3);
+}
+
+#[test]
+fn new_gaussian_noise_test() {
+    let a = lstm::new_gaussian_noise(2, 3, tanh::new());
+
+    assert_eq!(a.input_weights.rows, 2);
+    assert_eq!(a.input_weights.columns, 12);
+    assert_ne!(a.bias.value, [0.0; 12]);
+}
+
+#[test]
+fn print_test() {
+    let a = lstm::new_gaussian_noise(2, 3, tanh::new());
+    // pass = does not panic
+    lstm::print(&a);
+}
+
+#[test]
+fn feedforward_test() {
+    let mut a = lstm::new(1, 1, tanh::new());
+    // input, forget, candidate, output
+    a.input_weights.value = vec![0.5, -1.0, 2.0, 1.0];
+    a.hidden_weights.value = vec![1.0, 0.5, -0.5, 0.25];
+    a.bias.value = vec![0.1, 0.2, -0.3, 0.0];
+
+    let mut state = lstm::new_state(&a);
+
+    let mut input = matrix::new(1, 1);
+    input.value = vec![1.5];
+
+    let mut h: f32 = 0.0;
+    let mut c: f32 = 0.0;
+    for _ in 0..3 {
+        let i = sigmoid(1.5 * 0.5 + h * 1.0 + 0.1);
+        let f = sigmoid(-1.5 + h * 0.5 + 0.2);
+        let g = (1.5 * 2.0 + h * -0.5 - 0.3).tanh();
+        let o = sigmoid(1.5 * 1.0 + h * 0.25);
+        c = f * c + i * g;
+        h = o * c.tanh();
+
+        let output = lstm::feedforward(&a, &mut state, &input);
+        assert_eq!(output.rows, 1);
+        assert_eq!(output.columns, 1);
+        assert!((output.value[0] - h).abs() < 1e-6);
+        assert!((state.cell.value[0] - c).abs() < 1e-6);
+    }
+
+    lstm::reset_state(&mut state);
+    assert_eq!(state.hidden.value, [0.0]);
+    assert_eq!(state.cell.value, [0.0]);
+}
+
+#[test]
+fn add_test() {
+    let a = lstm::new_gaussian_noise(2, 3, tanh::new());
+    let b = lstm::new_gaussian_noise(2, 3, tanh::new());
+
+    let c = lstm::add(&a, &b);
+    for (i, val) in c.input_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.input_weights.value[i] + b.input_weights.value[i]);
+    }
+    for (i, val) in c.hidden_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.hidden_weights.value[i] + b.hidden_weights.value[i]);
+    }
+    for (i, val) in c.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] + b.bias.value[i]);
+    }
+}
+
+#[test]
+fn scalar_test() {
+    let a = lstm::new_gaussian_noise(2, 3, tanh::new());
+
+    let b = lstm::scalar(&a, 0.5);
+    for (i, val) in b.input_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.input_weights.value[i] * 0.5);
+    }
+    for (i, val) in b.hidden_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.hidden_weights.value[i] * 0.5);
+    }
+    for (i, val) in b.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] * 0.5);
+    }
+}
+
+#[test]
+fn save_load_test() {
+    let a = lstm::new_gaussian_noise(2, 3, tanh::new());
+
+    lstm::save(&a, "lstm");
+    let b = lstm::load(&lstm::new(2, 3, tanh::new()), "lstm");
+    fs::remove_dir_all("lstm").unwrap();
+
+    assert_eq!(b.input_weights.value, a.input_weights.value);
+    assert_eq!(b.hidden_weights.value, a.hidden_weights.value);
+    assert_eq!(b.bias.value, a.bias.value);
+}
@@ -1,3 +1,10 @@
+pub mod batch_norm;
 pub mod conv2d;
+pub mod ctrnn;
 pub mod fully_connected;
+pub mod gru;
+pub mod layer_norm;
+pub mod lstm;
 pub mod res2d;
+pub mod rnn;
+pub mod virtual_batch_norm;
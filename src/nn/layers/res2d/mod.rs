@@ -1,7 +1,10 @@
+pub mod test;
+
 use crate::matrix;
 use crate::nn::activations::no_activation;
 use crate::nn::activations::Activate;
 use crate::nn::layers::conv2d;
+use std::fs;
 
 #[derive(Clone)]
 pub struct Res2D<T: Activate> {
@@ -63,6 +66,68 @@ pub fn feedforward<T: Activate>(a: &Res2D<T>, input: &[matrix::Matrix]) -> Vec<m
     output
 }
 
+// returns the gradient of every conv2d stored in a Res2D and dL/dinput from dL/doutput
+pub fn backward<T: Activate + Clone>(
+    a: &Res2D<T>,
+    input: &[matrix::Matrix],
+    grad_output: &[matrix::Matrix],
+) -> (Res2D<T>, Vec<matrix::Matrix>) {
+    // keep the input to every layer for the backward pass
+    let mut layer_inputs: Vec<Vec<matrix::Matrix>> = Vec::with_capacity(a.strides.len());
+    layer_inputs.push(input.to_vec());
+    for (i, layer) in a.layers.iter().enumerate() {
+        let output = conv2d::feedforward(layer, &layer_inputs[i], a.strides[i], a.padding[i]);
+        layer_inputs.push(output);
+    }
+
+    let last = a.strides.len() - 1;
+    let mut pre_activation = conv2d::feedforward(
+        &a.last_layer,
+        &layer_inputs[last],
+        a.strides[last],
+        a.padding[last],
+    );
+    assert!(input.len() == pre_activation.len());
+    for (i, m) in pre_activation.iter_mut().enumerate() {
+        *m = matrix::add(m, &input[i]);
+    }
+
+    let mut grad: Vec<matrix::Matrix> = Vec::with_capacity(grad_output.len());
+    for (i, m) in grad_output.iter().enumerate() {
+        grad.push(a.activation.backward(&pre_activation[i], m));
+    }
+    // the skip connection passes the gradient straight through to the input
+    let grad_skip = grad.clone();
+
+    let mut gradient = a.clone();
+    let (last_gradient, mut grad) = conv2d::backward(
+        &a.last_layer,
+        &layer_inputs[last],
+        a.strides[last],
+        a.padding[last],
+        &grad,
+    );
+    gradient.last_layer = last_gradient;
+
+    for i in (0..a.layers.len()).rev() {
+        let (layer_gradient, layer_grad) = conv2d::backward(
+            &a.layers[i],
+            &layer_inputs[i],
+            a.strides[i],
+            a.padding[i],
+            &grad,
+        );
+        gradient.layers[i] = layer_gradient;
+        grad = layer_grad;
+    }
+
+    for (i, m) in grad.iter_mut().enumerate() {
+        *m = matrix::add(m, &grad_skip[i]);
+    }
+
+    (gradient, grad)
+}
+
 pub fn add<T: Activate + Clone>(a: &Res2D<T>, b: &Res2D<T>) -> Res2D<T> {
     let mut c = a.clone();
 
@@ -84,3 +149,59 @@ pub fn scalar<T: Activate + Clone>(a: &Res2D<T>, s: f32) -> Res2D<T> {
 
     b
 }
+
+pub fn parameters<T: Activate>(a: &Res2D<T>) -> Vec<(String, &matrix::Matrix)> {
+    let mut b: Vec<(String, &matrix::Matrix)> = Vec::new();
+    for (i, layer) in a.layers.iter().enumerate() {
+        for (name, m) in conv2d::parameters(layer) {
+            b.push((format!("layers{}.{}", i, name), m));
+        }
+    }
+    for (name, m) in conv2d::parameters(&a.last_layer) {
+        b.push((format!("last_layer.{}", name), m));
+    }
+
+    b
+}
+
+// same order as parameters
+pub fn parameters_mut<T: Activate>(a: &mut Res2D<T>) -> Vec<&mut matrix::Matrix> {
+    let mut b: Vec<&mut matrix::Matrix> = Vec::new();
+    for layer in a.layers.iter_mut() {
+        b.append(&mut conv2d::parameters_mut(layer));
+    }
+    b.append(&mut conv2d::parameters_mut(&mut a.last_layer));
+
+    b
+}
+
+pub fn save<T: Activate>(a: &Res2D<T>, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    for (i, layer) in a.layers.iter().enumerate() {
+        conv2d::save(
+            layer,
+            (dir_name.to_owned() + "/layers" + &i.to_string()).as_str(),
+        );
+    }
+    conv2d::save(
+        &a.last_layer,
+        (dir_name.to_owned() + "/last_layer").as_str(),
+    );
+}
+
+pub fn load<T: Activate + Clone>(a: &Res2D<T>, dir_name: &str) -> Res2D<T> {
+    let mut b = a.clone();
+
+    for (i, layer) in b.layers.iter_mut().enumerate() {
+        *layer = conv2d::load(
+            layer,
+            (dir_name.to_owned() + "/layers" + &i.to_string()).as_str(),
+        );
+    }
+    b.last_layer = conv2d::load(
+        &b.last_layer,
+        (dir_name.to_owned() + "/last_layer").as_str(),
+    );
+
+    b
+}
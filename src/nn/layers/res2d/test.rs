@@ -0,0 +1,136 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::no_activation;
+#[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
+use crate::nn::layers::conv2d;
+#[cfg(test)]
+use crate::nn::layers::res2d;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn backward_test() {
+    let res = res2d::new(
+        &[conv2d::new_gaussian_noise(2, 2, (3, 3), tanh::new())],
+        &conv2d::new_gaussian_noise(2, 2, (3, 3), no_activation::new()),
+        &[(1, 1), (1, 1)],
+        &[(1, 1, 1, 1), (1, 1, 1, 1)],
+        tanh::new(),
+    );
+    let input = vec![
+        matrix::new_gaussian_noise(4, 4),
+        matrix::new_gaussian_noise(4, 4),
+    ];
+
+    // loss = sum(output * direction) so dL/doutput = direction
+    let direction = vec![
+        matrix::new_gaussian_noise(4, 4),
+        matrix::new_gaussian_noise(4, 4),
+    ];
+    let loss = |r: &res2d::Res2D<tanh::Tanh>, x: &[matrix::Matrix]| -> f32 {
+        let output = res2d::feedforward(r, x);
+        let mut sum: f32 = 0.0;
+        for (i, m) in output.iter().enumerate() {
+            sum += matrix::element_wise_multiply(m, &direction[i])
+                .value
+                .iter()
+                .sum::<f32>();
+        }
+        sum
+    };
+
+    let (gradient, grad_input) = res2d::backward(&res, &input, &direction);
+    assert!(gradient.layers.len() == 1);
+    assert!(grad_input.len() == 2);
+
+    let h = 1e-2;
+    for i in 0..res.layers[0].filters.value.len() {
+        let mut up = res.clone();
+        let mut down = res.clone();
+        up.layers[0].filters.value[i] += h;
+        down.layers[0].filters.value[i] -= h;
+        let expected = (loss(&up, &input) - loss(&down, &input)) / (2.0 * h);
+        assert!((gradient.layers[0].filters.value[i] - expected).abs() < 1e-2);
+    }
+    for i in 0..res.last_layer.bias.value.len() {
+        let mut up = res.clone();
+        let mut down = res.clone();
+        up.last_layer.bias.value[i] += h;
+        down.last_layer.bias.value[i] -= h;
+        let expected = (loss(&up, &input) - loss(&down, &input)) / (2.0 * h);
+        assert!((gradient.last_layer.bias.value[i] - expected).abs() < 1e-2);
+    }
+    for c in 0..input.len() {
+        for i in 0..input[c].value.len() {
+            let mut up = input.to_vec();
+            let mut down = input.to_vec();
+            up[c].value[i] += h;
+            down[c].value[i] -= h;
+            let expected = (loss(&res, &up) - loss(&res, &down)) / (2.0 * h);
+            assert!((grad_input[c].value[i] - expected).abs() < 1e-2);
+        }
+    }
+}
+
+#[test]
+fn parameters_test() {
+    let mut res = res2d::new(
+        &[conv2d::new_gaussian_noise(2, 2, (3, 3), tanh::new())],
+        &conv2d::new_gaussian_noise(2, 2, (3, 3), no_activation::new()),
+        &[(1, 1), (1, 1)],
+        &[(1, 1, 1, 1), (1, 1, 1, 1)],
+        tanh::new(),
+    );
+
+    let names: Vec<String> = res2d::parameters(&res)
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect();
+    assert!(
+        names
+            == [
+                "layers0.filters",
+                "layers0.bias",
+                "last_layer.filters",
+                "last_layer.bias"
+            ]
+    );
+
+    let parameters = res2d::parameters_mut(&mut res);
+    assert!(parameters.len() == 4);
+    for m in parameters {
+        m.value[0] = 5.0;
+    }
+    assert!(res.layers[0].filters.value[0] == 5.0);
+    assert!(res.last_layer.bias.value[0] == 5.0);
+}
+
+#[test]
+fn save_load_test() {
+    let res = res2d::new(
+        &[conv2d::new_gaussian_noise(2, 2, (3, 3), tanh::new())],
+        &conv2d::new_gaussian_noise(2, 2, (3, 3), no_activation::new()),
+        &[(1, 1), (1, 1)],
+        &[(1, 1, 1, 1), (1, 1, 1, 1)],
+        tanh::new(),
+    );
+
+    res2d::save(&res, "res2d_save_load_test");
+    let template = res2d::new(
+        &[conv2d::new(2, 2, (3, 3), tanh::new())],
+        &conv2d::new(2, 2, (3, 3), no_activation::new()),
+        &[(1, 1), (1, 1)],
+        &[(1, 1, 1, 1), (1, 1, 1, 1)],
+        tanh::new(),
+    );
+    let b = res2d::load(&template, "res2d_save_load_test");
+    fs::remove_dir_all("res2d_save_load_test").unwrap();
+
+    assert_eq!(b.layers[0].filters.value, res.layers[0].filters.value);
+    assert_eq!(b.layers[0].bias.value, res.layers[0].bias.value);
+    assert_eq!(b.last_layer.filters.value, res.last_layer.filters.value);
+    assert_eq!(b.last_layer.bias.value, res.last_layer.bias.value);
+}
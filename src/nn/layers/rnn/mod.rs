@@ -0,0 +1,126 @@
+pub mod test;
+
+use crate::matrix;
+use crate::nn::activations::Activate;
+use std::fs;
+
+// Elman recurrent layer
+// hidden = activation(input * input_weights + hidden * hidden_weights + bias)
+#[derive(Clone)]
+pub struct Rnn<T: Activate> {
+    pub input_weights: matrix::Matrix,
+    pub hidden_weights: matrix::Matrix,
+    pub bias: matrix::Matrix,
+    pub activation: T,
+}
+
+// the hidden state is kept outside of the layer so one set of weights can drive many episodes
+#[derive(Clone)]
+pub struct RnnState {
+    pub hidden: matrix::Matrix,
+}
+
+pub fn new<T: Activate>(inputs: usize, outputs: usize, activation: T) -> Rnn<T> {
+    Rnn {
+        input_weights: matrix::new(inputs, outputs),
+        hidden_weights: matrix::new(outputs, outputs),
+        bias: matrix::new(1, outputs),
+        activation,
+    }
+}
+
+pub fn new_gaussian_noise<T: Activate>(inputs: usize, outputs: usize, activation: T) -> Rnn<T> {
+    Rnn {
+        input_weights: matrix::new_gaussian_noise(inputs, outputs),
+        hidden_weights: matrix::new_gaussian_noise(outputs, outputs),
+        bias: matrix::new_gaussian_noise(1, outputs),
+        activation,
+    }
+}
+
+pub fn new_state<T: Activate>(a: &Rnn<T>) -> RnnState {
+    RnnState {
+        hidden: matrix::new(1, a.hidden_weights.rows),
+    }
+}
+
+// call at the start of every episode
+pub fn reset_state(state: &mut RnnState) {
+    state.hidden = matrix::new(state.hidden.rows, state.hidden.columns);
+}
+
+pub fn print<T: Activate>(a: &Rnn<T>) {
+    println!("Rnn Layer");
+    println!("Input Weights");
+    matrix::print(&a.input_weights);
+    println!("Hidden Weights");
+    matrix::print(&a.hidden_weights);
+    println!("Bias");
+    matrix::print(&a.bias);
+    print!("Activation: ");
+    a.activation.print();
+}
+
+pub fn feedforward<T: Activate>(
+    rnn: &Rnn<T>,
+    state: &mut RnnState,
+    input: &matrix::Matrix,
+) -> matrix::Matrix {
+    let mut output = matrix::multiply(input, &rnn.input_weights);
+    output = matrix::add(
+        &output,
+        &matrix::multiply(&state.hidden, &rnn.hidden_weights),
+    );
+    output = matrix::add(&output, &rnn.bias);
+    output = rnn.activation.activate(&output);
+
+    state.hidden = output.clone();
+
+    output
+}
+
+pub fn add<T: Activate + Clone>(a: &Rnn<T>, b: &Rnn<T>) -> Rnn<T> {
+    let mut c = a.clone();
+    c.input_weights = matrix::add(&a.input_weights, &b.input_weights);
+    c.hidden_weights = matrix::add(&a.hidden_weights, &b.hidden_weights);
+    c.bias = matrix::add(&a.bias, &b.bias);
+
+    c
+}
+
+pub fn scalar<T: Activate + Clone>(a: &Rnn<T>, s: f32) -> Rnn<T> {
+    let mut b = a.clone();
+    b.input_weights = matrix::scalar(&b.input_weights, s);
+    b.hidden_weights = matrix::scalar(&b.hidden_weights, s);
+    b.bias = matrix::scalar(&b.bias, s);
+
+    b
+}
+
+pub fn save<T: Activate>(a: &Rnn<T>, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    matrix::save(
+        &a.input_weights,
+        (dir_name.to_owned() + "/input_weights.bin").as_str(),
+    );
+    matrix::save(
+        &a.hidden_weights,
+        (dir_name.to_owned() + "/hidden_weights.bin").as_str(),
+    );
+    matrix::save(&a.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+}
+
+pub fn load<T: Activate + Clone>(a: &Rnn<T>, dir_name: &str) -> Rnn<T> {
+    let mut b = a.clone();
+    b.input_weights = matrix::load(
+        &b.input_weights,
+        (dir_name.to_owned() + "/input_weights.bin").as_str(),
+    );
+    b.hidden_weights = matrix::load(
+        &b.hidden_weights,
+        (dir_name.to_owned() + "/hidden_weights.bin").as_str(),
+    );
+    b.bias = matrix::load(&b.bias, (dir_name.to_owned() + "/bias.bin").as_str());
+
+    b
+}
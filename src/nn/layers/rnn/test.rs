@@ -0,0 +1,147 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::param_relu;
+#[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
+use crate::nn::activations::Activate;
+#[cfg(test)]
+use crate::nn::layers::rnn;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn new_test() {
+    let a = rnn::new(2, 3, tanh::new());
+
+    assert_eq!(a.input_weights.rows, 2);
+    assert_eq!(a.input_weights.columns, 3);
+    assert_eq!(a.hidden_weights.rows, 3);
+    assert_eq!(a.hidden_weights.columns, 3);
+    assert_eq!(a.bias.rows, 1);
+    assert_eq!(a.bias.columns, 3);
+    assert_eq!(a.input_weights.value, [0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    assert_eq!(a.bias.value, [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn new_gaussian_noise_test() {
+    let a = rnn::new_gaussian_noise(2, 3, tanh::new());
+
+    assert_eq!(a.input_weights.rows, 2);
+    assert_eq!(a.input_weights.columns, 3);
+    assert_eq!(a.hidden_weights.rows, 3);
+    assert_eq!(a.hidden_weights.columns, 3);
+    assert_ne!(a.input_weights.value, [0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    assert_ne!(a.bias.value, [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn print_test() {
+    let a = rnn::new_gaussian_noise(2, 3, tanh::new());
+    // pass = does not panic
+    rnn::print(&a);
+}
+
+#[test]
+fn feedforward_test() {
+    let mut a = rnn::new(2, 2, param_relu::new(1.0, 1.0));
+    a.input_weights.value = vec![1.0, 2.0, -1.0, 0.5];
+    a.hidden_weights.value = vec![0.5, 1.0, 2.0, -1.0];
+    a.bias.value = vec![1.0, -1.0];
+
+    let mut state = rnn::new_state(&a);
+    assert_eq!(state.hidden.value, [0.0, 0.0]);
+
+    let mut input = matrix::new(1, 2);
+    input.value = vec![2.0, 1.0];
+
+    // first step has no contribution from the hidden state
+    let mut expected_output = matrix::multiply(&input, &a.input_weights);
+    expected_output = matrix::add(&expected_output, &a.bias);
+
+    let output = rnn::feedforward(&a, &mut state, &input);
+    assert_eq!(output.rows, 1);
+    assert_eq!(output.columns, 2);
+    assert_eq!(output.value, expected_output.value);
+    assert_eq!(state.hidden.value, expected_output.value);
+
+    // second step uses the previous output
+    let mut expected_second = matrix::multiply(&input, &a.input_weights);
+    expected_second = matrix::add(
+        &expected_second,
+        &matrix::multiply(&expected_output, &a.hidden_weights),
+    );
+    expected_second = matrix::add(&expected_second, &a.bias);
+
+    let second = rnn::feedforward(&a, &mut state, &input);
+    assert_eq!(second.value, expected_second.value);
+
+    rnn::reset_state(&mut state);
+    assert_eq!(state.hidden.value, [0.0, 0.0]);
+    let third = rnn::feedforward(&a, &mut state, &input);
+    assert_eq!(third.value, expected_output.value);
+}
+
+#[test]
+fn feedforward_activation_test() {
+    let t = tanh::new();
+    let a = rnn::new_gaussian_noise(3, 4, tanh::new());
+    let mut state = rnn::new_state(&a);
+
+    let input = matrix::new_gaussian_noise(1, 3);
+
+    let mut expected_output = matrix::multiply(&input, &a.input_weights);
+    expected_output = matrix::add(&expected_output, &a.bias);
+    expected_output = t.activate(&expected_output);
+
+    let output = rnn::feedforward(&a, &mut state, &input);
+    assert_eq!(output.value, expected_output.value);
+}
+
+#[test]
+fn add_test() {
+    let a = rnn::new_gaussian_noise(2, 3, tanh::new());
+    let b = rnn::new_gaussian_noise(2, 3, tanh::new());
+
+    let c = rnn::add(&a, &b);
+    for (i, val) in c.input_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.input_weights.value[i] + b.input_weights.value[i]);
+    }
+    for (i, val) in c.hidden_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.hidden_weights.value[i] + b.hidden_weights.value[i]);
+    }
+    for (i, val) in c.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] + b.bias.value[i]);
+    }
+}
+
+#[test]
+fn scalar_test() {
+    let a = rnn::new_gaussian_noise(2, 3, tanh::new());
+
+    let b = rnn::scalar(&a, 0.5);
+    for (i, val) in b.input_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.input_weights.value[i] * 0.5);
+    }
+    for (i, val) in b.hidden_weights.value.iter().enumerate() {
+        assert_eq!(*val, a.hidden_weights.value[i] * 0.5);
+    }
+    for (i, val) in b.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.bias.value[i] * 0.5);
+    }
+}
+
+#[test]
+fn save_load_test() {
+    let a = rnn::new_gaussian_noise(2, 3, tanh::new());
+
+    rnn::save(&a, "rnn");
+    let b = rnn::load(&rnn::new(2, 3, tanh::new()), "rnn");
+    fs::remove_dir_all("rnn").unwrap();
+
+    assert_eq!(b.input_weights.value, a.input_weights.value);
+    assert_eq!(b.hidden_weights.value, a.hidden_weights.value);
+    assert_eq!(b.bias.value, a.bias.value);
+}
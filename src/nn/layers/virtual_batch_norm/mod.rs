@@ -0,0 +1,120 @@
+pub mod test;
+
+use crate::matrix;
+use crate::nn::activations::Activate;
+use crate::nn::layers::batch_norm;
+use crate::nn::layers::conv2d;
+use std::fs;
+
+// virtual batch norm (Salimans et al. 2017)
+// the statistics come from a reference batch chosen once at startup and stay fixed until
+// set_reference or set_reference_conv2d is called again, add and scalar keep those of a
+// the caller has to set the reference for every candidate with its own weights, then a
+// perturbation cannot blow up the scale of the following layers
+// models::tic_tac_toe does this in its feedforward
+#[derive(Clone)]
+pub struct VirtualBatchNorm {
+    pub norm: batch_norm::BatchNorm,
+}
+
+pub fn new(num_channels: usize) -> VirtualBatchNorm {
+    VirtualBatchNorm {
+        norm: batch_norm::new(num_channels),
+    }
+}
+
+pub fn new_gaussian_noise(num_channels: usize) -> VirtualBatchNorm {
+    VirtualBatchNorm {
+        norm: batch_norm::new_gaussian_noise(num_channels),
+    }
+}
+
+pub fn print(a: &VirtualBatchNorm) {
+    println!("Virtual Batch Norm");
+    batch_norm::print(&a.norm);
+}
+
+// reference_batch holds the inputs to this layer for every sample of the reference batch
+pub fn set_reference(
+    a: &VirtualBatchNorm,
+    reference_batch: &[Vec<matrix::Matrix>],
+) -> VirtualBatchNorm {
+    VirtualBatchNorm {
+        norm: batch_norm::collect_statistics_2d(&a.norm, reference_batch),
+    }
+}
+
+// reference_batch holds the inputs to conv, the statistics are taken from its pre activations
+pub fn set_reference_conv2d<T: Activate>(
+    a: &VirtualBatchNorm,
+    conv: &conv2d::Conv2D<T>,
+    reference_batch: &[Vec<matrix::Matrix>],
+    stride: (usize, usize),
+    padding: (usize, usize, usize, usize),
+) -> VirtualBatchNorm {
+    let mut pre_activations: Vec<Vec<matrix::Matrix>> = Vec::with_capacity(reference_batch.len());
+    for input in reference_batch.iter() {
+        pre_activations.push(conv2d::pre_activation(conv, input, stride, padding));
+    }
+
+    set_reference(a, &pre_activations)
+}
+
+pub fn feedforward(a: &VirtualBatchNorm, input: &[matrix::Matrix]) -> Vec<matrix::Matrix> {
+    batch_norm::feedforward_2d(&a.norm, input)
+}
+
+// conv -> virtual batch norm -> conv activation
+pub fn feedforward_conv2d<T: Activate>(
+    a: &VirtualBatchNorm,
+    conv: &conv2d::Conv2D<T>,
+    input: &[matrix::Matrix],
+    stride: (usize, usize),
+    padding: (usize, usize, usize, usize),
+) -> Vec<matrix::Matrix> {
+    assert!(
+        conv.num_filters == a.norm.gain.columns,
+        "Number of filters and number of channels must match."
+    );
+
+    let mut output = conv2d::pre_activation(conv, input, stride, padding);
+    output = feedforward(a, &output);
+
+    for m in output.iter_mut() {
+        *m = conv.activation.activate(m);
+    }
+
+    output
+}
+
+pub fn add(a: &VirtualBatchNorm, b: &VirtualBatchNorm) -> VirtualBatchNorm {
+    VirtualBatchNorm {
+        norm: batch_norm::add(&a.norm, &b.norm),
+    }
+}
+
+pub fn scalar(a: &VirtualBatchNorm, s: f32) -> VirtualBatchNorm {
+    VirtualBatchNorm {
+        norm: batch_norm::scalar(&a.norm, s),
+    }
+}
+
+pub fn parameters(a: &VirtualBatchNorm) -> Vec<(&str, &matrix::Matrix)> {
+    batch_norm::parameters(&a.norm)
+}
+
+// same order as parameters
+pub fn parameters_mut(a: &mut VirtualBatchNorm) -> Vec<&mut matrix::Matrix> {
+    batch_norm::parameters_mut(&mut a.norm)
+}
+
+pub fn save(a: &VirtualBatchNorm, dir_name: &str) {
+    fs::create_dir_all(dir_name).unwrap();
+    batch_norm::save(&a.norm, dir_name);
+}
+
+pub fn load(a: &VirtualBatchNorm, dir_name: &str) -> VirtualBatchNorm {
+    VirtualBatchNorm {
+        norm: batch_norm::load(&a.norm, dir_name),
+    }
+}
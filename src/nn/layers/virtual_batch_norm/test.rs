@@ -0,0 +1,192 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::param_relu;
+#[cfg(test)]
+use crate::nn::activations::Activate;
+#[cfg(test)]
+use crate::nn::layers::conv2d;
+#[cfg(test)]
+use crate::nn::layers::virtual_batch_norm;
+#[cfg(test)]
+use std::fs;
+
+#[cfg(test)]
+fn reference_batch(size: usize, channels: usize) -> Vec<Vec<matrix::Matrix>> {
+    let mut batch: Vec<Vec<matrix::Matrix>> = Vec::with_capacity(size);
+    for _ in 0..size {
+        let mut input: Vec<matrix::Matrix> = Vec::with_capacity(channels);
+        for _ in 0..channels {
+            input.push(matrix::new_gaussian_noise(4, 4));
+        }
+        batch.push(input);
+    }
+
+    batch
+}
+
+#[test]
+fn new_test() {
+    let a = virtual_batch_norm::new(3);
+
+    assert_eq!(a.norm.gain.value, [1.0, 1.0, 1.0]);
+    assert_eq!(a.norm.bias.value, [0.0, 0.0, 0.0]);
+    assert_eq!(a.norm.mean.value, [0.0, 0.0, 0.0]);
+    assert_eq!(a.norm.variance.value, [1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn print_test() {
+    let a = virtual_batch_norm::new_gaussian_noise(3);
+    // pass = does not panic
+    virtual_batch_norm::print(&a);
+}
+
+#[test]
+fn set_reference_test() {
+    let a = virtual_batch_norm::new(2);
+
+    let mut first = vec![matrix::new(1, 2), matrix::new(1, 2)];
+    first[0].value = vec![1.0, 3.0];
+    first[1].value = vec![0.0, 0.0];
+    let mut second = vec![matrix::new(1, 2), matrix::new(1, 2)];
+    second[0].value = vec![5.0, 7.0];
+    second[1].value = vec![4.0, 4.0];
+
+    let b = virtual_batch_norm::set_reference(&a, &[first, second]);
+    assert_eq!(b.norm.mean.value, [4.0, 2.0]);
+    assert_eq!(b.norm.variance.value, [5.0, 4.0]);
+
+    // statistics stay fixed for inputs outside of the reference batch
+    let mut input = vec![matrix::new(1, 1), matrix::new(1, 1)];
+    input[0].value = vec![4.0];
+    input[1].value = vec![4.0];
+    let output = virtual_batch_norm::feedforward(&b, &input);
+    assert!(output[0].value[0].abs() < 1e-6);
+    assert!((output[1].value[0] - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn set_reference_conv2d_test() {
+    let conv = conv2d::new_gaussian_noise(2, 3, (3, 3), param_relu::new(1.0, 0.001));
+    let batch = reference_batch(8, 2);
+
+    let a = virtual_batch_norm::set_reference_conv2d(
+        &virtual_batch_norm::new(3),
+        &conv,
+        &batch,
+        (1, 1),
+        (1, 1, 1, 1),
+    );
+
+    // normalized pre activations of the reference batch have zero mean and unit variance
+    let mut normalized: Vec<Vec<matrix::Matrix>> = Vec::new();
+    for input in batch.iter() {
+        let output = conv2d::pre_activation(&conv, input, (1, 1), (1, 1, 1, 1));
+        normalized.push(virtual_batch_norm::feedforward(&a, &output));
+    }
+    let check = virtual_batch_norm::set_reference(&virtual_batch_norm::new(3), &normalized);
+    for i in 0..3 {
+        assert!(check.norm.mean.value[i].abs() < 1e-4);
+        assert!((check.norm.variance.value[i] - 1.0).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn feedforward_conv2d_test() {
+    let pr = param_relu::new(1.0, 0.1);
+    let conv = conv2d::new_gaussian_noise(2, 3, (3, 3), param_relu::new(1.0, 0.1));
+    let batch = reference_batch(4, 2);
+
+    let a = virtual_batch_norm::set_reference_conv2d(
+        &virtual_batch_norm::new_gaussian_noise(3),
+        &conv,
+        &batch,
+        (1, 1),
+        (1, 1, 1, 1),
+    );
+
+    let output = virtual_batch_norm::feedforward_conv2d(&a, &conv, &batch[0], (1, 1), (1, 1, 1, 1));
+    let mut expected_output = virtual_batch_norm::feedforward(
+        &a,
+        &conv2d::pre_activation(&conv, &batch[0], (1, 1), (1, 1, 1, 1)),
+    );
+    for m in expected_output.iter_mut() {
+        *m = pr.activate(m);
+    }
+
+    assert_eq!(output.len(), 3);
+    for (i, m) in output.iter().enumerate() {
+        assert_eq!(m.rows, 4);
+        assert_eq!(m.columns, 4);
+        assert_eq!(m.value, expected_output[i].value);
+    }
+
+    // a large perturbation of the filters is absorbed once the reference is recomputed
+    let scaled_conv = conv2d::scalar(&conv, 1000.0);
+    let scaled =
+        virtual_batch_norm::set_reference_conv2d(&a, &scaled_conv, &batch, (1, 1), (1, 1, 1, 1));
+    let scaled_output = virtual_batch_norm::feedforward_conv2d(
+        &scaled,
+        &scaled_conv,
+        &batch[0],
+        (1, 1),
+        (1, 1, 1, 1),
+    );
+    for (i, m) in scaled_output.iter().enumerate() {
+        for (j, val) in m.value.iter().enumerate() {
+            assert!((val - output[i].value[j]).abs() < 1e-2);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn feedforward_conv2d_filters_panic_test() {
+    let conv = conv2d::new_gaussian_noise(2, 3, (3, 3), param_relu::new(1.0, 0.1));
+    let a = virtual_batch_norm::new(2);
+    let batch = reference_batch(1, 2);
+
+    let _ = virtual_batch_norm::feedforward_conv2d(&a, &conv, &batch[0], (1, 1), (1, 1, 1, 1));
+}
+
+#[test]
+fn add_test() {
+    let a = virtual_batch_norm::new_gaussian_noise(3);
+    let b = virtual_batch_norm::new_gaussian_noise(3);
+
+    let c = virtual_batch_norm::add(&a, &b);
+    for (i, val) in c.norm.gain.value.iter().enumerate() {
+        assert_eq!(*val, a.norm.gain.value[i] + b.norm.gain.value[i]);
+    }
+    for (i, val) in c.norm.bias.value.iter().enumerate() {
+        assert_eq!(*val, a.norm.bias.value[i] + b.norm.bias.value[i]);
+    }
+}
+
+#[test]
+fn scalar_test() {
+    let a = virtual_batch_norm::new_gaussian_noise(3);
+
+    let b = virtual_batch_norm::scalar(&a, 0.5);
+    for (i, val) in b.norm.gain.value.iter().enumerate() {
+        assert_eq!(*val, a.norm.gain.value[i] * 0.5);
+    }
+}
+
+#[test]
+fn save_load_test() {
+    let a = virtual_batch_norm::set_reference(
+        &virtual_batch_norm::new_gaussian_noise(2),
+        &reference_batch(3, 2),
+    );
+
+    virtual_batch_norm::save(&a, "virtual_batch_norm");
+    let b = virtual_batch_norm::load(&virtual_batch_norm::new(2), "virtual_batch_norm");
+    fs::remove_dir_all("virtual_batch_norm").unwrap();
+
+    assert_eq!(b.norm.gain.value, a.norm.gain.value);
+    assert_eq!(b.norm.bias.value, a.norm.bias.value);
+    assert_eq!(b.norm.mean.value, a.norm.mean.value);
+    assert_eq!(b.norm.variance.value, a.norm.variance.value);
+}
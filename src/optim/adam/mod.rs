@@ -0,0 +1,105 @@
+pub mod test;
+
+use crate::models::Model;
+use crate::optim::Optimize;
+use std::fs;
+
+// adam, with weight_decay > 0 it is adamw where the decay is decoupled from the moments
+// first_moment = beta1 * first_moment + (1 - beta1) * gradient
+// second_moment = beta2 * second_moment + (1 - beta2) * gradient^2
+// model = model + learning_rate * (first / (sqrt(second) + epsilon) - weight_decay * model)
+// first and second are the bias corrected moments
+#[derive(Clone)]
+pub struct Adam<M: Model> {
+    pub learning_rate: f32,
+    pub beta1: f32,
+    pub beta2: f32,
+    pub epsilon: f32,
+    pub weight_decay: f32,
+    pub first_moment: M,
+    pub second_moment: M,
+    pub steps: usize,
+}
+
+pub fn new<M: Model>(model: &M, learning_rate: f32) -> Adam<M> {
+    new_adamw(model, learning_rate, 0.0)
+}
+
+pub fn new_adamw<M: Model>(model: &M, learning_rate: f32, weight_decay: f32) -> Adam<M> {
+    Adam {
+        learning_rate,
+        beta1: 0.9,
+        beta2: 0.999,
+        epsilon: 1e-8,
+        weight_decay,
+        first_moment: model.scalar(0.0),
+        second_moment: model.scalar(0.0),
+        steps: 0,
+    }
+}
+
+impl<M: Model> Optimize<M> for Adam<M> {
+    fn step(&mut self, model: &M, gradient: &M) -> M {
+        self.steps += 1;
+
+        self.first_moment = self
+            .first_moment
+            .scalar(self.beta1)
+            .add(&gradient.scalar(1.0 - self.beta1));
+
+        let gradient_parameters = gradient.parameters();
+        for (i, m) in self.second_moment.parameters_mut().into_iter().enumerate() {
+            let g = gradient_parameters[i].1;
+            for j in 0..m.value.len() {
+                m.value[j] = self.beta2 * m.value[j] + (1.0 - self.beta2) * g.value[j].powi(2);
+            }
+        }
+
+        let first_correction = 1.0 - self.beta1.powi(self.steps as i32);
+        let second_correction = 1.0 - self.beta2.powi(self.steps as i32);
+
+        // update layer by layer
+        let mut b = model.clone();
+        let first_parameters = self.first_moment.parameters();
+        let second_parameters = self.second_moment.parameters();
+        for (i, m) in b.parameters_mut().into_iter().enumerate() {
+            let first = first_parameters[i].1;
+            let second = second_parameters[i].1;
+            for j in 0..m.value.len() {
+                let first_hat = first.value[j] / first_correction;
+                let second_hat = second.value[j] / second_correction;
+                m.value[j] += self.learning_rate
+                    * (first_hat / (second_hat.sqrt() + self.epsilon)
+                        - self.weight_decay * m.value[j]);
+            }
+        }
+
+        b
+    }
+
+    fn save(&self, dir_name: &str) {
+        fs::create_dir_all(dir_name).unwrap();
+        self.first_moment
+            .save((dir_name.to_owned() + "/first_moment").as_str());
+        self.second_moment
+            .save((dir_name.to_owned() + "/second_moment").as_str());
+        fs::write(dir_name.to_owned() + "/steps.txt", self.steps.to_string()).unwrap();
+    }
+
+    fn load(&self, dir_name: &str) -> Adam<M> {
+        let mut b = self.clone();
+        b.first_moment = self
+            .first_moment
+            .load((dir_name.to_owned() + "/first_moment").as_str());
+        b.second_moment = self
+            .second_moment
+            .load((dir_name.to_owned() + "/second_moment").as_str());
+        b.steps = fs::read_to_string(dir_name.to_owned() + "/steps.txt")
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+
+        b
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use crate::optim::adam;
+#[cfg(test)]
+use crate::optim::Optimize;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn new_test() {
+    let model = xor::new_gaussian_noise();
+    let a = adam::new(&model, 0.01);
+
+    assert_eq!(a.learning_rate, 0.01);
+    assert_eq!(a.weight_decay, 0.0);
+    assert_eq!(a.steps, 0);
+    for (_, m) in a.first_moment.parameters() {
+        assert!(m.value.iter().all(|v| *v == 0.0));
+    }
+
+    let b = adam::new_adamw(&model, 0.01, 0.1);
+    assert_eq!(b.weight_decay, 0.1);
+}
+
+#[test]
+fn step_test() {
+    let model = xor::new_gaussian_noise();
+    let gradient = xor::new_gaussian_noise();
+    let mut a = adam::new(&model, 0.01);
+
+    // after bias correction the first step moves every parameter by learning_rate * sign(g)
+    let b = a.step(&model, &gradient);
+    assert_eq!(a.steps, 1);
+    for (i, (_, m)) in b.parameters().into_iter().enumerate() {
+        let start = model.parameters()[i].1;
+        let g = gradient.parameters()[i].1;
+        for j in 0..m.value.len() {
+            let expected = start.value[j] + 0.01 * g.value[j].signum();
+            assert!((m.value[j] - expected).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn step_weight_decay_test() {
+    let model = xor::new_gaussian_noise();
+    let gradient = xor::new().scalar(0.0);
+    let mut a = adam::new_adamw(&model, 0.1, 0.5);
+
+    // a zero gradient leaves only the decay
+    let b = a.step(&model, &gradient);
+    for (i, (_, m)) in b.parameters().into_iter().enumerate() {
+        let start = model.parameters()[i].1;
+        for j in 0..m.value.len() {
+            assert!((m.value[j] - start.value[j] * 0.95).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn step_convergence_test() {
+    // fitness = -|model - target|^2 has the gradient 2 * (target - model)
+    let target = xor::new_gaussian_noise();
+    let mut model = xor::new();
+    let mut a = adam::new(&model, 0.05);
+
+    for _ in 0..500 {
+        let gradient = target.add(&model.scalar(-1.0)).scalar(2.0);
+        model = a.step(&model, &gradient);
+    }
+
+    for (i, (_, m)) in model.parameters().into_iter().enumerate() {
+        let t = target.parameters()[i].1;
+        for j in 0..m.value.len() {
+            assert!((m.value[j] - t.value[j]).abs() < 0.05);
+        }
+    }
+}
+
+#[test]
+fn save_load_test() {
+    let model = xor::new_gaussian_noise();
+    let mut a = adam::new(&model, 0.01);
+    a.step(&model, &xor::new_gaussian_noise());
+    a.step(&model, &xor::new_gaussian_noise());
+
+    a.save("adam");
+    let b = adam::new(&model, 0.01).load("adam");
+    fs::remove_dir_all("adam").unwrap();
+
+    assert_eq!(b.steps, 2);
+    for (i, (_, m)) in b.first_moment.parameters().into_iter().enumerate() {
+        assert_eq!(m.value, a.first_moment.parameters()[i].1.value);
+    }
+    for (i, (_, m)) in b.second_moment.parameters().into_iter().enumerate() {
+        assert_eq!(m.value, a.second_moment.parameters()[i].1.value);
+    }
+}
@@ -0,0 +1,13 @@
+pub mod adam;
+pub mod momentum;
+
+use crate::models::Model;
+
+// first order optimizers keep their state in models with the same shape as the model they update
+// gradient points towards higher fitness, e.g. an evolution strategies gradient estimate,
+// pass the negative of a backpropagated loss gradient
+pub trait Optimize<M: Model> {
+    fn step(&mut self, model: &M, gradient: &M) -> M;
+    fn save(&self, dir_name: &str);
+    fn load(&self, dir_name: &str) -> Self;
+}
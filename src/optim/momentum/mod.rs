@@ -0,0 +1,46 @@
+pub mod test;
+
+use crate::models::Model;
+use crate::optim::Optimize;
+use std::fs;
+
+// sgd with momentum
+// velocity = momentum * velocity + gradient
+// model = model + learning_rate * velocity
+#[derive(Clone)]
+pub struct Momentum<M: Model> {
+    pub learning_rate: f32,
+    pub momentum: f32,
+    pub velocity: M,
+}
+
+pub fn new<M: Model>(model: &M, learning_rate: f32, momentum: f32) -> Momentum<M> {
+    Momentum {
+        learning_rate,
+        momentum,
+        velocity: model.scalar(0.0),
+    }
+}
+
+impl<M: Model> Optimize<M> for Momentum<M> {
+    fn step(&mut self, model: &M, gradient: &M) -> M {
+        self.velocity = self.velocity.scalar(self.momentum).add(gradient);
+
+        model.add(&self.velocity.scalar(self.learning_rate))
+    }
+
+    fn save(&self, dir_name: &str) {
+        fs::create_dir_all(dir_name).unwrap();
+        self.velocity
+            .save((dir_name.to_owned() + "/velocity").as_str());
+    }
+
+    fn load(&self, dir_name: &str) -> Momentum<M> {
+        let mut b = self.clone();
+        b.velocity = self
+            .velocity
+            .load((dir_name.to_owned() + "/velocity").as_str());
+
+        b
+    }
+}
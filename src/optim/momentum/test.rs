@@ -0,0 +1,57 @@
+#[cfg(test)]
+use crate::models::xor;
+#[cfg(test)]
+use crate::models::Model;
+#[cfg(test)]
+use crate::optim::momentum;
+#[cfg(test)]
+use crate::optim::Optimize;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn new_test() {
+    let model = xor::new_gaussian_noise();
+    let a = momentum::new(&model, 0.1, 0.9);
+
+    assert_eq!(a.learning_rate, 0.1);
+    assert_eq!(a.momentum, 0.9);
+    for (_, m) in a.velocity.parameters() {
+        assert!(m.value.iter().all(|v| *v == 0.0));
+    }
+}
+
+#[test]
+fn step_test() {
+    let model = xor::new_gaussian_noise();
+    let gradient = xor::new_gaussian_noise();
+    let mut a = momentum::new(&model, 0.1, 0.5);
+
+    let first = a.step(&model, &gradient);
+    let second = a.step(&first, &gradient);
+
+    // velocity = g, then 1.5 * g
+    for (i, (_, m)) in second.parameters().into_iter().enumerate() {
+        let start = model.parameters()[i].1;
+        let g = gradient.parameters()[i].1;
+        for j in 0..m.value.len() {
+            let expected = start.value[j] + 0.1 * g.value[j] + 0.1 * 1.5 * g.value[j];
+            assert!((m.value[j] - expected).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn save_load_test() {
+    let model = xor::new_gaussian_noise();
+    let mut a = momentum::new(&model, 0.1, 0.9);
+    a.step(&model, &xor::new_gaussian_noise());
+
+    a.save("momentum");
+    let b = momentum::new(&model, 0.1, 0.9).load("momentum");
+    fs::remove_dir_all("momentum").unwrap();
+
+    for (i, (_, m)) in b.velocity.parameters().into_iter().enumerate() {
+        assert_eq!(m.value, a.velocity.parameters()[i].1.value);
+    }
+}
@@ -0,0 +1,165 @@
+pub mod test;
+
+use crate::games::tic_tac_toe;
+use crate::matrix;
+use crate::models;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+// empty cells show the number to type for them
+pub fn render(board: &tic_tac_toe::Board) -> String {
+    let mut b = String::new();
+    for row in 0..3 {
+        let cells: Vec<String> = (0..3)
+            .map(|column| match board.cells[row * 3 + column] {
+                1 => "X".to_string(),
+                -1 => "O".to_string(),
+                _ => (row * 3 + column + 1).to_string(),
+            })
+            .collect();
+        b.push_str(&format!(" {} \n", cells.join(" | ")));
+        if row < 2 {
+            b.push_str("---+---+---\n");
+        }
+    }
+
+    b
+}
+
+// the 9 outputs laid out like the board, taken cells are marked with -
+pub fn render_scores(board: &tic_tac_toe::Board, output: &matrix::Matrix) -> String {
+    let mut b = String::new();
+    for row in 0..3 {
+        let scores: Vec<String> = (0..3)
+            .map(|column| row * 3 + column)
+            .map(|i| {
+                if board.cells[i] == 0 {
+                    format!("{:>8.3}", output.value[i])
+                } else {
+                    format!("{:>8}", "-")
+                }
+            })
+            .collect();
+        b.push_str(&scores.join(" "));
+        b.push('\n');
+    }
+
+    b
+}
+
+// reads lines until one is a legal move, None when input ends or the human quits
+fn read_move<R: BufRead, W: Write>(
+    board: &tic_tac_toe::Board,
+    input: &mut R,
+    output: &mut W,
+) -> Option<usize> {
+    let moves = tic_tac_toe::legal_moves(board);
+    loop {
+        write!(output, "your move (1-9, q to quit): ").unwrap();
+        output.flush().unwrap();
+
+        let mut line = String::new();
+        if input.read_line(&mut line).unwrap() == 0 {
+            return None;
+        }
+        let line = line.trim();
+        if line == "q" {
+            return None;
+        }
+        match line.parse::<usize>() {
+            Ok(cell) if (1..=9).contains(&cell) && moves.contains(&(cell - 1)) => {
+                return Some(cell - 1)
+            }
+            _ => writeln!(output, "{} is not a free cell", line).unwrap(),
+        }
+    }
+}
+
+// one game between a human on input and output and the model, X moves first
+// the network's scores are shown before every move, returns the winner or None if the human quit
+pub fn tic_tac_toe<R: BufRead, W: Write>(
+    model: &models::tic_tac_toe::TicTacToe,
+    human_first: bool,
+    input: &mut R,
+    output: &mut W,
+) -> Option<i8> {
+    let human: i8 = if human_first { 1 } else { -1 };
+    writeln!(output, "you are {}", if human == 1 { "X" } else { "O" }).unwrap();
+
+    let mut board = tic_tac_toe::new();
+    while tic_tac_toe::winner(&board).is_none() {
+        writeln!(output, "\n{}", render(&board)).unwrap();
+        let scores = models::tic_tac_toe::feedforward(model, &tic_tac_toe::encode(&board));
+        writeln!(output, "network scores\n{}", render_scores(&board, &scores)).unwrap();
+
+        let index = if board.player == human {
+            read_move(&board, input, output)?
+        } else {
+            let index = models::tic_tac_toe::choose_move(model, &board);
+            writeln!(output, "network plays {}", index + 1).unwrap();
+            index
+        };
+        board = tic_tac_toe::play(&board, index);
+    }
+
+    let winner = tic_tac_toe::winner(&board).unwrap();
+    writeln!(output, "\n{}", render(&board)).unwrap();
+    writeln!(
+        output,
+        "{}",
+        match winner {
+            0 => "draw",
+            w if w == human => "you win",
+            _ => "network wins",
+        }
+    )
+    .unwrap();
+
+    Some(winner)
+}
+
+// asks who moves first, None when input ends
+pub fn ask_human_first<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> Option<bool> {
+    loop {
+        write!(output, "do you want to move first? (y/n): ").unwrap();
+        output.flush().unwrap();
+
+        let mut line = String::new();
+        if input.read_line(&mut line).unwrap() == 0 {
+            return None;
+        }
+        match line.trim() {
+            "y" | "yes" => return Some(true),
+            "n" | "no" => return Some(false),
+            _ => {}
+        }
+    }
+}
+
+// plays games against the model saved in dir_name on the terminal until the human quits
+// first is "human" or "model", otherwise the human is asked before every game
+pub fn run(dir_name: &str, first: Option<&str>) {
+    let model = models::tic_tac_toe::load(dir_name);
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut output = io::stdout();
+
+    loop {
+        let human_first = match first {
+            Some("human") => true,
+            Some("model") => false,
+            _ => match ask_human_first(&mut input, &mut output) {
+                Some(human_first) => human_first,
+                None => return,
+            },
+        };
+        if tic_tac_toe(&model, human_first, &mut input, &mut output).is_none() {
+            return;
+        }
+        // with a fixed first player there is no prompt between games to stop at
+        if first.is_some() {
+            return;
+        }
+    }
+}
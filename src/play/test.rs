@@ -0,0 +1,86 @@
+#[cfg(test)]
+use crate::games::tic_tac_toe;
+#[cfg(test)]
+use crate::models;
+#[cfg(test)]
+use crate::play;
+
+#[test]
+fn render_test() {
+    let board = tic_tac_toe::play(&tic_tac_toe::play(&tic_tac_toe::new(), 0), 4);
+
+    assert_eq!(
+        play::render(&board),
+        " X | 2 | 3 \n---+---+---\n 4 | O | 6 \n---+---+---\n 7 | 8 | 9 \n"
+    );
+}
+
+#[test]
+fn render_scores_test() {
+    let board = tic_tac_toe::play(&tic_tac_toe::new(), 0);
+    let output =
+        models::tic_tac_toe::feedforward(&models::tic_tac_toe::new(), &tic_tac_toe::encode(&board));
+
+    let lines: Vec<String> = play::render_scores(&board, &output)
+        .lines()
+        .map(|l| l.to_string())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].trim_start().starts_with('-'));
+    assert_eq!(lines[1].split_whitespace().count(), 3);
+}
+
+#[test]
+fn tic_tac_toe_test() {
+    let model = models::tic_tac_toe::new_gaussian_noise();
+
+    // bad input is asked again, then the human takes any free cell until the game ends
+    let mut input: &[u8] = b"0\nx\n1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+    let mut output: Vec<u8> = Vec::new();
+    let winner = play::tic_tac_toe(&model, true, &mut input, &mut output);
+    let text = String::from_utf8(output).unwrap();
+
+    assert!(winner.is_some());
+    assert!(text.starts_with("you are X"));
+    assert!(text.contains("0 is not a free cell"));
+    assert!(text.contains("x is not a free cell"));
+    assert!(text.contains("network scores"));
+    assert!(text.contains("network plays"));
+    assert!(text.contains("you win") || text.contains("network wins") || text.contains("draw"));
+}
+
+#[test]
+fn quit_test() {
+    let model = models::tic_tac_toe::new_gaussian_noise();
+
+    // the model moves first, then the human quits
+    let mut input: &[u8] = b"q\n";
+    let mut output: Vec<u8> = Vec::new();
+    assert_eq!(
+        play::tic_tac_toe(&model, false, &mut input, &mut output),
+        None
+    );
+    let text = String::from_utf8(output).unwrap();
+    assert!(text.starts_with("you are O"));
+    assert!(text.contains("network plays"));
+
+    // input ending also stops the game
+    let mut input: &[u8] = b"";
+    let mut output: Vec<u8> = Vec::new();
+    assert_eq!(
+        play::tic_tac_toe(&model, true, &mut input, &mut output),
+        None
+    );
+}
+
+#[test]
+fn ask_human_first_test() {
+    let mut output: Vec<u8> = Vec::new();
+
+    let mut input: &[u8] = b"maybe\ny\n";
+    assert_eq!(play::ask_human_first(&mut input, &mut output), Some(true));
+    let mut input: &[u8] = b"no\n";
+    assert_eq!(play::ask_human_first(&mut input, &mut output), Some(false));
+    let mut input: &[u8] = b"";
+    assert_eq!(play::ask_human_first(&mut input, &mut output), None);
+}
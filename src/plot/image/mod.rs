@@ -0,0 +1,211 @@
+pub mod test;
+
+use crate::matrix;
+use crate::nn::activations::Activate;
+use crate::nn::layers::conv2d;
+use std::fs;
+
+// pixels between tiles, drawn white
+pub const GAP: usize = 1;
+
+pub enum Normalization {
+    // one range over all tiles so their values can be compared
+    Global,
+    // every tile stretched over the full range, shows the pattern of weak tiles too
+    PerTile,
+}
+
+// values of a mapped linearly onto [0, 1] from the range (low, high), constant input is mid grey
+pub fn normalize(a: &matrix::Matrix, low: f32, high: f32) -> matrix::Matrix {
+    let mut b = a.clone();
+    for value in b.value.iter_mut() {
+        *value = if high > low {
+            ((*value - low) / (high - low)).clamp(0.0, 1.0)
+        } else {
+            0.5
+        };
+    }
+
+    b
+}
+
+fn range(values: &[f32]) -> (f32, f32) {
+    (
+        values.iter().cloned().fold(f32::MAX, f32::min),
+        values.iter().cloned().fold(f32::MIN, f32::max),
+    )
+}
+
+// images of one shape laid out row by row in a grid of columns tiles, each pixel scale x scale
+// the result has values in [0, 1] ready for pgm or png
+pub fn tile(
+    images: &[matrix::Matrix],
+    columns: usize,
+    scale: usize,
+    normalization: Normalization,
+) -> matrix::Matrix {
+    assert!(!images.is_empty(), "Cannot tile no images.");
+    assert!(
+        columns != 0 && scale != 0,
+        "Columns and scale cannot be zero."
+    );
+    let (rows, cols) = (images[0].rows, images[0].columns);
+    assert!(
+        images.iter().all(|m| m.rows == rows && m.columns == cols),
+        "Tiled images must have the same shape."
+    );
+
+    let all: Vec<f32> = images
+        .iter()
+        .flat_map(|m| m.value.iter().cloned())
+        .collect();
+    let global = range(&all);
+
+    let grid_rows = images.len().div_ceil(columns);
+    let (tile_rows, tile_cols) = (rows * scale + GAP, cols * scale + GAP);
+    let mut b = matrix::new(grid_rows * tile_rows + GAP, columns * tile_cols + GAP);
+    b.value.iter_mut().for_each(|v| *v = 1.0);
+
+    for (i, image) in images.iter().enumerate() {
+        let (low, high) = match normalization {
+            Normalization::Global => global,
+            Normalization::PerTile => range(&image.value),
+        };
+        let image = normalize(image, low, high);
+        let (top, left) = (
+            (i / columns) * tile_rows + GAP,
+            (i % columns) * tile_cols + GAP,
+        );
+
+        for r in 0..rows * scale {
+            for c in 0..cols * scale {
+                let b_index = (left + c) * b.rows + top + r;
+                b.value[b_index] = image.value[(c / scale) * rows + r / scale];
+            }
+        }
+    }
+
+    b
+}
+
+// one row per filter and one column per input channel
+pub fn filters<T: Activate>(
+    conv: &conv2d::Conv2D<T>,
+    scale: usize,
+    normalization: Normalization,
+) -> matrix::Matrix {
+    let filters = conv2d::get_filters(&conv.filters, conv.filter_size, conv.num_channels);
+
+    tile(&filters, conv.num_channels, scale, normalization)
+}
+
+// output channels of conv2d::feedforward in a square grid
+pub fn channels(
+    output: &[matrix::Matrix],
+    scale: usize,
+    normalization: Normalization,
+) -> matrix::Matrix {
+    let columns = (output.len() as f32).sqrt().ceil() as usize;
+
+    tile(output, columns, scale, normalization)
+}
+
+fn bytes(a: &matrix::Matrix) -> Vec<u8> {
+    let mut b = Vec::with_capacity(a.value.len());
+    for r in 0..a.rows {
+        for c in 0..a.columns {
+            b.push((a.value[c * a.rows + r].clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+    }
+
+    b
+}
+
+// binary greyscale pgm of values in [0, 1]
+pub fn pgm(a: &matrix::Matrix) -> Vec<u8> {
+    let mut b = format!("P5\n{} {}\n255\n", a.columns, a.rows).into_bytes();
+    b.extend(bytes(a));
+
+    b
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xffffffff;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+// 8 bit greyscale png of values in [0, 1], the pixels are stored uncompressed inside zlib
+pub fn png(a: &matrix::Matrix) -> Vec<u8> {
+    let pixels = bytes(a);
+    // filter type 0 in front of every row
+    let mut raw = Vec::with_capacity(pixels.len() + a.rows);
+    for row in pixels.chunks(a.columns) {
+        raw.push(0);
+        raw.extend(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = raw.chunks(65535).collect();
+    for (i, block) in blocks.iter().enumerate() {
+        zlib.push((i + 1 == blocks.len()) as u8);
+        zlib.extend((block.len() as u16).to_le_bytes());
+        zlib.extend((!(block.len() as u16)).to_le_bytes());
+        zlib.extend(*block);
+    }
+    zlib.extend(adler32(&raw).to_be_bytes());
+
+    let mut header = vec![];
+    header.extend((a.columns as u32).to_be_bytes());
+    header.extend((a.rows as u32).to_be_bytes());
+    header.extend([8, 0, 0, 0, 0]);
+
+    let mut b = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    chunk(&mut b, b"IHDR", &header);
+    chunk(&mut b, b"IDAT", &zlib);
+    chunk(&mut b, b"IEND", &[]);
+
+    b
+}
+
+// pgm or png by the extension of file_name
+pub fn save(a: &matrix::Matrix, file_name: &str) {
+    let data = if file_name.ends_with(".pgm") {
+        pgm(a)
+    } else if file_name.ends_with(".png") {
+        png(a)
+    } else {
+        panic!("Image files must end in .pgm or .png.");
+    };
+
+    fs::write(file_name, data).unwrap();
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+use crate::matrix;
+#[cfg(test)]
+use crate::nn::activations::tanh;
+#[cfg(test)]
+use crate::nn::layers::conv2d;
+#[cfg(test)]
+use crate::plot::image;
+#[cfg(test)]
+use std::fs;
+
+#[cfg(test)]
+fn image_of(rows: usize, columns: usize, values: &[f32]) -> matrix::Matrix {
+    let mut a = matrix::new(rows, columns);
+    a.value = values.to_vec();
+    a
+}
+
+#[test]
+fn normalize_test() {
+    let a = image_of(1, 3, &[-1.0, 0.0, 3.0]);
+    assert_eq!(image::normalize(&a, -1.0, 3.0).value, vec![0.0, 0.25, 1.0]);
+    // values outside the range are clamped, a constant range is mid grey
+    assert_eq!(image::normalize(&a, 0.0, 1.0).value, vec![0.0, 0.0, 1.0]);
+    assert_eq!(image::normalize(&a, 1.0, 1.0).value, vec![0.5, 0.5, 0.5]);
+}
+
+#[test]
+fn tile_test() {
+    let images = vec![
+        image_of(1, 2, &[0.0, 1.0]),
+        image_of(1, 2, &[0.0, 2.0]),
+        image_of(1, 2, &[4.0, 4.0]),
+    ];
+
+    // 2 columns of 2 wide tiles, 2 rows of 1 high tiles, gaps around every tile
+    let b = image::tile(&images, 2, 1, image::Normalization::Global);
+    assert_eq!((b.rows, b.columns), (5, 7));
+    // first tile at row 1 columns 1 and 2, column major
+    assert_eq!(b.value[b.rows + 1], 0.0);
+    assert_eq!(b.value[2 * b.rows + 1], 0.25);
+    // third tile starts the second row, gap and the unused fourth tile stay white
+    assert_eq!(b.value[b.rows + 3], 1.0);
+    assert_eq!(b.value[0], 1.0);
+    assert_eq!(b.value[5 * b.rows + 3], 1.0);
+
+    let b = image::tile(&images, 2, 1, image::Normalization::PerTile);
+    assert_eq!(b.value[4 * b.rows + 1], 0.0);
+    assert_eq!(b.value[5 * b.rows + 1], 1.0);
+    assert_eq!(b.value[b.rows + 3], 0.5);
+
+    // every pixel becomes a scale x scale block
+    let b = image::tile(&images[..1], 1, 3, image::Normalization::Global);
+    assert_eq!((b.rows, b.columns), (5, 8));
+    assert_eq!(b.value[3 * b.rows + 3], 0.0);
+    assert_eq!(b.value[4 * b.rows + 3], 1.0);
+}
+
+#[test]
+#[should_panic]
+fn tile_shape_panic_test() {
+    let images = vec![matrix::new(2, 2), matrix::new(3, 2)];
+    let _ = image::tile(&images, 2, 1, image::Normalization::Global);
+}
+
+#[test]
+fn filters_channels_test() {
+    let conv = conv2d::new_gaussian_noise(3, 4, (3, 3), tanh::new());
+
+    // a row of 3 channel tiles per filter
+    let b = image::filters(&conv, 2, image::Normalization::PerTile);
+    assert_eq!((b.rows, b.columns), (4 * 7 + 1, 3 * 7 + 1));
+
+    let input = vec![matrix::new_gaussian_noise(5, 5); 3];
+    let output = conv2d::feedforward(&conv, &input, (1, 1), (1, 1, 1, 1));
+    let b = image::channels(&output, 1, image::Normalization::Global);
+    assert_eq!((b.rows, b.columns), (2 * 6 + 1, 2 * 6 + 1));
+    assert!(b.value.iter().all(|v| (0.0..=1.0).contains(v)));
+}
+
+#[test]
+fn pgm_test() {
+    let a = image_of(2, 2, &[0.0, 1.0, 0.5, 0.25]);
+
+    // rows are written top to bottom
+    let b = image::pgm(&a);
+    assert!(b.starts_with(b"P5\n2 2\n255\n"));
+    assert_eq!(b[b.len() - 4..], [0, 128, 255, 64]);
+}
+
+#[test]
+fn png_test() {
+    let a = image_of(2, 3, &[0.0, 1.0, 0.5, 0.25, 1.0, 0.0]);
+
+    let b = image::png(&a);
+    assert_eq!(b[..8], [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+    // width 3 and height 2 in the header
+    assert_eq!(b[12..16], *b"IHDR");
+    assert_eq!(b[16..24], [0, 0, 0, 3, 0, 0, 0, 2]);
+    // crc of the IEND chunk is fixed
+    assert_eq!(b[b.len() - 4..], [0xae, 0x42, 0x60, 0x82]);
+    // stored pixels, each row after a filter byte
+    let raw = [0, 0, 128, 255, 0, 255, 64, 0];
+    assert!(b.windows(raw.len()).any(|w| w == raw));
+
+    // large images split into several stored blocks
+    let b = image::png(&matrix::new(300, 300));
+    assert!(b.len() > 300 * 301);
+}
+
+#[test]
+fn save_test() {
+    let a = image_of(1, 2, &[0.0, 1.0]);
+
+    image::save(&a, "plot_image_save_test.pgm");
+    image::save(&a, "plot_image_save_test.png");
+    let pgm = fs::read("plot_image_save_test.pgm").unwrap();
+    let png = fs::read("plot_image_save_test.png").unwrap();
+    fs::remove_file("plot_image_save_test.pgm").unwrap();
+    fs::remove_file("plot_image_save_test.png").unwrap();
+
+    assert_eq!(pgm, image::pgm(&a));
+    assert_eq!(png, image::png(&a));
+}
+
+#[test]
+#[should_panic]
+fn save_panic_test() {
+    image::save(&matrix::new(1, 1), "plot_image_save_panic_test.bmp");
+}
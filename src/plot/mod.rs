@@ -0,0 +1,268 @@
+pub mod image;
+pub mod test;
+
+use crate::models::Model;
+use std::fs;
+
+pub const WIDTH: f32 = 640.0;
+pub const HEIGHT: f32 = 400.0;
+// left, right, top and bottom
+const MARGINS: (f32, f32, f32, f32) = (70.0, 20.0, 40.0, 50.0);
+const COLOURS: [&str; 6] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b",
+];
+
+// range with a little room around the values, never empty
+fn range(values: &[f32]) -> (f32, f32) {
+    let finite: Vec<f32> = values.iter().cloned().filter(|v| v.is_finite()).collect();
+    if finite.is_empty() {
+        return (0.0, 1.0);
+    }
+
+    let low = finite.iter().cloned().fold(f32::MAX, f32::min);
+    let high = finite.iter().cloned().fold(f32::MIN, f32::max);
+    if low == high {
+        return (low - 0.5, high + 0.5);
+    }
+    let pad = (high - low) * 0.05;
+
+    (low - pad, high + pad)
+}
+
+fn label(v: f32) -> String {
+    if v.abs() >= 100.0 {
+        format!("{:.0}", v)
+    } else if v.abs() >= 1.0 {
+        format!("{:.2}", v)
+    } else {
+        format!("{:.3}", v)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// svg header, title, axes and 5 ticks on each axis for the given value ranges
+fn frame(title: &str, x_label: &str, y_label: &str, x: (f32, f32), y: (f32, f32)) -> String {
+    let (left, right, top, bottom) = MARGINS;
+    let (x1, y1) = (WIDTH - right, HEIGHT - bottom);
+    let mut b = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        WIDTH, HEIGHT, WIDTH, HEIGHT
+    );
+    b.push_str(&format!(
+        "<rect width=\"{}\" height=\"{}\" fill=\"white\"/>\n",
+        WIDTH, HEIGHT
+    ));
+    b.push_str(&format!(
+        "<text x=\"{}\" y=\"24\" text-anchor=\"middle\" font-size=\"16\">{}</text>\n",
+        WIDTH / 2.0,
+        escape(title)
+    ));
+    b.push_str(&format!(
+        "<path d=\"M{left} {top} V{y1} H{x1}\" fill=\"none\" stroke=\"black\"/>\n"
+    ));
+
+    for i in 0..5 {
+        let t = i as f32 / 4.0;
+        let (px, py) = (left + t * (x1 - left), y1 - t * (y1 - top));
+        b.push_str(&format!(
+            "<line x1=\"{px}\" y1=\"{y1}\" x2=\"{px}\" y2=\"{}\" stroke=\"black\"/>\n",
+            y1 + 5.0
+        ));
+        b.push_str(&format!(
+            "<text x=\"{px}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            y1 + 18.0,
+            label(x.0 + t * (x.1 - x.0))
+        ));
+        b.push_str(&format!(
+            "<line x1=\"{}\" y1=\"{py}\" x2=\"{left}\" y2=\"{py}\" stroke=\"black\"/>\n",
+            left - 5.0
+        ));
+        b.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+            left - 8.0,
+            py + 4.0,
+            label(y.0 + t * (y.1 - y.0))
+        ));
+    }
+    b.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+        (left + x1) / 2.0,
+        HEIGHT - 10.0,
+        escape(x_label)
+    ));
+    b.push_str(&format!(
+        "<text x=\"16\" y=\"{}\" text-anchor=\"middle\" transform=\"rotate(-90 16 {})\">{}</text>\n",
+        (top + y1) / 2.0,
+        (top + y1) / 2.0,
+        escape(y_label)
+    ));
+
+    b
+}
+
+// one polyline per named series of (x, y) points, non finite points are skipped
+pub fn line_chart(
+    series: &[(String, Vec<(f32, f32)>)],
+    title: &str,
+    x_label: &str,
+    y_label: &str,
+) -> String {
+    let xs: Vec<f32> = series
+        .iter()
+        .flat_map(|(_, p)| p.iter().map(|p| p.0))
+        .collect();
+    let ys: Vec<f32> = series
+        .iter()
+        .flat_map(|(_, p)| p.iter().map(|p| p.1))
+        .collect();
+    let (x, y) = (range(&xs), range(&ys));
+    let (left, right, top, bottom) = MARGINS;
+    let to_pixels = |(px, py): (f32, f32)| {
+        (
+            left + (px - x.0) / (x.1 - x.0) * (WIDTH - right - left),
+            HEIGHT - bottom - (py - y.0) / (y.1 - y.0) * (HEIGHT - bottom - top),
+        )
+    };
+
+    let mut b = frame(title, x_label, y_label, x, y);
+    for (i, (name, points)) in series.iter().enumerate() {
+        let colour = COLOURS[i % COLOURS.len()];
+        let coordinates: Vec<String> = points
+            .iter()
+            .filter(|p| p.0.is_finite() && p.1.is_finite())
+            .map(|p| to_pixels(*p))
+            .map(|(px, py)| format!("{:.1},{:.1}", px, py))
+            .collect();
+        b.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/>\n",
+            coordinates.join(" "),
+            colour
+        ));
+
+        // legend in the top left corner of the plot
+        let ly = top + 14.0 + 16.0 * i as f32;
+        b.push_str(&format!(
+            "<line x1=\"{}\" y1=\"{ly}\" x2=\"{}\" y2=\"{ly}\" stroke=\"{colour}\" stroke-width=\"2\"/>\n",
+            left + 10.0,
+            left + 30.0
+        ));
+        b.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\">{}</text>\n",
+            left + 36.0,
+            ly + 4.0,
+            escape(name)
+        ));
+    }
+    b.push_str("</svg>\n");
+
+    b
+}
+
+// counts of values in bins equal width bins over their range, the last bin includes the maximum
+pub fn bin_counts(values: &[f32], bins: usize) -> (Vec<usize>, f32, f32) {
+    assert!(bins != 0, "Number of bins cannot be zero.");
+
+    let finite: Vec<f32> = values.iter().cloned().filter(|v| v.is_finite()).collect();
+    let mut low = finite.iter().cloned().fold(f32::MAX, f32::min);
+    let mut high = finite.iter().cloned().fold(f32::MIN, f32::max);
+    if finite.is_empty() {
+        (low, high) = (0.0, 1.0);
+    } else if low == high {
+        (low, high) = (low - 0.5, high + 0.5);
+    }
+
+    let mut counts = vec![0; bins];
+    for v in finite {
+        let bin = ((v - low) / (high - low) * bins as f32) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+
+    (counts, low, high)
+}
+
+pub fn histogram(values: &[f32], bins: usize, title: &str) -> String {
+    let (counts, low, high) = bin_counts(values, bins);
+    let most = *counts.iter().max().unwrap() as f32;
+    let y = (0.0, most.max(1.0) * 1.05);
+    let (left, right, top, bottom) = MARGINS;
+    let width = (WIDTH - right - left) / bins as f32;
+
+    let mut b = frame(title, "value", "count", (low, high), y);
+    for (i, count) in counts.iter().enumerate() {
+        let height = *count as f32 / y.1 * (HEIGHT - bottom - top);
+        b.push_str(&format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" stroke=\"white\" stroke-width=\"0.5\"/>\n",
+            left + i as f32 * width,
+            HEIGHT - bottom - height,
+            width,
+            height,
+            COLOURS[0]
+        ));
+    }
+    b.push_str("</svg>\n");
+
+    b
+}
+
+// columns of a metrics.csv as written by evolution::metrics, empty fields are NaN
+pub fn read_csv(file_name: &str) -> Result<Vec<(String, Vec<f32>)>, String> {
+    let text = fs::read_to_string(file_name).map_err(|e| format!("{}: {}", file_name, e))?;
+    let mut lines = text.lines();
+    let header = lines.next().ok_or(format!("{} is empty", file_name))?;
+    let mut columns: Vec<(String, Vec<f32>)> = header
+        .split(',')
+        .map(|name| (name.to_string(), vec![]))
+        .collect();
+
+    for line in lines {
+        for (column, field) in columns.iter_mut().zip(line.split(',')) {
+            column.1.push(field.parse().unwrap_or(f32::NAN));
+        }
+    }
+
+    Ok(columns)
+}
+
+// best, mean and median fitness over the generations of a metrics.csv
+pub fn fitness_curves(metrics_file: &str, title: &str) -> Result<String, String> {
+    let columns = read_csv(metrics_file)?;
+    let column = |name: &str| {
+        columns
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, values)| values.clone())
+            .ok_or(format!("{} has no {} column", metrics_file, name))
+    };
+
+    let generations = column("generation")?;
+    let mut series = vec![];
+    for name in ["best_fitness", "mean_fitness", "median_fitness"] {
+        let points = generations.iter().cloned().zip(column(name)?).collect();
+        series.push((name.replace('_', " "), points));
+    }
+
+    Ok(line_chart(&series, title, "generation", "fitness"))
+}
+
+// one histogram per conv filter and fully connected weight matrix, in dir_name/<parameter>.svg
+// returns the files written
+pub fn weight_histograms<M: Model>(model: &M, bins: usize, dir_name: &str) -> Vec<String> {
+    fs::create_dir_all(dir_name).unwrap();
+
+    let mut files = vec![];
+    for (name, m) in model.parameters() {
+        if !(name.ends_with("filters") || name.ends_with("weights")) {
+            continue;
+        }
+        let file_name = dir_name.to_owned() + "/" + &name + ".svg";
+        fs::write(&file_name, histogram(&m.value, bins, &name)).unwrap();
+        files.push(file_name);
+    }
+
+    files
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+use crate::models::tic_tac_toe;
+#[cfg(test)]
+use crate::plot;
+#[cfg(test)]
+use std::fs;
+
+#[test]
+fn line_chart_test() {
+    let series = vec![
+        (
+            "best".to_string(),
+            vec![(0.0, 1.0), (1.0, 3.0), (2.0, f32::NAN)],
+        ),
+        ("mean <all>".to_string(), vec![(0.0, 0.5), (1.0, 2.0)]),
+    ];
+
+    let svg = plot::line_chart(&series, "fitness", "generation", "fitness");
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert!(svg.contains("mean &lt;all&gt;"));
+    // the NaN point is left out
+    assert!(!svg.contains("NaN"));
+}
+
+#[test]
+fn bin_counts_test() {
+    let (counts, low, high) = plot::bin_counts(&[0.0, 0.1, 0.5, 1.0, f32::NAN], 2);
+    assert_eq!(counts, vec![2, 2]);
+    assert_eq!((low, high), (0.0, 1.0));
+
+    // equal values still get a range
+    let (counts, low, high) = plot::bin_counts(&[2.0, 2.0], 4);
+    assert_eq!(counts.iter().sum::<usize>(), 2);
+    assert!(low < 2.0 && high > 2.0);
+}
+
+#[test]
+fn histogram_test() {
+    let svg = plot::histogram(&[1.0, 2.0, 2.5, 3.0], 5, "weights");
+    // the background and one bar per bin
+    assert_eq!(svg.matches("<rect").count(), 6);
+    assert!(svg.contains(">weights<"));
+}
+
+#[test]
+fn fitness_curves_test() {
+    fs::write(
+        "plot_fitness_curves_test.csv",
+        "generation,best_fitness,mean_fitness,median_fitness,sigma\n0,1,0.5,0.4,\n1,2,1,0.9,\n",
+    )
+    .unwrap();
+    let svg = plot::fitness_curves("plot_fitness_curves_test.csv", "run");
+    let columns = plot::read_csv("plot_fitness_curves_test.csv").unwrap();
+    fs::remove_file("plot_fitness_curves_test.csv").unwrap();
+
+    assert_eq!(svg.unwrap().matches("<polyline").count(), 3);
+    assert_eq!(columns[1].1, vec![1.0, 2.0]);
+    assert!(columns[4].1[0].is_nan());
+    assert!(plot::fitness_curves("plot_fitness_curves_test_missing.csv", "run").is_err());
+}
+
+#[test]
+fn weight_histograms_test() {
+    let model = tic_tac_toe::new_gaussian_noise();
+
+    let files = plot::weight_histograms(&model, 20, "plot_weight_histograms_test");
+    let conv = fs::read_to_string("plot_weight_histograms_test/conv1.filters.svg");
+    fs::remove_dir_all("plot_weight_histograms_test").unwrap();
+
+    // 3 conv layers and 2 fully connected layers, no biases
+    assert_eq!(files.len(), 5);
+    assert!(files.iter().any(|f| f.ends_with("fc2.weights.svg")));
+    assert!(conv.unwrap().starts_with("<svg"));
+}